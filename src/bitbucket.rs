@@ -1,4 +1,7 @@
+use clap::ValueEnum;
 use clap_derive::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
 
 #[derive(Debug, Args)]
 pub struct PullRequestArgs {
@@ -22,3 +25,214 @@ pub struct CommitArgs {
     #[arg(short = 'k', long)]
     pub project_key: String,
 }
+
+#[derive(Clone, Debug, Args)]
+pub struct RepositoryArgs {
+    /// The repository slug. e.g. `my-repository-name`
+    #[arg(short, long)]
+    pub repository_slug: String,
+    /// The project key
+    #[arg(short = 'k', long)]
+    pub project_key: String,
+}
+
+#[derive(Clone, Debug, Args)]
+pub struct ProjectArgs {
+    /// The project key
+    #[arg(short = 'k', long)]
+    pub project_key: String,
+}
+
+/// A project, or a repository in it when the slug is given
+#[derive(Clone, Debug, Args)]
+pub struct ScopeArgs {
+    /// The project key
+    #[arg(short = 'k', long)]
+    pub project_key: String,
+    /// The repository slug. e.g. `my-repository-name`. Without it, the project is used.
+    #[arg(short, long)]
+    pub repository_slug: Option<String>,
+}
+
+impl ScopeArgs {
+    /// The path of the project or repository in a URI, e.g. `projects/KEY/repos/my-repository`
+    pub fn path(&self) -> String {
+        match &self.repository_slug {
+            Some(repository_slug) => {
+                format!("projects/{}/repos/{}", self.project_key, repository_slug)
+            }
+            None => format!("projects/{}", self.project_key),
+        }
+    }
+}
+
+impl From<&RepositoryArgs> for ScopeArgs {
+    fn from(repository_args: &RepositoryArgs) -> Self {
+        ScopeArgs {
+            project_key: repository_args.project_key.clone(),
+            repository_slug: Some(repository_args.repository_slug.clone()),
+        }
+    }
+}
+
+/// The order in which to list branches or tags
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum RefOrder {
+    /// Order by name
+    Alphabetical,
+    /// Order by most recently modified first
+    Modification,
+}
+
+impl RefOrder {
+    /// The value of the `orderBy` query parameter
+    pub fn as_param(&self) -> &'static str {
+        match self {
+            RefOrder::Alphabetical => "ALPHABETICAL",
+            RefOrder::Modification => "MODIFICATION",
+        }
+    }
+}
+
+/// What a branch restriction, default reviewer condition or required build applies to
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct RefMatcher {
+    #[serde(rename = "type")]
+    pub matcher_type: MatcherType,
+    /// A branch name, a pattern like `release/*`, a branching model category like `FEATURE` or
+    /// a branching model branch like `production`. Not needed for `any`.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub id: String,
+}
+
+/// The kinds of ref matchers
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, ValueEnum,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum MatcherType {
+    /// Any ref
+    Any,
+    /// A single branch
+    Branch,
+    /// Refs matching a pattern
+    Pattern,
+    /// A branching model category, e.g. `FEATURE` or `RELEASE`
+    ModelCategory,
+    /// A branching model branch, e.g. `production` or `development`
+    ModelBranch,
+}
+
+impl MatcherType {
+    /// The id of the matcher type in the Bitbucket API
+    pub fn as_param(&self) -> &'static str {
+        match self {
+            MatcherType::Any => "ANY_REF",
+            MatcherType::Branch => "BRANCH",
+            MatcherType::Pattern => "PATTERN",
+            MatcherType::ModelCategory => "MODEL_CATEGORY",
+            MatcherType::ModelBranch => "MODEL_BRANCH",
+        }
+    }
+
+    fn from_param(id: &str) -> Option<MatcherType> {
+        match id {
+            "ANY_REF" => Some(MatcherType::Any),
+            "BRANCH" => Some(MatcherType::Branch),
+            "PATTERN" => Some(MatcherType::Pattern),
+            "MODEL_CATEGORY" => Some(MatcherType::ModelCategory),
+            "MODEL_BRANCH" => Some(MatcherType::ModelBranch),
+            _ => None,
+        }
+    }
+}
+
+impl RefMatcher {
+    pub fn new(matcher_type: MatcherType, id: &str) -> Self {
+        let id = match matcher_type {
+            MatcherType::Any => String::new(),
+            // the API wants the full ref name of a branch
+            MatcherType::Branch if !id.starts_with("refs/") => format!("refs/heads/{}", id),
+            _ => id.to_string(),
+        };
+        RefMatcher { matcher_type, id }
+    }
+
+    /// Read a matcher as returned by the Bitbucket API, e.g.
+    /// `{"id": "refs/heads/main", "type": {"id": "BRANCH"}}`
+    pub fn from_api(matcher: &Value) -> Option<RefMatcher> {
+        let matcher_type = MatcherType::from_param(matcher.pointer("/type/id")?.as_str()?)?;
+        let id = matcher
+            .get("id")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        Some(RefMatcher::new(matcher_type, id))
+    }
+
+    /// The matcher as the Bitbucket API expects it in request bodies
+    pub fn to_api(&self) -> Value {
+        let id = match self.matcher_type {
+            MatcherType::Any => "ANY_REF_MATCHER_ID",
+            _ => &self.id,
+        };
+        json!({ "id": id, "type": { "id": self.matcher_type.as_param() } })
+    }
+
+    /// Restore the canonical form after deserializing a hand written matcher
+    pub fn normalize(&mut self) {
+        *self = RefMatcher::new(self.matcher_type, &self.id);
+    }
+}
+
+/// Parse a matcher given on the command line: `any`, or `<type>:<id>` such as `branch:main`,
+/// `pattern:release/*`, `model-category:FEATURE` or `model-branch:production`
+pub fn parse_ref_matcher(value: &str) -> Result<RefMatcher, String> {
+    if value == "any" {
+        return Ok(RefMatcher::new(MatcherType::Any, ""));
+    }
+
+    let invalid = || {
+        format!(
+            "invalid matcher `{}`, expected `any` or `<type>:<id>`, e.g. `branch:main`",
+            value
+        )
+    };
+    let (matcher_type, id) = value.split_once(':').ok_or_else(invalid)?;
+    let matcher_type = MatcherType::from_str(matcher_type, false).map_err(|_| invalid())?;
+    if id.is_empty() || matcher_type == MatcherType::Any {
+        return Err(invalid());
+    }
+
+    Ok(RefMatcher::new(matcher_type, id))
+}
+
+impl std::fmt::Display for RefMatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.matcher_type {
+            MatcherType::Any => write!(f, "any ref"),
+            _ => write!(
+                f,
+                "{} {}",
+                self.matcher_type.to_possible_value().unwrap().get_name(),
+                self.id
+            ),
+        }
+    }
+}
+
+/// Match a branch name against a glob pattern where `*` matches any run of characters and `?`
+/// matches a single character
+pub fn matches_glob(pattern: &str, name: &str) -> bool {
+    fn matches(pattern: &[char], name: &[char]) -> bool {
+        match pattern.split_first() {
+            None => name.is_empty(),
+            Some(('*', rest)) => (0..=name.len()).any(|i| matches(rest, &name[i..])),
+            Some(('?', rest)) => !name.is_empty() && matches(rest, &name[1..]),
+            Some((c, rest)) => name.first() == Some(c) && matches(rest, &name[1..]),
+        }
+    }
+
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    matches(&pattern, &name)
+}
@@ -0,0 +1,49 @@
+use crate::cmd::access_token::{self, TokenPermission, TokenScope};
+use crate::cmd::CommandResult;
+use bitbucket_server_rs::client::Client;
+use clap_derive::Args;
+
+#[derive(Debug, Args)]
+pub struct AccessTokenCreateArgs {
+    /// The name of the token, e.g. the CI job that uses it
+    #[arg(long)]
+    pub name: String,
+    /// A permission of the token. May be repeated, e.g. `--permission project-read --permission
+    /// repo-write`. Repository tokens only take repository permissions.
+    #[arg(long, value_enum, required = true)]
+    pub permission: Vec<TokenPermission>,
+    /// The number of days until the token expires. Without it, the token does not expire, unless
+    /// the server enforces a maximum.
+    #[arg(long)]
+    pub expiry_days: Option<u32>,
+    /// Print the token as a `.env` line setting this variable instead. Defaults to
+    /// `BITBUCKET_API_TOKEN`.
+    #[arg(long, value_name = "VARIABLE", num_args = 0..=1, default_missing_value = "BITBUCKET_API_TOKEN")]
+    pub env: Option<String>,
+}
+
+/// Create an access token and print its secret, which Bitbucket never shows again
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-authentication/#api-access-tokens-latest-projects-projectkey-put)
+pub async fn handle(
+    scope: &TokenScope,
+    create_args: &AccessTokenCreateArgs,
+    client: &Client,
+) -> CommandResult {
+    let permissions: Vec<String> = create_args
+        .permission
+        .iter()
+        .map(|permission| permission.as_api().to_string())
+        .collect();
+
+    let created = access_token::create(
+        scope,
+        &create_args.name,
+        &permissions,
+        create_args.expiry_days,
+        client,
+    )
+    .await?;
+
+    access_token::print_created(&created, create_args.env.as_deref())
+}
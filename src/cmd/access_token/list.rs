@@ -0,0 +1,14 @@
+use crate::cmd::access_token::{AccessToken, TokenScope, COLUMNS};
+use crate::cmd::CommandResult;
+use crate::output;
+use crate::rest;
+use bitbucket_server_rs::client::Client;
+
+/// List the access tokens of a user, project or repository
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-authentication/#api-access-tokens-latest-users-userslug-get)
+pub async fn handle(scope: &TokenScope, client: &Client) -> CommandResult {
+    let tokens: Vec<AccessToken> = rest::get_paged(client, &scope.uri(), &[]).await?;
+
+    output::print(&tokens, COLUMNS)
+}
@@ -0,0 +1,206 @@
+pub mod create;
+pub mod list;
+pub mod revoke;
+pub mod rotate;
+
+use crate::bitbucket::ScopeArgs;
+use crate::cmd::{auth, CommandResult};
+use crate::output;
+use crate::rest;
+use bitbucket_server_rs::client::{ApiError, Client};
+use clap::ValueEnum;
+use clap_derive::{Args, Subcommand};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// The columns of a table of access tokens
+pub(crate) const COLUMNS: &[&str] = &[
+    "id",
+    "name",
+    "permissions",
+    "createdDate",
+    "expiryDate",
+    "lastAuthenticated",
+];
+
+/// Common args for access token operations
+#[derive(Debug, Args)]
+pub struct AccessTokenArgs {
+    /// The user whose tokens to manage, by slug. Without it or a project, your own tokens are
+    /// managed.
+    #[arg(short, long, conflicts_with = "project_key")]
+    user: Option<String>,
+    /// The project key, to manage the tokens of a project
+    #[arg(short = 'k', long)]
+    project_key: Option<String>,
+    /// The repository slug. e.g. `my-repository-name`. With it, the tokens of the repository are
+    /// managed.
+    #[arg(short, long, requires = "project_key")]
+    repository_slug: Option<String>,
+    #[command(subcommand)]
+    command: AccessTokenSubcommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum AccessTokenSubcommands {
+    /// List the access tokens
+    List,
+    /// Create an access token and print its secret, which cannot be shown again
+    Create(create::AccessTokenCreateArgs),
+    /// Revoke an access token
+    Revoke(revoke::AccessTokenRevokeArgs),
+    /// Replace an access token by a new one with the same permissions, revoking the old one
+    /// after a grace period
+    Rotate(rotate::AccessTokenRotateArgs),
+}
+
+/// Whose access tokens are managed
+#[derive(Debug, Clone)]
+pub enum TokenScope {
+    /// A user, by slug
+    User(String),
+    /// A project, or a repository in it
+    Project(ScopeArgs),
+}
+
+impl TokenScope {
+    /// URI of the access tokens of the scope
+    pub fn uri(&self) -> String {
+        match self {
+            TokenScope::User(slug) => format!("access-tokens/latest/users/{}", slug),
+            TokenScope::Project(scope) => format!("access-tokens/latest/{}", scope.path()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TokenPermission {
+    ProjectRead,
+    ProjectWrite,
+    ProjectAdmin,
+    RepoRead,
+    RepoWrite,
+    RepoAdmin,
+}
+
+impl TokenPermission {
+    /// The permission as the API names it
+    pub fn as_api(&self) -> &'static str {
+        match self {
+            TokenPermission::ProjectRead => "PROJECT_READ",
+            TokenPermission::ProjectWrite => "PROJECT_WRITE",
+            TokenPermission::ProjectAdmin => "PROJECT_ADMIN",
+            TokenPermission::RepoRead => "REPO_READ",
+            TokenPermission::RepoWrite => "REPO_WRITE",
+            TokenPermission::RepoAdmin => "REPO_ADMIN",
+        }
+    }
+}
+
+/// An HTTP access token as returned by the Bitbucket API
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessToken {
+    pub id: String,
+    pub name: String,
+    /// e.g. `REPO_READ` or `PROJECT_ADMIN`
+    #[serde(default)]
+    pub permissions: Vec<String>,
+    /// Milliseconds since the epoch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_date: Option<i64>,
+    /// Milliseconds since the epoch. Tokens without one never expire.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiry_date: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_authenticated: Option<i64>,
+    /// The secret, only returned when the token is created
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+}
+
+/// Access token command handler
+pub async fn handle(args: &AccessTokenArgs, client: &Client) -> CommandResult {
+    let scope = match (&args.user, &args.project_key) {
+        (Some(user), _) => TokenScope::User(user.clone()),
+        (None, Some(project_key)) => TokenScope::Project(ScopeArgs {
+            project_key: project_key.clone(),
+            repository_slug: args.repository_slug.clone(),
+        }),
+        (None, None) => TokenScope::User(current_user_slug(client).await?),
+    };
+
+    match &args.command {
+        AccessTokenSubcommands::List => list::handle(&scope, client).await,
+        AccessTokenSubcommands::Create(create_args) => {
+            create::handle(&scope, create_args, client).await
+        }
+        AccessTokenSubcommands::Revoke(revoke_args) => {
+            revoke::handle(&scope, revoke_args, client).await
+        }
+        AccessTokenSubcommands::Rotate(rotate_args) => {
+            rotate::handle(&scope, rotate_args, client).await
+        }
+    }
+}
+
+/// Create an access token. Without an expiry, it never expires, unless the server sets a maximum.
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-authentication/#api-access-tokens-latest-users-userslug-put)
+pub(crate) async fn create(
+    scope: &TokenScope,
+    name: &str,
+    permissions: &[String],
+    expiry_days: Option<u32>,
+    client: &Client,
+) -> Result<AccessToken, ApiError> {
+    let mut body = json!({ "name": name, "permissions": permissions });
+    if let Some(expiry_days) = expiry_days {
+        body["expiryDays"] = json!(expiry_days);
+    }
+
+    rest::put(client, &scope.uri(), &body)
+        .await?
+        .ok_or(ApiError::ResponseError)
+}
+
+/// Revoke an access token
+pub(crate) async fn revoke(scope: &TokenScope, id: &str, client: &Client) -> Result<(), ApiError> {
+    rest::delete::<()>(client, &format!("{}/{}", scope.uri(), id), None).await
+}
+
+/// Print a new token along with its secret, or only as a `.env` line setting `env_variable`
+pub(crate) fn print_created(token: &AccessToken, env_variable: Option<&str>) -> CommandResult {
+    match env_variable {
+        Some(variable) => {
+            output::message(format!(
+                "{}={}",
+                variable,
+                token.token.as_deref().unwrap_or_default()
+            ));
+            Ok(())
+        }
+        None => {
+            let mut columns = COLUMNS.to_vec();
+            columns.push("token");
+            output::print(token, &columns)
+        }
+    }
+}
+
+/// The slug of the user the client authenticates as
+async fn current_user_slug(client: &Client) -> Result<String, ApiError> {
+    let name = auth::whoami(client).await?.name;
+
+    // the slug only differs from the name for names with special characters
+    let users: Vec<Value> =
+        rest::get_paged(client, "api/latest/users", &[("filter", name.clone())]).await?;
+    let slug = users
+        .iter()
+        .find(|user| user.get("name").and_then(Value::as_str) == Some(name.as_str()))
+        .and_then(|user| user.get("slug"))
+        .and_then(Value::as_str)
+        .map(str::to_string);
+
+    Ok(slug.unwrap_or(name))
+}
@@ -0,0 +1,25 @@
+use crate::cmd::access_token::{self, TokenScope};
+use crate::cmd::CommandResult;
+use crate::output;
+use bitbucket_server_rs::client::Client;
+use clap_derive::Args;
+
+#[derive(Debug, Args)]
+pub struct AccessTokenRevokeArgs {
+    /// The ID of the token to revoke
+    pub id: String,
+}
+
+/// Revoke an access token
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-authentication/#api-access-tokens-latest-users-userslug-tokenid-delete)
+pub async fn handle(
+    scope: &TokenScope,
+    revoke_args: &AccessTokenRevokeArgs,
+    client: &Client,
+) -> CommandResult {
+    access_token::revoke(scope, &revoke_args.id, client).await?;
+
+    output::message(format!("Access token {} revoked.", revoke_args.id));
+    Ok(())
+}
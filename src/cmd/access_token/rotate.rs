@@ -0,0 +1,93 @@
+use crate::cmd::access_token::{self, AccessToken, TokenScope};
+use crate::cmd::CommandResult;
+use crate::rest;
+use bitbucket_server_rs::client::{ApiError, Client};
+use clap_derive::Args;
+use std::time::Duration;
+
+const DAY_MILLIS: i64 = 24 * 60 * 60 * 1000;
+
+#[derive(Debug, Args)]
+pub struct AccessTokenRotateArgs {
+    /// The ID of the token to replace
+    pub id: String,
+    /// The name of the new token. Defaults to the name of the old one.
+    #[arg(long)]
+    pub name: Option<String>,
+    /// The number of days until the new token expires. Defaults to the lifetime of the old one.
+    #[arg(long)]
+    pub expiry_days: Option<u32>,
+    /// How long the old token keeps working, so that running jobs can switch to the new one.
+    /// e.g. `30s`, `10m` or `1h`, or `0s` to revoke it straight away
+    #[arg(long, value_parser = parse_grace_period, default_value = "5m")]
+    pub grace_period: Duration,
+    /// Print the new token as a `.env` line setting this variable instead. Defaults to
+    /// `BITBUCKET_API_TOKEN`.
+    #[arg(long, value_name = "VARIABLE", num_args = 0..=1, default_missing_value = "BITBUCKET_API_TOKEN")]
+    pub env: Option<String>,
+}
+
+/// Create a token with the permissions of an existing one, print it, and revoke the old token
+/// once the grace period is over
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-authentication/#api-access-tokens-latest-users-userslug-tokenid-get)
+pub async fn handle(
+    scope: &TokenScope,
+    rotate_args: &AccessTokenRotateArgs,
+    client: &Client,
+) -> CommandResult {
+    let old: AccessToken = rest::get(client, &format!("{}/{}", scope.uri(), rotate_args.id), &[])
+        .await?
+        .ok_or(ApiError::ResponseError)?;
+
+    let lifetime_days = match (old.created_date, old.expiry_date) {
+        (Some(created), Some(expiry)) => {
+            Some(((expiry - created + DAY_MILLIS - 1) / DAY_MILLIS).max(1) as u32)
+        }
+        _ => None,
+    };
+    let created = access_token::create(
+        scope,
+        rotate_args.name.as_deref().unwrap_or(&old.name),
+        &old.permissions,
+        rotate_args.expiry_days.or(lifetime_days),
+        client,
+    )
+    .await?;
+    access_token::print_created(&created, rotate_args.env.as_deref())?;
+
+    // progress goes to stderr, so that the new token can be redirected to a file
+    if !rotate_args.grace_period.is_zero() {
+        eprintln!(
+            "Revoking access token {} in {}s.",
+            old.id,
+            rotate_args.grace_period.as_secs()
+        );
+        tokio::time::sleep(rotate_args.grace_period).await;
+    }
+    access_token::revoke(scope, &old.id, client).await?;
+    eprintln!("Access token {} revoked.", old.id);
+
+    Ok(())
+}
+
+/// Parse a grace period such as `30s`, `10m` or `1h`
+fn parse_grace_period(value: &str) -> Result<Duration, String> {
+    let amount = value.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    let unit = &value[amount.len()..];
+    let invalid = || {
+        format!(
+            "invalid grace period `{}`, expected e.g. 30s, 10m or 1h",
+            value
+        )
+    };
+
+    let amount: u64 = amount.parse().map_err(|_| invalid())?;
+    let seconds = match unit {
+        "s" => Some(amount),
+        "m" => amount.checked_mul(60),
+        "h" => amount.checked_mul(60 * 60),
+        _ => None,
+    };
+    seconds.map(Duration::from_secs).ok_or_else(invalid)
+}
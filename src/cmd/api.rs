@@ -0,0 +1,218 @@
+use crate::cmd::{CommandError, CommandResult};
+use crate::output;
+use crate::rest;
+use bitbucket_server_rs::client::{ApiError, Client};
+use clap_derive::Args;
+use reqwest::header::{HeaderName, HeaderValue};
+use reqwest::{Method, Response};
+use serde_json::{Map, Value};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Args)]
+pub struct ApiArgs {
+    /// The HTTP method, e.g. `GET` or `POST`
+    #[arg(value_parser = parse_method)]
+    pub method: Method,
+    /// The path of the endpoint, relative to the `/rest` base URL. e.g.
+    /// `api/latest/projects/PROJ/repos`
+    pub path: String,
+    /// A `key=value` field. Sent as a query parameter for GET and DELETE, or in the JSON body
+    /// otherwise. `true`, `false`, `null` and numbers are sent as JSON values, and `@file` reads
+    /// the value from a file.
+    #[arg(short = 'F', long = "field", value_parser = parse_field)]
+    pub fields: Vec<(String, String)>,
+    /// A file with the request body, or `-` for stdin. Fields are then sent as query parameters.
+    #[arg(long)]
+    pub input: Option<PathBuf>,
+    /// Fetch every page of a paged collection and print their values as one list
+    #[arg(long)]
+    pub paginate: bool,
+    /// A `Name: value` header to add to the request
+    #[arg(short = 'H', long = "header", value_parser = parse_header)]
+    pub headers: Vec<(HeaderName, HeaderValue)>,
+    /// Print the status line and headers of the response before its body
+    #[arg(long)]
+    pub include: bool,
+}
+
+/// Call any REST endpoint with the configured server and token, for what the other commands do
+/// not cover
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/intro/)
+pub async fn handle(args: &ApiArgs, client: &Client) -> CommandResult {
+    let uri = relative_uri(&args.path, &client.base_path)?;
+    if args.paginate && args.method != Method::GET {
+        return Err(CommandError::ArgumentError(vec![
+            "--paginate only works with GET".to_string(),
+        ]));
+    }
+
+    let mut fields = Vec::new();
+    for (key, value) in &args.fields {
+        fields.push((key.clone(), field_value(value)?));
+    }
+
+    let in_query = args.input.is_some() || matches!(args.method, Method::GET | Method::DELETE);
+    let body = match &args.input {
+        Some(input) => Some(read_input(input)?),
+        None if !in_query && !fields.is_empty() => {
+            let object: Map<String, Value> = fields.iter().cloned().collect();
+            Some(Value::Object(object).to_string())
+        }
+        None => None,
+    };
+    let mut params: Vec<(String, String)> = match in_query {
+        true => fields
+            .iter()
+            .map(|(key, value)| match value {
+                Value::String(text) => (key.clone(), text.clone()),
+                value => (key.clone(), value.to_string()),
+            })
+            .collect(),
+        false => Vec::new(),
+    };
+
+    let mut values = Vec::new();
+    loop {
+        let mut request = rest::request(client, args.method.clone(), &uri).query(&params);
+        for (name, value) in &args.headers {
+            request = request.header(name, value);
+        }
+        if let Some(body) = &body {
+            let has_content_type = args
+                .headers
+                .iter()
+                .any(|(name, _)| name == reqwest::header::CONTENT_TYPE);
+            if !has_content_type {
+                request = request.header(reqwest::header::CONTENT_TYPE, "application/json");
+            }
+            request = request.body(body.clone());
+        }
+
+        let response = rest::send(client, request).await?;
+        if args.include {
+            print_head(&response);
+        }
+        let text = response.text().await.map_err(|_| ApiError::ResponseError)?;
+
+        if !args.paginate {
+            return print_body(&text);
+        }
+
+        let page: Value = serde_json::from_str(&text)
+            .map_err(|e| ApiError::DeserializationError(e.to_string()))?;
+        match page.get("values") {
+            Some(Value::Array(page_values)) => values.extend(page_values.iter().cloned()),
+            _ => {
+                return Err(CommandError::UnexpectedError(
+                    "--paginate needs a paged collection, with `values`".to_string(),
+                ))
+            }
+        }
+
+        let is_last_page = page.get("isLastPage").and_then(Value::as_bool);
+        match page.get("nextPageStart").and_then(Value::as_u64) {
+            Some(next) if is_last_page == Some(false) => {
+                params.retain(|(key, _)| key != "start");
+                params.push(("start".to_string(), next.to_string()));
+            }
+            _ => break,
+        }
+    }
+
+    output::print(&values, &[])
+}
+
+/// The path relative to the `/rest` base URL. Full URLs are accepted when they point at it,
+/// so that links from responses can be followed, but the token is never sent anywhere else.
+fn relative_uri(path: &str, base_path: &str) -> Result<String, CommandError> {
+    if path.starts_with("http://") || path.starts_with("https://") {
+        return match path.strip_prefix(base_path.trim_end_matches('/')) {
+            Some(relative) => Ok(relative.trim_start_matches('/').to_string()),
+            None => Err(CommandError::ArgumentError(vec![format!(
+                "{} is not on the server {}",
+                path, base_path
+            )])),
+        };
+    }
+
+    let path = path.trim_start_matches('/');
+    Ok(path.strip_prefix("rest/").unwrap_or(path).to_string())
+}
+
+fn read_input(input: &Path) -> Result<String, CommandError> {
+    let content = match input.to_str() {
+        Some("-") => std::io::read_to_string(std::io::stdin()),
+        _ => std::fs::read_to_string(input),
+    };
+    content.map_err(|e| {
+        CommandError::ArgumentError(vec![format!("Unable to read {}: {}", input.display(), e)])
+    })
+}
+
+/// The JSON value of a field, reading `@file` values from the file
+fn field_value(value: &str) -> Result<Value, CommandError> {
+    if let Some(path) = value.strip_prefix('@') {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            CommandError::ArgumentError(vec![format!("Unable to read {}: {}", path, e)])
+        })?;
+        return Ok(Value::String(content));
+    }
+
+    Ok(match value {
+        "true" => Value::Bool(true),
+        "false" => Value::Bool(false),
+        "null" => Value::Null,
+        _ => match value.parse::<i64>() {
+            Ok(number) => Value::from(number),
+            Err(_) => Value::String(value.to_string()),
+        },
+    })
+}
+
+fn print_head(response: &Response) {
+    output::message(format!("{:?} {}", response.version(), response.status()));
+    for (name, value) in response.headers() {
+        output::message(format!(
+            "{}: {}",
+            name,
+            String::from_utf8_lossy(value.as_bytes())
+        ));
+    }
+    output::message("");
+}
+
+/// Print a JSON body in the chosen output format, and anything else as it is
+fn print_body(text: &str) -> CommandResult {
+    if text.is_empty() {
+        return Ok(());
+    }
+    match serde_json::from_str::<Value>(text) {
+        Ok(value) => output::print(&value, &[]),
+        Err(_) => {
+            output::message(text.trim_end());
+            Ok(())
+        }
+    }
+}
+
+fn parse_method(value: &str) -> Result<Method, String> {
+    Method::from_bytes(value.to_uppercase().as_bytes()).map_err(|e| e.to_string())
+}
+
+fn parse_field(value: &str) -> Result<(String, String), String> {
+    match value.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(format!("invalid field `{}`, expected `key=value`", value)),
+    }
+}
+
+fn parse_header(value: &str) -> Result<(HeaderName, HeaderValue), String> {
+    let (name, value) = value
+        .split_once(':')
+        .ok_or_else(|| format!("invalid header `{}`, expected `Name: value`", value))?;
+    Ok((
+        HeaderName::from_bytes(name.trim().as_bytes()).map_err(|e| e.to_string())?,
+        HeaderValue::from_str(value.trim()).map_err(|e| e.to_string())?,
+    ))
+}
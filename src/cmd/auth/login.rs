@@ -0,0 +1,77 @@
+use crate::cmd::auth::{server, whoami};
+use crate::cmd::{CommandError, CommandResult, ServerArgs};
+use crate::config::{self, Auth, Config};
+use crate::credentials::SecretStore;
+use crate::output;
+use bitbucket_server_rs::client::Client;
+use std::io::{IsTerminal, Write};
+use std::process::Stdio;
+
+/// Check a token and save it for the server in the secret store. The token is taken from
+/// `--api-token-file`, `--api-token-stdin` or BITBUCKET_API_TOKEN, or else asked for.
+pub async fn handle(
+    server_args: &ServerArgs,
+    client: &Client,
+    store: &dyn SecretStore,
+) -> CommandResult {
+    let server = server(client)?;
+    let token = match config::secret(server_args)? {
+        Some(token) => token,
+        None => read_token(server)?,
+    };
+    if token.is_empty() {
+        return Err(CommandError::ArgumentError(vec![
+            "No token given".to_string()
+        ]));
+    }
+
+    let config = Config::new(
+        server.to_string(),
+        Auth::from_args(server_args, token.clone())?,
+    );
+    let identity = whoami(&config.client()?).await?;
+
+    store.set(server, &token)?;
+    output::message(format!(
+        "Logged in to {} as {}. The token is kept in the {} store.",
+        server,
+        identity.name,
+        store.name()
+    ));
+    Ok(())
+}
+
+/// Ask for the token on a terminal without echoing it, or read it from piped stdin
+fn read_token(server: &str) -> Result<String, CommandError> {
+    let stdin = std::io::stdin();
+    let terminal = stdin.is_terminal();
+
+    let mut echo_off = false;
+    if terminal {
+        eprint!("Token for {}: ", server);
+        std::io::stderr()
+            .flush()
+            .map_err(|e| CommandError::UnexpectedError(e.to_string()))?;
+        echo_off = stty("-echo");
+    }
+
+    let mut token = String::new();
+    let read = stdin.read_line(&mut token);
+    if echo_off {
+        stty("echo");
+        eprintln!();
+    }
+    read.map_err(|e| CommandError::UnexpectedError(e.to_string()))?;
+
+    Ok(token.trim().to_string())
+}
+
+/// Change the settings of the terminal, returning whether it worked
+fn stty(setting: &str) -> bool {
+    std::process::Command::new("stty")
+        .arg(setting)
+        .stdin(Stdio::inherit())
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false)
+}
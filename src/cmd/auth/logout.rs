@@ -0,0 +1,16 @@
+use crate::cmd::auth::server;
+use crate::cmd::CommandResult;
+use crate::credentials::SecretStore;
+use crate::output;
+use bitbucket_server_rs::client::Client;
+
+/// Remove the saved token of the server from the secret store
+pub async fn handle(client: &Client, store: &dyn SecretStore) -> CommandResult {
+    let server = server(client)?;
+
+    match store.delete(server)? {
+        true => output::message(format!("Logged out of {}.", server)),
+        false => output::message(format!("No token saved for {}.", server)),
+    }
+    Ok(())
+}
@@ -0,0 +1,85 @@
+pub mod login;
+pub mod logout;
+pub mod status;
+
+use crate::cmd::{CommandError, CommandResult, ServerArgs};
+use crate::config::{server_key, Settings};
+use crate::rest;
+use bitbucket_server_rs::client::{ApiError, Client};
+use clap_derive::{Args, Subcommand};
+use reqwest::Method;
+use serde_json::Value;
+
+/// Common args for auth operations
+#[derive(Debug, Args)]
+pub struct AuthArgs {
+    #[command(subcommand)]
+    command: AuthSubcommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum AuthSubcommands {
+    /// Check a token and save it for the server in the secret store
+    Login,
+    /// Remove the saved token of the server
+    Logout,
+    /// Check the token and show the user it authenticates
+    Status,
+}
+
+/// The user a client authenticates as
+#[derive(Debug)]
+pub(crate) struct Identity {
+    pub name: String,
+    pub id: Option<String>,
+    /// The application properties of the server, with its version
+    pub properties: Value,
+}
+
+/// Find out who the credentials of a client belong to. Bitbucket names the user in the headers of
+/// every authenticated response, so the cheap application properties endpoint is enough.
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-system-maintenance/#api-api-latest-application-properties-get)
+pub(crate) async fn whoami(client: &Client) -> Result<Identity, ApiError> {
+    let request = rest::request(client, Method::GET, "api/latest/application-properties");
+    let response = rest::send(client, request).await?;
+
+    let header = |name: &str| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    };
+    // anonymous requests succeed too, but without a user
+    let name = header("X-AUSERNAME").ok_or(ApiError::Unauthorized)?;
+    let id = header("X-AUSERID");
+
+    let properties = rest::read_json(response).await?.unwrap_or(Value::Null);
+    Ok(Identity {
+        name,
+        id,
+        properties,
+    })
+}
+
+/// The server a client talks to, as tokens are stored by it
+pub(crate) fn server(client: &Client) -> Result<&str, CommandError> {
+    match server_key(&client.base_path) {
+        "" => Err(CommandError::ArgumentError(vec![
+            "Set the server with --server or BITBUCKET_SERVER".to_string(),
+        ])),
+        server => Ok(server),
+    }
+}
+
+/// Auth command handler
+pub async fn handle(args: &AuthArgs, server_args: &ServerArgs, client: &Client) -> CommandResult {
+    let store = Settings::load()?.store()?;
+
+    match &args.command {
+        AuthSubcommands::Login => login::handle(server_args, client, store.as_ref()).await,
+        AuthSubcommands::Logout => logout::handle(client, store.as_ref()).await,
+        AuthSubcommands::Status => status::handle(client, store.as_ref()).await,
+    }
+}
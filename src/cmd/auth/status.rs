@@ -0,0 +1,31 @@
+use crate::cmd::auth::{server, whoami};
+use crate::cmd::CommandResult;
+use crate::credentials::SecretStore;
+use crate::output;
+use bitbucket_server_rs::client::Client;
+use serde_json::json;
+
+/// The columns of the table output
+const COLUMNS: &[&str] = &["server", "user", "version", "store", "saved"];
+
+/// Check the token and show the user it authenticates, the server's version and whether a token
+/// is saved for the server
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-system-maintenance/#api-api-latest-application-properties-get)
+pub async fn handle(client: &Client, store: &dyn SecretStore) -> CommandResult {
+    let server = server(client)?;
+    let saved = store.get(server)?.is_some();
+    let identity = whoami(client).await?;
+
+    output::print(
+        &json!({
+            "server": server,
+            "user": identity.name,
+            "userId": identity.id,
+            "version": identity.properties.get("version"),
+            "store": store.name(),
+            "saved": saved,
+        }),
+        COLUMNS,
+    )
+}
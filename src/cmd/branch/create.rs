@@ -0,0 +1,48 @@
+use crate::bitbucket::RepositoryArgs;
+use crate::cmd::branch::Branch;
+use crate::cmd::CommandResult;
+use crate::output;
+use crate::rest;
+use bitbucket_server_rs::client::Client;
+use clap_derive::Args;
+use serde_json::json;
+
+#[derive(Debug, Args)]
+pub struct BranchCreateArgs {
+    /// The name of the new branch. e.g. `release/1.2`
+    pub name: String,
+    /// The branch, tag or commit to create the branch from
+    #[arg(long)]
+    pub from: String,
+    /// An optional message to associate with the new branch
+    #[arg(long)]
+    pub message: Option<String>,
+}
+
+/// Create a branch
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-repository/#api-api-latest-projects-projectkey-repos-repositoryslug-branches-post)
+pub async fn handle(
+    repository_args: &RepositoryArgs,
+    create_args: &BranchCreateArgs,
+    client: &Client,
+) -> CommandResult {
+    let uri = format!(
+        "api/latest/projects/{}/repos/{}/branches",
+        repository_args.project_key, repository_args.repository_slug
+    );
+
+    let branch: Option<Branch> = rest::post(
+        client,
+        &uri,
+        &json!({
+            "name": create_args.name,
+            "startPoint": create_args.from,
+            "message": create_args.message,
+        }),
+    )
+    .await?;
+
+    output::print(&branch, super::COLUMNS)?;
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use crate::bitbucket::RepositoryArgs;
+use crate::cmd::branch::{branch_ref, Branch};
+use crate::cmd::CommandResult;
+use crate::output;
+use crate::rest;
+use bitbucket_server_rs::client::{ApiError, Client};
+use clap_derive::{Args, Subcommand};
+use serde_json::{json, Value};
+
+#[derive(Debug, Args)]
+pub struct BranchDefaultArgs {
+    #[command(subcommand)]
+    command: BranchDefaultSubcommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum BranchDefaultSubcommands {
+    /// Get the default branch
+    Get,
+    /// Set the default branch
+    Set(BranchDefaultSetArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct BranchDefaultSetArgs {
+    /// The branch to make the default. e.g. `main`
+    pub branch: String,
+}
+
+/// Default branch command handler
+pub async fn handle(
+    repository_args: &RepositoryArgs,
+    default_args: &BranchDefaultArgs,
+    client: &Client,
+) -> CommandResult {
+    match &default_args.command {
+        BranchDefaultSubcommands::Get => get(repository_args, client).await,
+        BranchDefaultSubcommands::Set(set_args) => set(repository_args, set_args, client).await,
+    }
+}
+
+/// Get the default branch
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-repository/#api-api-latest-projects-projectkey-repos-repositoryslug-default-branch-get)
+pub async fn get(repository_args: &RepositoryArgs, client: &Client) -> CommandResult {
+    let branch = fetch(repository_args, client).await?;
+
+    output::print(&branch, super::COLUMNS)?;
+    Ok(())
+}
+
+/// Set the default branch
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-repository/#api-api-latest-projects-projectkey-repos-repositoryslug-default-branch-put)
+pub async fn set(
+    repository_args: &RepositoryArgs,
+    set_args: &BranchDefaultSetArgs,
+    client: &Client,
+) -> CommandResult {
+    update(repository_args, &set_args.branch, client).await?;
+
+    output::message(format!("Default branch set to {}.", set_args.branch));
+    Ok(())
+}
+
+/// Fetch the default branch of a repository
+pub(crate) async fn fetch(
+    repository_args: &RepositoryArgs,
+    client: &Client,
+) -> Result<Branch, ApiError> {
+    rest::get(client, &uri(repository_args), &[])
+        .await?
+        .ok_or(ApiError::ResponseError)
+}
+
+/// Make `branch` the default branch of a repository
+pub(crate) async fn update(
+    repository_args: &RepositoryArgs,
+    branch: &str,
+    client: &Client,
+) -> Result<(), ApiError> {
+    let _: Option<Value> = rest::put(
+        client,
+        &uri(repository_args),
+        &json!({ "id": branch_ref(branch) }),
+    )
+    .await?;
+    Ok(())
+}
+
+fn uri(repository_args: &RepositoryArgs) -> String {
+    format!(
+        "api/latest/projects/{}/repos/{}/default-branch",
+        repository_args.project_key, repository_args.repository_slug
+    )
+}
@@ -0,0 +1,65 @@
+use crate::bitbucket::RepositoryArgs;
+use crate::cmd::branch::{branch_ref, default, is_branch};
+use crate::cmd::{CommandError, CommandResult};
+use crate::output;
+use crate::rest;
+use bitbucket_server_rs::client::Client;
+use clap_derive::Args;
+use serde_json::json;
+
+#[derive(Debug, Args)]
+pub struct BranchDeleteArgs {
+    /// The branch to delete. e.g. `feature/old-work`
+    pub name: String,
+    /// Only check whether the branch can be deleted
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+/// Delete a branch. The default branch is never deleted.
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-repository/#api-branch-utils-latest-projects-projectkey-repos-repositoryslug-branches-delete)
+pub async fn handle(
+    repository_args: &RepositoryArgs,
+    delete_args: &BranchDeleteArgs,
+    client: &Client,
+) -> CommandResult {
+    let default_branch = default::fetch(repository_args, client).await?;
+    if is_branch(&default_branch, &delete_args.name) {
+        return Err(CommandError::ArgumentError(vec![format!(
+            "Refusing to delete the default branch {}",
+            delete_args.name
+        )]));
+    }
+
+    delete(repository_args, &delete_args.name, delete_args.dry_run, client).await?;
+
+    if delete_args.dry_run {
+        output::message(format!("Branch {} can be deleted (dry run).", delete_args.name));
+    } else {
+        output::message(format!("Branch {} deleted.", delete_args.name));
+    }
+    Ok(())
+}
+
+/// Delete a branch through the branch utils API, which also supports dry runs
+pub(crate) async fn delete(
+    repository_args: &RepositoryArgs,
+    name: &str,
+    dry_run: bool,
+    client: &Client,
+) -> CommandResult {
+    let uri = format!(
+        "branch-utils/latest/projects/{}/repos/{}/branches",
+        repository_args.project_key, repository_args.repository_slug
+    );
+
+    rest::delete(
+        client,
+        &uri,
+        Some(&json!({ "name": branch_ref(name), "dryRun": dry_run })),
+    )
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use crate::bitbucket::RepositoryArgs;
+use crate::cmd::branch::{is_branch, list};
+use crate::cmd::CommandResult;
+use crate::output;
+use bitbucket_server_rs::client::{ApiError, Client};
+use clap_derive::Args;
+
+#[derive(Debug, Args)]
+pub struct BranchInfoArgs {
+    /// The branch to show. e.g. `main`
+    pub branch: String,
+}
+
+/// Show a single branch, including ahead/behind and latest commit metadata
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-repository/#api-api-latest-projects-projectkey-repos-repositoryslug-branches-get)
+pub async fn handle(
+    repository_args: &RepositoryArgs,
+    info_args: &BranchInfoArgs,
+    client: &Client,
+) -> CommandResult {
+    // the API has no single-branch endpoint, so filter the listing down to an exact match
+    let branch = list::fetch(repository_args, Some(&info_args.branch), true, None, client)
+        .await?
+        .into_iter()
+        .find(|branch| is_branch(branch, &info_args.branch))
+        .ok_or_else(|| {
+            ApiError::HttpClientError(404, format!("Branch {} does not exist", info_args.branch))
+        })?;
+
+    output::print(&branch, super::COLUMNS)?;
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use crate::bitbucket::{RefOrder, RepositoryArgs};
+use crate::cmd::branch::Branch;
+use crate::cmd::CommandResult;
+use crate::output;
+use crate::rest;
+use bitbucket_server_rs::client::{ApiError, Client};
+use clap_derive::Args;
+
+#[derive(Debug, Args)]
+pub struct BranchListArgs {
+    /// Only list branches whose name contains this text
+    #[arg(long)]
+    pub filter: Option<String>,
+    /// Include ahead/behind counts and latest commit metadata
+    #[arg(long)]
+    pub details: bool,
+    /// The order in which to list branches
+    #[arg(long, value_enum)]
+    pub order_by: Option<RefOrder>,
+}
+
+/// List the branches of a repository
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-repository/#api-api-latest-projects-projectkey-repos-repositoryslug-branches-get)
+pub async fn handle(
+    repository_args: &RepositoryArgs,
+    list_args: &BranchListArgs,
+    client: &Client,
+) -> CommandResult {
+    let branches = fetch(
+        repository_args,
+        list_args.filter.as_deref(),
+        list_args.details,
+        list_args.order_by,
+        client,
+    )
+    .await?;
+
+    output::print(&branches, super::COLUMNS)?;
+    Ok(())
+}
+
+/// Fetch all branches of a repository, following pagination
+pub(crate) async fn fetch(
+    repository_args: &RepositoryArgs,
+    filter: Option<&str>,
+    details: bool,
+    order_by: Option<RefOrder>,
+    client: &Client,
+) -> Result<Vec<Branch>, ApiError> {
+    let uri = format!(
+        "api/latest/projects/{}/repos/{}/branches",
+        repository_args.project_key, repository_args.repository_slug
+    );
+
+    let mut params = vec![("details", details.to_string())];
+    if let Some(filter) = filter {
+        params.push(("filterText", filter.to_string()));
+    }
+    if let Some(order_by) = order_by {
+        params.push(("orderBy", order_by.as_param().to_string()));
+    }
+
+    rest::get_paged(client, &uri, &params).await
+}
@@ -0,0 +1,118 @@
+pub mod create;
+pub mod default;
+pub mod delete;
+pub mod info;
+pub mod list;
+pub mod prune;
+
+use crate::bitbucket::RepositoryArgs;
+use crate::cmd::{CommandError, CommandResult};
+use bitbucket_server_rs::client::Client;
+use clap_derive::{Args, Subcommand};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The columns of a table of branches
+pub(crate) const COLUMNS: &[&str] = &["displayId", "latestCommit", "isDefault"];
+
+/// Common args for branch operations
+#[derive(Debug, Args)]
+pub struct BranchArgs {
+    /// The repository slug. e.g. `my-repository-name`. Required by every subcommand except
+    /// `prune`, which covers the whole project when it is omitted.
+    #[arg(short, long)]
+    repository_slug: Option<String>,
+    /// The project key
+    #[arg(short = 'k', long)]
+    project_key: String,
+    #[command(subcommand)]
+    command: BranchSubcommands,
+}
+
+impl BranchArgs {
+    fn repository_args(&self) -> Result<RepositoryArgs, CommandError> {
+        match &self.repository_slug {
+            Some(repository_slug) => Ok(RepositoryArgs {
+                project_key: self.project_key.clone(),
+                repository_slug: repository_slug.clone(),
+            }),
+            None => Err(CommandError::ArgumentError(vec![
+                "--repository-slug is required".to_string(),
+            ])),
+        }
+    }
+}
+
+#[derive(Debug, Subcommand)]
+enum BranchSubcommands {
+    /// List branches
+    List(list::BranchListArgs),
+    /// Create a branch from another branch, tag or commit
+    Create(create::BranchCreateArgs),
+    /// Delete a branch
+    Delete(delete::BranchDeleteArgs),
+    /// Get or set the default branch
+    Default(default::BranchDefaultArgs),
+    /// Show a single branch with its details
+    Info(info::BranchInfoArgs),
+    /// Report, and optionally delete, stale branches
+    Prune(prune::BranchPruneArgs),
+}
+
+/// A branch as returned by the Bitbucket API
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Branch {
+    /// The fully qualified ref, e.g. `refs/heads/main`
+    pub id: String,
+    /// The short name, e.g. `main`
+    pub display_id: String,
+    #[serde(rename = "type")]
+    pub ref_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latest_commit: Option<String>,
+    #[serde(default)]
+    pub is_default: bool,
+    /// Ahead/behind, latest commit and other metadata. Only present when details are requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Value>,
+}
+
+/// Branch command handler
+pub async fn handle(args: &BranchArgs, client: &Client) -> CommandResult {
+    match &args.command {
+        BranchSubcommands::List(list_args) => {
+            list::handle(&args.repository_args()?, list_args, client).await
+        }
+        BranchSubcommands::Create(create_args) => {
+            create::handle(&args.repository_args()?, create_args, client).await
+        }
+        BranchSubcommands::Delete(delete_args) => {
+            delete::handle(&args.repository_args()?, delete_args, client).await
+        }
+        BranchSubcommands::Default(default_args) => {
+            default::handle(&args.repository_args()?, default_args, client).await
+        }
+        BranchSubcommands::Info(info_args) => {
+            info::handle(&args.repository_args()?, info_args, client).await
+        }
+        BranchSubcommands::Prune(prune_args) => {
+            let repository_slug = args.repository_slug.as_deref();
+            prune::handle(&args.project_key, repository_slug, prune_args, client).await
+        }
+    }
+}
+
+/// Qualify a branch name as a `refs/heads/` ref. Fully qualified refs are returned as is.
+pub(crate) fn branch_ref(name: &str) -> String {
+    if name.starts_with("refs/") {
+        name.to_string()
+    } else {
+        format!("refs/heads/{}", name)
+    }
+}
+
+/// Whether `name` refers to `branch`, by either its short or fully qualified name
+pub(crate) fn is_branch(branch: &Branch, name: &str) -> bool {
+    branch.display_id == name || branch.id == branch_ref(name)
+}
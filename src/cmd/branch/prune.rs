@@ -0,0 +1,278 @@
+use crate::bitbucket::{matches_glob, RepositoryArgs};
+use crate::cmd::branch::{delete, list, Branch};
+use crate::cmd::repo;
+use crate::cmd::{CommandError, CommandResult};
+use crate::output;
+use crate::rest;
+use bitbucket_server_rs::client::{ApiError, Client};
+use chrono::{DateTime, TimeDelta, Utc};
+use clap_derive::Args;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+/// The columns of the table output
+const COLUMNS: &[&str] = &[
+    "repository",
+    "branch",
+    "lastModified",
+    "ahead",
+    "behind",
+    "deleted",
+    "error",
+];
+
+const LATEST_COMMIT_METADATA: &str =
+    "com.atlassian.bitbucket.server.bitbucket-branch:latest-commit-metadata";
+const AHEAD_BEHIND_METADATA: &str =
+    "com.atlassian.bitbucket.server.bitbucket-branch:ahead-behind-metadata-provider";
+
+#[derive(Clone, Debug, Args)]
+pub struct BranchPruneArgs {
+    /// Only prune branches whose latest commit is older than this. e.g. `90d`, `12w` or `48h`
+    #[arg(long, value_parser = parse_age)]
+    pub older_than: TimeDelta,
+    /// Only prune branches that are fully merged into the default branch
+    #[arg(long)]
+    pub merged: bool,
+    /// Never prune branches matching this pattern, e.g. `release/*`. May be repeated.
+    #[arg(long)]
+    pub exclude: Vec<String>,
+    /// Delete the stale branches. Without this, they are only reported.
+    #[arg(long)]
+    pub execute: bool,
+    /// The maximum number of repositories to process at the same time
+    #[arg(long, default_value_t = 4)]
+    pub parallelism: usize,
+}
+
+/// A branch that is old enough to be pruned
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StaleBranch {
+    pub repository: String,
+    pub branch: String,
+    pub latest_commit: Option<String>,
+    pub last_modified: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ahead: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub behind: Option<u64>,
+    pub deleted: bool,
+    /// Why the branch could not be deleted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PullRequestRefs {
+    from_ref: PullRequestRef,
+    to_ref: PullRequestRef,
+}
+
+#[derive(Deserialize)]
+struct PullRequestRef {
+    id: String,
+    repository: PullRequestRepository,
+}
+
+#[derive(Deserialize)]
+struct PullRequestRepository {
+    slug: String,
+}
+
+/// Report stale branches of one repository, or of every repository in a project, and delete
+/// them with `--execute`. Default branches and branches with an open pull request are kept.
+pub async fn handle(
+    project_key: &str,
+    repository_slug: Option<&str>,
+    prune_args: &BranchPruneArgs,
+    client: &Client,
+) -> CommandResult {
+    let cutoff = Utc::now()
+        .checked_sub_signed(prune_args.older_than)
+        .ok_or_else(|| {
+            CommandError::ArgumentError(vec!["--older-than is too far in the past".to_string()])
+        })?;
+    let repository_slugs = match repository_slug {
+        Some(repository_slug) => vec![repository_slug.to_string()],
+        None => repo::list::fetch_project(project_key, client)
+            .await?
+            .into_iter()
+            .map(|repository| repository.slug)
+            .collect(),
+    };
+
+    let permits = Arc::new(Semaphore::new(prune_args.parallelism.max(1)));
+    let mut tasks = JoinSet::new();
+
+    for repository_slug in repository_slugs {
+        let repository_args = RepositoryArgs {
+            project_key: project_key.to_string(),
+            repository_slug,
+        };
+        let prune_args = prune_args.clone();
+        let permits = permits.clone();
+        let client = client.clone();
+
+        tasks.spawn(async move {
+            let _permit = permits
+                .acquire_owned()
+                .await
+                .map_err(|e| CommandError::UnexpectedError(e.to_string()))?;
+            prune_repository(&repository_args, &prune_args, cutoff, &client).await
+        });
+    }
+
+    // let every repository finish so that a failure in one does not leave the others half done
+    let mut report = Vec::new();
+    let mut first_error = None;
+    while let Some(result) = tasks.join_next().await {
+        match result.map_err(|e| CommandError::UnexpectedError(e.to_string())) {
+            Ok(Ok((stale, error))) => {
+                report.extend(stale);
+                if let Some(e) = error {
+                    first_error.get_or_insert(e);
+                }
+            }
+            Ok(Err(e)) | Err(e) => {
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+
+    report.sort_by(|a, b| (&a.repository, &a.branch).cmp(&(&b.repository, &b.branch)));
+    output::print(&report, COLUMNS)?;
+
+    match first_error {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+/// The stale branches of one repository, and the first error deleting them. A branch that
+/// cannot be deleted does not stop the others, so the report lists every branch that was.
+async fn prune_repository(
+    repository_args: &RepositoryArgs,
+    prune_args: &BranchPruneArgs,
+    cutoff: DateTime<Utc>,
+    client: &Client,
+) -> Result<(Vec<StaleBranch>, Option<CommandError>), CommandError> {
+    let branches = list::fetch(repository_args, None, true, None, client).await?;
+    let pull_request_refs = open_pull_request_refs(repository_args, client).await?;
+
+    let mut stale = Vec::new();
+    let mut first_error = None;
+    for branch in branches {
+        if branch.is_default
+            || pull_request_refs.contains(&branch.id)
+            || prune_args
+                .exclude
+                .iter()
+                .any(|pattern| matches_glob(pattern, &branch.display_id))
+        {
+            continue;
+        }
+
+        // branches without commit metadata have an unknown age, so leave them alone
+        let Some(last_modified) = last_modified(&branch) else {
+            continue;
+        };
+        if last_modified >= cutoff {
+            continue;
+        }
+
+        let (ahead, behind) = ahead_behind(&branch);
+        if prune_args.merged && ahead != Some(0) {
+            continue;
+        }
+
+        let mut error = None;
+        if prune_args.execute {
+            if let Err(e) = delete::delete(repository_args, &branch.id, false, client).await {
+                error = Some(e.to_string());
+                first_error.get_or_insert(e);
+            }
+        }
+
+        stale.push(StaleBranch {
+            repository: repository_args.repository_slug.clone(),
+            branch: branch.display_id,
+            latest_commit: branch.latest_commit,
+            last_modified,
+            ahead,
+            behind,
+            deleted: prune_args.execute && error.is_none(),
+            error,
+        });
+    }
+
+    Ok((stale, first_error))
+}
+
+/// Refs of this repository that are the source or target of an open pull request
+async fn open_pull_request_refs(
+    repository_args: &RepositoryArgs,
+    client: &Client,
+) -> Result<HashSet<String>, ApiError> {
+    let uri = format!(
+        "api/latest/projects/{}/repos/{}/pull-requests",
+        repository_args.project_key, repository_args.repository_slug
+    );
+    let pull_requests: Vec<PullRequestRefs> =
+        rest::get_paged(client, &uri, &[("state", "OPEN".to_string())]).await?;
+
+    let mut refs = HashSet::new();
+    for pull_request in pull_requests {
+        // the source may live in a fork, in which case it is not one of our branches
+        if pull_request.from_ref.repository.slug == repository_args.repository_slug {
+            refs.insert(pull_request.from_ref.id);
+        }
+        refs.insert(pull_request.to_ref.id);
+    }
+
+    Ok(refs)
+}
+
+fn last_modified(branch: &Branch) -> Option<DateTime<Utc>> {
+    let commit = branch.metadata.as_ref()?.get(LATEST_COMMIT_METADATA)?;
+    let timestamp = commit
+        .get("committerTimestamp")
+        .or_else(|| commit.get("authorTimestamp"))?
+        .as_i64()?;
+
+    DateTime::from_timestamp_millis(timestamp)
+}
+
+fn ahead_behind(branch: &Branch) -> (Option<u64>, Option<u64>) {
+    let counts = branch
+        .metadata
+        .as_ref()
+        .and_then(|metadata| metadata.get(AHEAD_BEHIND_METADATA));
+    let count = |key| counts.and_then(|counts| counts.get(key)).and_then(|v| v.as_u64());
+
+    (count("ahead"), count("behind"))
+}
+
+/// Parse a positive age such as `90d`, `12w` or `48h`
+fn parse_age(value: &str) -> Result<TimeDelta, String> {
+    let amount = value.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+    let unit = &value[amount.len()..];
+    let invalid = || format!("invalid age `{}`, expected e.g. 90d, 12w or 48h", value);
+
+    let amount: i64 = amount
+        .parse()
+        .ok()
+        .filter(|amount| *amount > 0)
+        .ok_or_else(invalid)?;
+    match unit {
+        "h" => TimeDelta::try_hours(amount),
+        "d" => TimeDelta::try_days(amount),
+        "w" => TimeDelta::try_weeks(amount),
+        _ => None,
+    }
+    .ok_or_else(invalid)
+}
@@ -0,0 +1,83 @@
+use crate::bitbucket::{MatcherType, RefMatcher, ScopeArgs};
+use crate::cmd::branch_restrictions::{self, BranchRestriction, RestrictionType};
+use crate::cmd::CommandResult;
+use crate::output;
+use bitbucket_server_rs::client::Client;
+use clap_derive::Args;
+
+#[derive(Debug, Args)]
+pub struct BranchRestrictionsCreateArgs {
+    /// What to prevent on the matching branches
+    #[arg(long = "type", value_enum)]
+    pub restriction_type: RestrictionType,
+    /// The branches to restrict
+    #[command(flatten)]
+    pub matcher: MatcherArgs,
+    /// A user exempt from the restriction. May be repeated.
+    #[arg(long)]
+    pub user: Vec<String>,
+    /// A group exempt from the restriction. May be repeated.
+    #[arg(long)]
+    pub group: Vec<String>,
+    /// The id of an access key exempt from the restriction. May be repeated.
+    #[arg(long)]
+    pub access_key: Vec<u64>,
+}
+
+/// The refs a restriction applies to, named with exactly one of the options
+#[derive(Debug, Args)]
+#[group(required = true, multiple = false)]
+pub struct MatcherArgs {
+    /// A single branch, e.g. `main`
+    #[arg(long)]
+    pub branch: Option<String>,
+    /// Branches matching a pattern, e.g. `release/*`
+    #[arg(long)]
+    pub pattern: Option<String>,
+    /// A branching model category, e.g. `RELEASE` or `FEATURE`
+    #[arg(long)]
+    pub model_category: Option<String>,
+    /// A branching model branch, e.g. `production` or `development`
+    #[arg(long)]
+    pub model_branch: Option<String>,
+}
+
+impl MatcherArgs {
+    pub fn matcher(&self) -> RefMatcher {
+        let candidates = [
+            (MatcherType::Branch, &self.branch),
+            (MatcherType::Pattern, &self.pattern),
+            (MatcherType::ModelCategory, &self.model_category),
+            (MatcherType::ModelBranch, &self.model_branch),
+        ];
+
+        candidates
+            .into_iter()
+            .find_map(|(matcher_type, id)| {
+                id.as_ref().map(|id| RefMatcher::new(matcher_type, id))
+            })
+            // clap requires exactly one of the options
+            .unwrap_or_else(|| RefMatcher::new(MatcherType::Any, ""))
+    }
+}
+
+/// Create a branch restriction
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-repository/#api-branch-permissions-latest-projects-projectkey-repos-repositoryslug-restrictions-post)
+pub async fn handle(
+    scope: &ScopeArgs,
+    create_args: &BranchRestrictionsCreateArgs,
+    client: &Client,
+) -> CommandResult {
+    let restriction = BranchRestriction {
+        restriction_type: create_args.restriction_type,
+        matcher: create_args.matcher.matcher(),
+        users: create_args.user.clone(),
+        groups: create_args.group.clone(),
+        access_keys: create_args.access_key.clone(),
+    };
+    let created = branch_restrictions::create(scope, &restriction, client).await?;
+
+    output::print(&created, super::COLUMNS)?;
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use crate::bitbucket::ScopeArgs;
+use crate::cmd::branch_restrictions;
+use crate::cmd::CommandResult;
+use crate::output;
+use bitbucket_server_rs::client::Client;
+use clap_derive::Args;
+
+#[derive(Debug, Args)]
+pub struct BranchRestrictionsDeleteArgs {
+    /// The id of the restriction, as shown by `list`
+    pub id: u64,
+}
+
+/// Delete a branch restriction
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-repository/#api-branch-permissions-latest-projects-projectkey-repos-repositoryslug-restrictions-id-delete)
+pub async fn handle(
+    scope: &ScopeArgs,
+    delete_args: &BranchRestrictionsDeleteArgs,
+    client: &Client,
+) -> CommandResult {
+    branch_restrictions::delete(scope, delete_args.id, client).await?;
+
+    output::message(format!("Branch restriction {} deleted.", delete_args.id));
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use crate::bitbucket::ScopeArgs;
+use crate::cmd::branch_restrictions::{uri, ApiRestriction, Restriction};
+use crate::cmd::CommandResult;
+use crate::output;
+use crate::rest;
+use bitbucket_server_rs::client::{ApiError, Client};
+
+/// List the branch restrictions of a project or repository. A repository also lists the
+/// restrictions it inherits from its project.
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-repository/#api-branch-permissions-latest-projects-projectkey-repos-repositoryslug-restrictions-get)
+pub async fn handle(scope: &ScopeArgs, client: &Client) -> CommandResult {
+    let restrictions = fetch(scope, client).await?;
+
+    output::print(&restrictions, super::COLUMNS)?;
+    Ok(())
+}
+
+/// Fetch every branch restriction that applies to a project or repository
+pub(crate) async fn fetch(scope: &ScopeArgs, client: &Client) -> Result<Vec<Restriction>, ApiError> {
+    let restrictions: Vec<ApiRestriction> = rest::get_paged(client, &uri(scope), &[]).await?;
+
+    restrictions.into_iter().map(Restriction::try_from).collect()
+}
@@ -0,0 +1,209 @@
+pub mod create;
+pub mod delete;
+pub mod list;
+
+use crate::bitbucket::{RefMatcher, ScopeArgs};
+use crate::cmd::CommandResult;
+use crate::rest;
+use bitbucket_server_rs::client::{ApiError, Client};
+use clap::ValueEnum;
+use clap_derive::{Args, Subcommand};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// The columns of a table of branch restrictions
+pub(crate) const COLUMNS: &[&str] = &[
+    "id",
+    "scope",
+    "type",
+    "matcher.type",
+    "matcher.id",
+    "users",
+    "groups",
+    "accessKeys",
+];
+
+/// Common args for branch restriction operations
+#[derive(Debug, Args)]
+pub struct BranchRestrictionsArgs {
+    /// Refers to the project, or to a repository in it
+    #[command(flatten)]
+    scope: ScopeArgs,
+    #[command(subcommand)]
+    command: BranchRestrictionsSubcommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum BranchRestrictionsSubcommands {
+    /// List the branch restrictions
+    List,
+    /// Restrict what can be done to matching branches
+    Create(create::BranchRestrictionsCreateArgs),
+    /// Delete a branch restriction
+    Delete(delete::BranchRestrictionsDeleteArgs),
+}
+
+/// A restriction on matching refs, and who is exempt from it
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct BranchRestriction {
+    #[serde(rename = "type")]
+    pub restriction_type: RestrictionType,
+    pub matcher: RefMatcher,
+    /// Users exempt from the restriction
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub users: Vec<String>,
+    /// Groups exempt from the restriction
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<String>,
+    /// Ids of the access keys exempt from the restriction
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub access_keys: Vec<u64>,
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, ValueEnum,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum RestrictionType {
+    /// Prevent all changes
+    ReadOnly,
+    /// Prevent deletion
+    NoDeletes,
+    /// Prevent rewriting history
+    FastForwardOnly,
+    /// Prevent changes without a pull request
+    PullRequestOnly,
+}
+
+impl RestrictionType {
+    /// The restriction type in the Bitbucket API, which uses the same names
+    pub fn as_param(&self) -> &'static str {
+        match self {
+            RestrictionType::ReadOnly => "read-only",
+            RestrictionType::NoDeletes => "no-deletes",
+            RestrictionType::FastForwardOnly => "fast-forward-only",
+            RestrictionType::PullRequestOnly => "pull-request-only",
+        }
+    }
+}
+
+/// A branch restriction as stored on the server
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Restriction {
+    pub id: u64,
+    /// `PROJECT` or `REPOSITORY`, where the restriction is defined, if the server says
+    pub scope: Option<String>,
+    #[serde(flatten)]
+    pub restriction: BranchRestriction,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiRestriction {
+    id: u64,
+    #[serde(rename = "type")]
+    restriction_type: RestrictionType,
+    matcher: Value,
+    #[serde(default)]
+    users: Vec<ApiUser>,
+    #[serde(default)]
+    groups: Vec<String>,
+    #[serde(default)]
+    access_keys: Vec<ApiAccessKey>,
+    scope: Option<ApiScope>,
+}
+
+#[derive(Deserialize)]
+struct ApiUser {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct ApiAccessKey {
+    key: ApiKey,
+}
+
+#[derive(Deserialize)]
+struct ApiKey {
+    id: u64,
+}
+
+#[derive(Deserialize)]
+struct ApiScope {
+    #[serde(rename = "type")]
+    scope_type: String,
+}
+
+impl TryFrom<ApiRestriction> for Restriction {
+    type Error = ApiError;
+
+    fn try_from(restriction: ApiRestriction) -> Result<Self, Self::Error> {
+        let matcher = RefMatcher::from_api(&restriction.matcher).ok_or_else(|| {
+            ApiError::DeserializationError(format!("unknown ref matcher {}", restriction.matcher))
+        })?;
+        let mut users: Vec<String> = restriction.users.into_iter().map(|u| u.name).collect();
+        let mut groups = restriction.groups;
+        let mut access_keys: Vec<u64> = restriction.access_keys.iter().map(|k| k.key.id).collect();
+        users.sort();
+        groups.sort();
+        access_keys.sort();
+
+        Ok(Restriction {
+            id: restriction.id,
+            scope: restriction.scope.map(|scope| scope.scope_type),
+            restriction: BranchRestriction {
+                restriction_type: restriction.restriction_type,
+                matcher,
+                users,
+                groups,
+                access_keys,
+            },
+        })
+    }
+}
+
+/// Branch restrictions command handler
+pub async fn handle(args: &BranchRestrictionsArgs, client: &Client) -> CommandResult {
+    let scope = &args.scope;
+
+    match &args.command {
+        BranchRestrictionsSubcommands::List => list::handle(scope, client).await,
+        BranchRestrictionsSubcommands::Create(create_args) => {
+            create::handle(scope, create_args, client).await
+        }
+        BranchRestrictionsSubcommands::Delete(delete_args) => {
+            delete::handle(scope, delete_args, client).await
+        }
+    }
+}
+
+/// Create a branch restriction
+pub(crate) async fn create(
+    scope: &ScopeArgs,
+    restriction: &BranchRestriction,
+    client: &Client,
+) -> Result<Restriction, ApiError> {
+    let body = json!({
+        "type": restriction.restriction_type.as_param(),
+        "matcher": restriction.matcher.to_api(),
+        "users": restriction.users,
+        "groups": restriction.groups,
+        "accessKeyIds": restriction.access_keys,
+    });
+    let created: ApiRestriction = rest::post(client, &uri(scope), &body)
+        .await?
+        .ok_or(ApiError::ResponseError)?;
+
+    created.try_into()
+}
+
+/// Delete a branch restriction
+pub(crate) async fn delete(scope: &ScopeArgs, id: u64, client: &Client) -> Result<(), ApiError> {
+    rest::delete::<()>(client, &format!("{}/{}", uri(scope), id), None).await
+}
+
+fn uri(scope: &ScopeArgs) -> String {
+    format!("branch-permissions/latest/{}/restrictions", scope.path())
+}
@@ -1,8 +1,13 @@
 use crate::bitbucket::CommitArgs;
-use crate::cmd::{CommandError, CommandResult};
-use bitbucket_server_rs::client::{ApiRequest, Client};
+use crate::cmd::CommandResult;
+use crate::output;
+use crate::rest;
+use bitbucket_server_rs::api::build_status_get::BuildStatus;
+use bitbucket_server_rs::client::Client;
 use clap_derive::Args;
-use serde_json::json;
+
+/// The columns of the table output
+const COLUMNS: &[&str] = &["state", "key", "name", "url", "dateAdded"];
 
 // todo move to own module
 #[derive(Debug, Args)]
@@ -20,30 +25,20 @@ pub async fn handle(
     get_args: &BuildStatusGetArgs,
     client: &Client,
 ) -> CommandResult {
-    let client = client.clone();
-    let mut builder =
-        client
-            .api()
-            .build_status_get(&commit_args.project_key, &commit_args.commit_id, &commit_args.repository_slug);
-
+    let mut params = Vec::new();
     if let Some(key) = &get_args.key {
-        builder.key(key);
+        params.push(("key", key.clone()));
     }
 
-    let response = builder
-        .build()
-        .map_err(|e| CommandError::UnexpectedError(
-            format!("Failed to build request: {}", e),
-        ))?
-        .send()
-        .await;
+    let build_status: Option<BuildStatus> = rest::get(
+        client,
+        &format!(
+            "api/latest/projects/{}/repos/{}/commits/{}/builds",
+            commit_args.project_key, commit_args.repository_slug, commit_args.commit_id
+        ),
+        &params,
+    )
+    .await?;
 
-    match response {
-        Ok(build_status) => {
-            let json = json!(build_status).to_string();
-            println!("{}", json);
-            Ok(())
-        }
-        Err(err) => Err(err.into()),
-    }
+    output::print(&build_status, COLUMNS)
 }
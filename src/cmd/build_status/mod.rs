@@ -23,7 +23,7 @@ pub struct BuildStatusArgs {
 #[derive(Debug, Subcommand)]
 enum BuildStatusSubcommands {
     Get(get::BuildStatusGetArgs),
-    Store(store::BuildStatusStoreArgs),
+    Store(Box<store::BuildStatusStoreArgs>),
 }
 
 /// Build status command handler
@@ -1,9 +1,12 @@
 use crate::bitbucket::CommitArgs;
 use crate::cmd::CommandResult;
+use crate::output;
+use crate::rest;
 use bitbucket_server_rs::api::build_status::TestResults;
 use bitbucket_server_rs::api::build_status_post::BuildStatusPostPayload;
-use bitbucket_server_rs::client::{ApiError, ApiRequest, Client};
+use bitbucket_server_rs::client::{ApiError, Client};
 use clap_derive::Args;
+use reqwest::Method;
 
 // todo move to own module
 #[derive(Debug, Args)]
@@ -51,48 +54,35 @@ pub async fn handle(
     store_args: &BuildStatusStoreArgs,
     client: &Client,
 ) -> CommandResult {
-    let client = client.clone();
+    let payload = BuildStatusPostPayload {
+        url: store_args.url.to_owned(),
+        key: store_args.key.to_owned(),
+        state: store_args.state.to_owned().into(),
+        build_number: store_args.build_number.to_owned(),
+        description: store_args.description.to_owned(),
+        duration: store_args.duration,
+        name: store_args.name.to_owned(),
+        parent: store_args.parent.to_owned(),
+        reference: store_args.reference.to_owned(),
+        date_added: match store_args.date_added.to_owned() {
+            Some(date_added) => {
+                Some(date_added.parse().map_err(|_| ApiError::RequestError)?)
+            }
+            None => None,
+        },
+        test_results: store_args.test_results.to_owned().map(|test_results| TestResults {
+            successful: test_results[0],
+            failed: test_results[1],
+            skipped: test_results[2],
+        }),
+    };
+    let uri = format!(
+        "api/latest/projects/{}/repos/{}/commits/{}/builds",
+        commit_args.project_key, commit_args.repository_slug, commit_args.commit_id
+    );
 
-    let response = client
-        .api()
-        .build_status_post(
-            &commit_args.project_key,
-            &commit_args.repository_slug,
-            &commit_args.commit_id,
-            &BuildStatusPostPayload {
-                url: store_args.url.to_owned(),
-                key: store_args.key.to_owned(),
-                state: store_args.state.to_owned().into(),
-                build_number: store_args.build_number.to_owned(),
-                description: store_args.description.to_owned(),
-                duration: store_args.duration,
-                name: store_args.name.to_owned(),
-                parent: store_args.parent.to_owned(),
-                reference: store_args.reference.to_owned(),
-                date_added: match store_args.date_added.to_owned() {
-                    Some(date_added) => {
-                        Some(date_added.parse().map_err(|_| ApiError::RequestError)?)
-                    }
-                    None => None,
-                },
-                test_results: match store_args.test_results.to_owned() {
-                    Some(test_results) => Some(TestResults {
-                        successful: test_results[0],
-                        failed: test_results[1],
-                        skipped: test_results[2],
-                    }),
-                    None => None,
-                },
-            },
-        )
-        .send()
-        .await;
-
-    match response {
-        Ok(_) => {
-            println!("Build status stored.");
-            Ok(())
-        }
-        Err(err) => Err(err.into()),
-    }
+    // the response has no content
+    rest::send(client, rest::request(client, Method::POST, &uri).json(&payload)).await?;
+    output::message("Build status stored.");
+    Ok(())
 }
@@ -0,0 +1,43 @@
+use crate::bitbucket::{parse_ref_matcher, RefMatcher, ScopeArgs};
+use crate::cmd::default_reviewers::{self, DefaultReviewers};
+use crate::cmd::CommandResult;
+use crate::output;
+use bitbucket_server_rs::client::Client;
+use clap_derive::Args;
+
+#[derive(Debug, Args)]
+pub struct DefaultReviewersCreateArgs {
+    /// The source refs of the pull requests: `any`, or e.g. `branch:main`, `pattern:feature/*`,
+    /// `model-category:FEATURE` or `model-branch:development`
+    #[arg(long, value_parser = parse_ref_matcher, default_value = "any")]
+    pub source: RefMatcher,
+    /// The target refs of the pull requests, in the same form as `--source`
+    #[arg(long, value_parser = parse_ref_matcher, default_value = "any")]
+    pub target: RefMatcher,
+    /// The username of a reviewer. May be repeated.
+    #[arg(long, required = true)]
+    pub reviewer: Vec<String>,
+    /// How many of the reviewers must approve
+    #[arg(long, default_value_t = 0)]
+    pub required_approvals: u32,
+}
+
+/// Create a default reviewer condition
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-repository/#api-default-reviewers-latest-projects-projectkey-repos-repositoryslug-condition-post)
+pub async fn handle(
+    scope: &ScopeArgs,
+    create_args: &DefaultReviewersCreateArgs,
+    client: &Client,
+) -> CommandResult {
+    let condition = DefaultReviewers {
+        source: create_args.source.clone(),
+        target: create_args.target.clone(),
+        reviewers: create_args.reviewer.clone(),
+        required_approvals: create_args.required_approvals,
+    };
+    let created = default_reviewers::create(scope, &condition, client).await?;
+
+    output::print(&created, super::COLUMNS)?;
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use crate::bitbucket::ScopeArgs;
+use crate::cmd::default_reviewers;
+use crate::cmd::CommandResult;
+use crate::output;
+use bitbucket_server_rs::client::Client;
+use clap_derive::Args;
+
+#[derive(Debug, Args)]
+pub struct DefaultReviewersDeleteArgs {
+    /// The id of the condition, as shown by `list`
+    pub id: u64,
+}
+
+/// Delete a default reviewer condition
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-repository/#api-default-reviewers-latest-projects-projectkey-repos-repositoryslug-condition-id-delete)
+pub async fn handle(
+    scope: &ScopeArgs,
+    delete_args: &DefaultReviewersDeleteArgs,
+    client: &Client,
+) -> CommandResult {
+    default_reviewers::delete(scope, delete_args.id, client).await?;
+
+    output::message(format!(
+        "Default reviewer condition {} deleted.",
+        delete_args.id
+    ));
+    Ok(())
+}
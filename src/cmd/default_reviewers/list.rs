@@ -0,0 +1,27 @@
+use crate::bitbucket::ScopeArgs;
+use crate::cmd::default_reviewers::{uri, ApiCondition, Condition};
+use crate::cmd::CommandResult;
+use crate::output;
+use crate::rest;
+use bitbucket_server_rs::client::{ApiError, Client};
+
+/// List the default reviewer conditions of a project or repository. A repository also lists the
+/// conditions it inherits from its project.
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-repository/#api-default-reviewers-latest-projects-projectkey-repos-repositoryslug-conditions-get)
+pub async fn handle(scope: &ScopeArgs, client: &Client) -> CommandResult {
+    let conditions = fetch(scope, client).await?;
+
+    output::print(&conditions, super::COLUMNS)?;
+    Ok(())
+}
+
+/// Fetch every default reviewer condition that applies to a project or repository
+pub(crate) async fn fetch(scope: &ScopeArgs, client: &Client) -> Result<Vec<Condition>, ApiError> {
+    // this resource is not paged
+    let conditions: Vec<ApiCondition> = rest::get(client, &uri(scope, "conditions"), &[])
+        .await?
+        .unwrap_or_default();
+
+    conditions.into_iter().map(Condition::try_from).collect()
+}
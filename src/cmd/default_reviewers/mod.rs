@@ -0,0 +1,201 @@
+pub mod create;
+pub mod delete;
+pub mod list;
+pub mod update;
+
+use crate::bitbucket::{RefMatcher, ScopeArgs};
+use crate::cmd::CommandResult;
+use crate::rest;
+use bitbucket_server_rs::client::{ApiError, Client};
+use clap_derive::{Args, Subcommand};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// The columns of a table of default reviewer conditions
+pub(crate) const COLUMNS: &[&str] = &[
+    "id",
+    "scope",
+    "source.type",
+    "source.id",
+    "target.type",
+    "target.id",
+    "reviewers",
+    "requiredApprovals",
+];
+
+/// Common args for default reviewer operations
+#[derive(Debug, Args)]
+pub struct DefaultReviewersArgs {
+    /// Refers to the project, or to a repository in it
+    #[command(flatten)]
+    scope: ScopeArgs,
+    #[command(subcommand)]
+    command: DefaultReviewersSubcommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum DefaultReviewersSubcommands {
+    /// List the default reviewer conditions
+    List,
+    /// Add reviewers to pull requests between matching refs
+    Create(create::DefaultReviewersCreateArgs),
+    /// Change a default reviewer condition
+    Update(update::DefaultReviewersUpdateArgs),
+    /// Delete a default reviewer condition
+    Delete(delete::DefaultReviewersDeleteArgs),
+}
+
+/// Reviewers added to pull requests from `source` to `target`
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct DefaultReviewers {
+    pub source: RefMatcher,
+    pub target: RefMatcher,
+    /// Usernames of the reviewers
+    pub reviewers: Vec<String>,
+    #[serde(default)]
+    pub required_approvals: u32,
+}
+
+/// A default reviewer condition as stored on the server
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Condition {
+    pub id: u64,
+    /// `PROJECT` or `REPOSITORY`, where the condition is defined, if the server says
+    pub scope: Option<String>,
+    #[serde(flatten)]
+    pub condition: DefaultReviewers,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiCondition {
+    id: u64,
+    scope: Option<ApiScope>,
+    source_ref_matcher: Value,
+    target_ref_matcher: Value,
+    #[serde(default)]
+    reviewers: Vec<ApiUser>,
+    #[serde(default)]
+    required_approvals: u32,
+}
+
+#[derive(Deserialize)]
+struct ApiUser {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct ApiScope {
+    #[serde(rename = "type")]
+    scope_type: String,
+}
+
+impl TryFrom<ApiCondition> for Condition {
+    type Error = ApiError;
+
+    fn try_from(condition: ApiCondition) -> Result<Self, Self::Error> {
+        let matcher = |matcher: &Value| {
+            RefMatcher::from_api(matcher).ok_or_else(|| {
+                ApiError::DeserializationError(format!("unknown ref matcher {}", matcher))
+            })
+        };
+        let mut reviewers: Vec<String> = condition.reviewers.into_iter().map(|u| u.name).collect();
+        reviewers.sort();
+
+        Ok(Condition {
+            id: condition.id,
+            scope: condition.scope.map(|scope| scope.scope_type),
+            condition: DefaultReviewers {
+                source: matcher(&condition.source_ref_matcher)?,
+                target: matcher(&condition.target_ref_matcher)?,
+                reviewers,
+                required_approvals: condition.required_approvals,
+            },
+        })
+    }
+}
+
+/// Default reviewers command handler
+pub async fn handle(args: &DefaultReviewersArgs, client: &Client) -> CommandResult {
+    let scope = &args.scope;
+
+    match &args.command {
+        DefaultReviewersSubcommands::List => list::handle(scope, client).await,
+        DefaultReviewersSubcommands::Create(create_args) => {
+            create::handle(scope, create_args, client).await
+        }
+        DefaultReviewersSubcommands::Update(update_args) => {
+            update::handle(scope, update_args, client).await
+        }
+        DefaultReviewersSubcommands::Delete(delete_args) => {
+            delete::handle(scope, delete_args, client).await
+        }
+    }
+}
+
+/// Create a default reviewer condition
+pub(crate) async fn create(
+    scope: &ScopeArgs,
+    condition: &DefaultReviewers,
+    client: &Client,
+) -> Result<Condition, ApiError> {
+    let body = body(condition, client).await?;
+    let created: ApiCondition = rest::post(client, &uri(scope, "condition"), &body)
+        .await?
+        .ok_or(ApiError::ResponseError)?;
+
+    created.try_into()
+}
+
+/// Replace a default reviewer condition
+pub(crate) async fn update(
+    scope: &ScopeArgs,
+    id: u64,
+    condition: &DefaultReviewers,
+    client: &Client,
+) -> Result<Condition, ApiError> {
+    let body = body(condition, client).await?;
+    let updated: ApiCondition = rest::put(client, &uri(scope, &format!("condition/{}", id)), &body)
+        .await?
+        .ok_or(ApiError::ResponseError)?;
+
+    updated.try_into()
+}
+
+/// Delete a default reviewer condition
+pub(crate) async fn delete(scope: &ScopeArgs, id: u64, client: &Client) -> Result<(), ApiError> {
+    rest::delete::<()>(client, &uri(scope, &format!("condition/{}", id)), None).await
+}
+
+/// The REST API wants reviewers as full user objects, so look each username up
+async fn body(condition: &DefaultReviewers, client: &Client) -> Result<Value, ApiError> {
+    let mut reviewers = Vec::new();
+    for name in &condition.reviewers {
+        reviewers.push(user(name, client).await?);
+    }
+
+    Ok(json!({
+        "sourceMatcher": condition.source.to_api(),
+        "targetMatcher": condition.target.to_api(),
+        "reviewers": reviewers,
+        "requiredApprovals": condition.required_approvals,
+    }))
+}
+
+/// Look a user up by username
+async fn user(name: &str, client: &Client) -> Result<Value, ApiError> {
+    let users: Vec<Value> =
+        rest::get_paged(client, "api/latest/users", &[("filter", name.to_string())]).await?;
+
+    // the filter also matches display names and email addresses
+    users
+        .into_iter()
+        .find(|user| user.get("name").and_then(Value::as_str) == Some(name))
+        .ok_or_else(|| ApiError::HttpClientError(404, format!("User {} does not exist", name)))
+}
+
+fn uri(scope: &ScopeArgs, path: &str) -> String {
+    format!("default-reviewers/latest/{}/{}", scope.path(), path)
+}
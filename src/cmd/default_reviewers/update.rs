@@ -0,0 +1,78 @@
+use crate::bitbucket::{parse_ref_matcher, RefMatcher, ScopeArgs};
+use crate::cmd::default_reviewers::{self, list};
+use crate::cmd::{CommandError, CommandResult};
+use crate::output;
+use bitbucket_server_rs::client::Client;
+use clap_derive::Args;
+
+#[derive(Debug, Args)]
+pub struct DefaultReviewersUpdateArgs {
+    /// The id of the condition, as shown by `list`
+    pub id: u64,
+    /// The new source refs, e.g. `any` or `branch:main`
+    #[arg(long, value_parser = parse_ref_matcher)]
+    pub source: Option<RefMatcher>,
+    /// The new target refs, e.g. `any` or `branch:main`
+    #[arg(long, value_parser = parse_ref_matcher)]
+    pub target: Option<RefMatcher>,
+    /// Replace the reviewers with these usernames. May be repeated.
+    #[arg(long, conflicts_with_all = ["add_reviewer", "remove_reviewer"])]
+    pub reviewer: Vec<String>,
+    /// Add a reviewer. May be repeated.
+    #[arg(long)]
+    pub add_reviewer: Vec<String>,
+    /// Remove a reviewer. May be repeated.
+    #[arg(long)]
+    pub remove_reviewer: Vec<String>,
+    /// How many of the reviewers must approve
+    #[arg(long)]
+    pub required_approvals: Option<u32>,
+}
+
+/// Change the given fields of a default reviewer condition
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-repository/#api-default-reviewers-latest-projects-projectkey-repos-repositoryslug-condition-id-put)
+pub async fn handle(
+    scope: &ScopeArgs,
+    update_args: &DefaultReviewersUpdateArgs,
+    client: &Client,
+) -> CommandResult {
+    // the API replaces the whole condition, so start from the current one
+    let mut condition = list::fetch(scope, client)
+        .await?
+        .into_iter()
+        .find(|condition| condition.id == update_args.id)
+        .ok_or_else(|| {
+            CommandError::ArgumentError(vec![format!(
+                "Default reviewer condition {} does not exist here",
+                update_args.id
+            )])
+        })?
+        .condition;
+
+    if let Some(source) = &update_args.source {
+        condition.source = source.clone();
+    }
+    if let Some(target) = &update_args.target {
+        condition.target = target.clone();
+    }
+    if !update_args.reviewer.is_empty() {
+        condition.reviewers = update_args.reviewer.clone();
+    }
+    for reviewer in &update_args.add_reviewer {
+        if !condition.reviewers.contains(reviewer) {
+            condition.reviewers.push(reviewer.clone());
+        }
+    }
+    condition
+        .reviewers
+        .retain(|reviewer| !update_args.remove_reviewer.contains(reviewer));
+    if let Some(required_approvals) = update_args.required_approvals {
+        condition.required_approvals = required_approvals;
+    }
+
+    let updated = default_reviewers::update(scope, update_args.id, &condition, client).await?;
+
+    output::print(&updated, super::COLUMNS)?;
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use crate::bitbucket::RepositoryArgs;
+use crate::cmd::file::{at_param, uri};
+use crate::cmd::CommandResult;
+use crate::output;
+use crate::rest;
+use bitbucket_server_rs::client::{ApiError, Client};
+use clap_derive::Args;
+use serde::Deserialize;
+use serde_json::Value;
+
+/// The columns of the table output
+const COLUMNS: &[&str] = &[
+    "lineNumber",
+    "spannedLines",
+    "displayCommitId",
+    "author.name",
+    "authorTimestamp",
+];
+
+#[derive(Debug, Args)]
+pub struct FileBlameArgs {
+    /// The path of the file, relative to the repository root. e.g. `CODEOWNERS`
+    pub path: String,
+    /// The branch, tag or commit to blame the file at. Defaults to the default branch.
+    #[arg(long)]
+    pub at: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BlamePage {
+    #[serde(default)]
+    blame: Vec<Value>,
+    #[serde(default)]
+    is_last_page: bool,
+    next_page_start: Option<u32>,
+}
+
+/// Show the commit and author that last changed each range of lines in a file
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-repository/#api-api-latest-projects-projectkey-repos-repositoryslug-browse-path-get)
+pub async fn handle(
+    repository_args: &RepositoryArgs,
+    blame_args: &FileBlameArgs,
+    client: &Client,
+) -> CommandResult {
+    let uri = uri(repository_args, "browse", &blame_args.path);
+    let mut blame = Vec::new();
+    let mut start = 0;
+
+    loop {
+        let mut params = at_param(blame_args.at.as_deref());
+        params.push(("blame", "true".to_string()));
+        params.push(("start", start.to_string()));
+
+        let page: BlamePage = rest::get(client, &uri, &params)
+            .await?
+            .ok_or(ApiError::ResponseError)?;
+        blame.extend(page.blame);
+
+        match page.next_page_start {
+            Some(next) if !page.is_last_page => start = next,
+            _ => break,
+        }
+    }
+
+    output::print(&blame, COLUMNS)?;
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use crate::bitbucket::RepositoryArgs;
+use crate::cmd::file::{at_param, uri};
+use crate::cmd::{CommandError, CommandResult};
+use crate::rest;
+use bitbucket_server_rs::client::{ApiError, Client};
+use clap_derive::Args;
+use reqwest::Method;
+use serde::Deserialize;
+use std::io::Write;
+
+#[derive(Debug, Args)]
+pub struct FileCatArgs {
+    /// The path of the file, relative to the repository root. e.g. `version.txt`
+    pub path: String,
+    /// The branch, tag or commit to read the file at. Defaults to the default branch.
+    #[arg(long)]
+    pub at: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BrowsePage {
+    #[serde(default)]
+    lines: Vec<Line>,
+    #[serde(default)]
+    is_last_page: bool,
+    next_page_start: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct Line {
+    text: String,
+}
+
+/// Print the content of a file to stdout, byte for byte unless the server lacks the raw endpoint
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-repository/#api-api-latest-projects-projectkey-repos-repositoryslug-raw-path-get)
+pub async fn handle(
+    repository_args: &RepositoryArgs,
+    cat_args: &FileCatArgs,
+    client: &Client,
+) -> CommandResult {
+    let content = read(repository_args, &cat_args.path, cat_args.at.as_deref(), client).await?;
+
+    std::io::stdout()
+        .write_all(&content)
+        .map_err(|e| CommandError::UnexpectedError(format!("Failed to write output: {}", e)))
+}
+
+/// Read a file through the raw endpoint, falling back to the line-paged browse endpoint on
+/// servers that do not have it
+pub(crate) async fn read(
+    repository_args: &RepositoryArgs,
+    path: &str,
+    at: Option<&str>,
+    client: &Client,
+) -> Result<Vec<u8>, ApiError> {
+    match read_raw(repository_args, path, at, client).await {
+        Err(ApiError::HttpClientError(404 | 405, _)) => {
+            read_browse(repository_args, path, at, client).await
+        }
+        result => result,
+    }
+}
+
+async fn read_raw(
+    repository_args: &RepositoryArgs,
+    path: &str,
+    at: Option<&str>,
+    client: &Client,
+) -> Result<Vec<u8>, ApiError> {
+    let request = rest::request(client, Method::GET, &uri(repository_args, "raw", path))
+        .query(&at_param(at));
+
+    let response = rest::send(client, request).await?;
+    let content = response.bytes().await.map_err(|_| ApiError::ResponseError)?;
+
+    Ok(content.to_vec())
+}
+
+/// The browse endpoint only returns text, a page of lines at a time, without their line breaks
+async fn read_browse(
+    repository_args: &RepositoryArgs,
+    path: &str,
+    at: Option<&str>,
+    client: &Client,
+) -> Result<Vec<u8>, ApiError> {
+    let uri = uri(repository_args, "browse", path);
+    let mut lines = Vec::new();
+    let mut start = 0;
+
+    loop {
+        let mut params = at_param(at);
+        params.push(("start", start.to_string()));
+
+        let page: BrowsePage = rest::get(client, &uri, &params)
+            .await?
+            .ok_or(ApiError::ResponseError)?;
+        lines.extend(page.lines.into_iter().map(|line| line.text));
+
+        match page.next_page_start {
+            Some(next) if !page.is_last_page => start = next,
+            _ => break,
+        }
+    }
+
+    Ok(lines.join("\n").into_bytes())
+}
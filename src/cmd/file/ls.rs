@@ -0,0 +1,135 @@
+use crate::bitbucket::RepositoryArgs;
+use crate::cmd::file::{at_param, uri};
+use crate::cmd::CommandResult;
+use crate::output;
+use crate::rest::{self, Page};
+use bitbucket_server_rs::client::{ApiError, Client};
+use clap_derive::Args;
+use serde::{Deserialize, Serialize};
+
+/// The columns of the table output
+const COLUMNS: &[&str] = &["path", "type", "size"];
+
+#[derive(Debug, Args)]
+pub struct FileLsArgs {
+    /// The directory to list, relative to the repository root. Defaults to the root.
+    #[arg(default_value = "")]
+    pub path: String,
+    /// The branch, tag or commit to list the directory at. Defaults to the default branch.
+    #[arg(long)]
+    pub at: Option<String>,
+    /// List every file below the directory instead of only its direct children
+    #[arg(long)]
+    pub recursive: bool,
+}
+
+/// A file or directory
+#[derive(Debug, Serialize)]
+pub struct Entry {
+    /// The path relative to the repository root
+    pub path: String,
+    /// `FILE`, `DIRECTORY` or `SUBMODULE`
+    #[serde(rename = "type")]
+    pub entry_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct Directory {
+    children: Page<Child>,
+}
+
+#[derive(Deserialize)]
+struct Child {
+    path: ChildPath,
+    #[serde(rename = "type")]
+    child_type: String,
+    size: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct ChildPath {
+    #[serde(rename = "toString")]
+    to_string: String,
+}
+
+/// List the files in a directory
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-repository/#api-api-latest-projects-projectkey-repos-repositoryslug-browse-path-get)
+pub async fn handle(
+    repository_args: &RepositoryArgs,
+    ls_args: &FileLsArgs,
+    client: &Client,
+) -> CommandResult {
+    let at = ls_args.at.as_deref();
+    let entries = if ls_args.recursive {
+        list_recursive(repository_args, &ls_args.path, at, client).await?
+    } else {
+        list_children(repository_args, &ls_args.path, at, client).await?
+    };
+
+    output::print(&entries, COLUMNS)?;
+    Ok(())
+}
+
+/// The files endpoint lists every file below a directory, but not the directories themselves
+async fn list_recursive(
+    repository_args: &RepositoryArgs,
+    path: &str,
+    at: Option<&str>,
+    client: &Client,
+) -> Result<Vec<Entry>, ApiError> {
+    let files: Vec<String> =
+        rest::get_paged(client, &uri(repository_args, "files", path), &at_param(at)).await?;
+
+    Ok(files
+        .into_iter()
+        .map(|file| Entry {
+            path: join(path, &file),
+            entry_type: "FILE".to_string(),
+            size: None,
+        })
+        .collect())
+}
+
+/// The browse endpoint lists direct children, paged under `children`
+async fn list_children(
+    repository_args: &RepositoryArgs,
+    path: &str,
+    at: Option<&str>,
+    client: &Client,
+) -> Result<Vec<Entry>, ApiError> {
+    let uri = uri(repository_args, "browse", path);
+    let mut entries = Vec::new();
+    let mut start = 0;
+
+    loop {
+        let mut params = at_param(at);
+        params.push(("start", start.to_string()));
+
+        let directory: Directory = rest::get(client, &uri, &params)
+            .await?
+            .ok_or(ApiError::ResponseError)?;
+        let page = directory.children;
+        entries.extend(page.values.into_iter().map(|child| Entry {
+            path: join(path, &child.path.to_string),
+            entry_type: child.child_type,
+            size: child.size,
+        }));
+
+        match page.next_page_start {
+            Some(next) if !page.is_last_page => start = next,
+            _ => break,
+        }
+    }
+
+    Ok(entries)
+}
+
+fn join(directory: &str, path: &str) -> String {
+    match directory.trim_matches('/') {
+        "" => path.to_string(),
+        directory => format!("{}/{}", directory, path),
+    }
+}
@@ -0,0 +1,68 @@
+pub mod blame;
+pub mod cat;
+pub mod ls;
+pub mod put;
+
+use crate::bitbucket::RepositoryArgs;
+use crate::cmd::CommandResult;
+use crate::rest;
+use bitbucket_server_rs::client::Client;
+use clap_derive::{Args, Subcommand};
+
+/// Common args for file operations
+#[derive(Debug, Args)]
+pub struct FileArgs {
+    /// Refers to the repository
+    #[command(flatten)]
+    repository_args: RepositoryArgs,
+    #[command(subcommand)]
+    command: FileSubcommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum FileSubcommands {
+    /// Print the raw content of a file
+    ///
+    /// On servers without the raw file endpoint, the file is read line by line instead. The
+    /// output is then not byte-exact: line endings become `\n` and a trailing line break is
+    /// dropped.
+    Cat(cat::FileCatArgs),
+    /// List the files in a directory
+    Ls(ls::FileLsArgs),
+    /// Show who last changed each line of a file
+    Blame(blame::FileBlameArgs),
+    /// Commit new content for a file, read from stdin
+    Put(put::FilePutArgs),
+}
+
+/// File command handler
+pub async fn handle(args: &FileArgs, client: &Client) -> CommandResult {
+    let repository_args = &args.repository_args;
+
+    match &args.command {
+        FileSubcommands::Cat(cat_args) => cat::handle(repository_args, cat_args, client).await,
+        FileSubcommands::Ls(ls_args) => ls::handle(repository_args, ls_args, client).await,
+        FileSubcommands::Blame(blame_args) => {
+            blame::handle(repository_args, blame_args, client).await
+        }
+        FileSubcommands::Put(put_args) => put::handle(repository_args, put_args, client).await,
+    }
+}
+
+/// URI of a path under one of the repository's file endpoints, e.g. `browse` or `raw`
+pub(crate) fn uri(repository_args: &RepositoryArgs, endpoint: &str, path: &str) -> String {
+    let uri = format!(
+        "api/latest/projects/{}/repos/{}/{}",
+        repository_args.project_key, repository_args.repository_slug, endpoint
+    );
+
+    match rest::encode_path(path).as_str() {
+        "" => uri,
+        path => format!("{}/{}", uri, path),
+    }
+}
+
+/// The `at` query parameter, if a ref was given
+pub(crate) fn at_param(at: Option<&str>) -> Vec<(&'static str, String)> {
+    at.map(|at| vec![("at", at.to_string())]).unwrap_or_default()
+}
@@ -0,0 +1,84 @@
+use crate::bitbucket::RepositoryArgs;
+use crate::cmd::file::uri;
+use crate::cmd::{CommandError, CommandResult};
+use crate::output;
+use crate::rest;
+use bitbucket_server_rs::client::{ApiError, Client};
+use clap_derive::Args;
+use reqwest::multipart::{Form, Part};
+use reqwest::Method;
+use serde_json::Value;
+use std::io::Read;
+
+/// The columns of the table output
+const COLUMNS: &[&str] = &["displayId", "author.name", "message"];
+
+#[derive(Debug, Args)]
+pub struct FilePutArgs {
+    /// The path of the file, relative to the repository root. e.g. `VERSION`
+    pub path: String,
+    /// The branch to commit to
+    #[arg(long)]
+    pub branch: String,
+    /// The commit message
+    #[arg(short, long)]
+    pub message: String,
+    /// The commit the edit is based on. Required when changing an existing file; the edit is
+    /// rejected if the file has changed on the branch since this commit.
+    #[arg(long)]
+    pub source_commit: Option<String>,
+}
+
+/// Commit new content for a file, read from stdin
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-repository/#api-api-latest-projects-projectkey-repos-repositoryslug-browse-path-put)
+pub async fn handle(
+    repository_args: &RepositoryArgs,
+    put_args: &FilePutArgs,
+    client: &Client,
+) -> CommandResult {
+    let mut content = Vec::new();
+    std::io::stdin()
+        .read_to_end(&mut content)
+        .map_err(|e| CommandError::UnexpectedError(format!("Failed to read stdin: {}", e)))?;
+
+    put(repository_args, put_args, content, client).await
+}
+
+/// Commit `content` as the new content of the file
+pub async fn put(
+    repository_args: &RepositoryArgs,
+    put_args: &FilePutArgs,
+    content: Vec<u8>,
+    client: &Client,
+) -> CommandResult {
+    let file_name = put_args.path.rsplit('/').next().unwrap_or_default().to_string();
+    let mut form = Form::new()
+        .part("content", Part::bytes(content).file_name(file_name))
+        .text("branch", put_args.branch.clone())
+        .text("message", put_args.message.clone());
+    if let Some(source_commit) = &put_args.source_commit {
+        form = form.text("sourceCommitId", source_commit.clone());
+    }
+
+    let request = rest::request(client, Method::PUT, &uri(repository_args, "browse", &put_args.path))
+        .multipart(form);
+
+    let commit: Option<Value> = match rest::send(client, request).await {
+        Ok(response) => rest::read_json(response).await?,
+        // the file changed on the branch after the source commit
+        Err(ApiError::HttpClientError(409, message)) => {
+            return Err(CommandError::ArgumentError(vec![format!(
+                "{} has changed on {} since {}: {}",
+                put_args.path,
+                put_args.branch,
+                put_args.source_commit.as_deref().unwrap_or("the source commit"),
+                message
+            )]))
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    output::print(&commit, COLUMNS)?;
+    Ok(())
+}
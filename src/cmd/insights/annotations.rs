@@ -0,0 +1,162 @@
+use crate::bitbucket::CommitArgs;
+use crate::cmd::insights::{
+    add_annotations, commit_uri, report_uri, Annotation, AnnotationType, Severity,
+};
+use crate::cmd::CommandResult;
+use crate::output;
+use crate::rest;
+use bitbucket_server_rs::client::{ApiError, Client};
+use clap_derive::{Args, Subcommand};
+use serde_json::Value;
+
+/// The columns of the table output
+const COLUMNS: &[&str] = &["reportKey", "path", "line", "severity", "type", "message"];
+
+#[derive(Debug, Args)]
+pub struct InsightsAnnotationsArgs {
+    #[command(subcommand)]
+    command: InsightsAnnotationsSubcommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum InsightsAnnotationsSubcommands {
+    /// Add an annotation to a report
+    Add(InsightsAnnotationsAddArgs),
+    /// List the annotations of a report, or of every report of the commit
+    List(InsightsAnnotationsListArgs),
+    /// Delete annotations from a report
+    Delete(InsightsAnnotationsDeleteArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct InsightsAnnotationsAddArgs {
+    /// The key of the report
+    #[arg(long)]
+    pub key: String,
+    /// The path of the file, relative to the repository root
+    #[arg(long)]
+    pub path: String,
+    /// The line. Without it, the annotation is on the whole file.
+    #[arg(long)]
+    pub line: Option<u32>,
+    #[arg(long)]
+    pub severity: Severity,
+    #[arg(long = "type")]
+    pub annotation_type: Option<AnnotationType>,
+    #[arg(long)]
+    pub message: String,
+    /// A link to more details
+    #[arg(long)]
+    pub link: Option<String>,
+    /// An id to delete the annotation by later
+    #[arg(long)]
+    pub external_id: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct InsightsAnnotationsListArgs {
+    /// The key of the report. Without it, the annotations of every report are listed.
+    #[arg(long)]
+    pub key: Option<String>,
+    /// Only list the annotations on this file
+    #[arg(long)]
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct InsightsAnnotationsDeleteArgs {
+    /// The key of the report
+    #[arg(long)]
+    pub key: String,
+    /// Only delete the annotation with this external id. May be repeated. Without it, every
+    /// annotation of the report is deleted.
+    #[arg(long)]
+    pub external_id: Vec<String>,
+}
+
+/// Annotations command handler
+pub async fn handle(
+    commit_args: &CommitArgs,
+    annotations_args: &InsightsAnnotationsArgs,
+    client: &Client,
+) -> CommandResult {
+    match &annotations_args.command {
+        InsightsAnnotationsSubcommands::Add(add_args) => add(commit_args, add_args, client).await,
+        InsightsAnnotationsSubcommands::List(list_args) => {
+            list(commit_args, list_args, client).await
+        }
+        InsightsAnnotationsSubcommands::Delete(delete_args) => {
+            delete(commit_args, delete_args, client).await
+        }
+    }
+}
+
+/// Add an annotation to a report
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-builds-and-deployments/#api-insights-latest-projects-projectkey-repos-repositoryslug-commits-commitid-reports-key-annotations-post)
+pub async fn add(
+    commit_args: &CommitArgs,
+    add_args: &InsightsAnnotationsAddArgs,
+    client: &Client,
+) -> CommandResult {
+    let annotation = Annotation {
+        path: add_args.path.clone(),
+        line: add_args.line,
+        message: add_args.message.clone(),
+        severity: add_args.severity,
+        annotation_type: add_args.annotation_type,
+        link: add_args.link.clone(),
+        external_id: add_args.external_id.clone(),
+    };
+    add_annotations(commit_args, &add_args.key, &[annotation], client).await?;
+
+    output::message("Annotation added.");
+    Ok(())
+}
+
+/// List annotations
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-builds-and-deployments/#api-insights-latest-projects-projectkey-repos-repositoryslug-commits-commitid-annotations-get)
+pub async fn list(
+    commit_args: &CommitArgs,
+    list_args: &InsightsAnnotationsListArgs,
+    client: &Client,
+) -> CommandResult {
+    let uri = match &list_args.key {
+        Some(key) => format!("{}/annotations", report_uri(commit_args, key)),
+        None => format!("{}/annotations", commit_uri(commit_args)),
+    };
+    let mut params = Vec::new();
+    if let Some(path) = &list_args.path {
+        params.push(("path", path.clone()));
+    }
+
+    let annotations: Value = rest::get(client, &uri, &params)
+        .await?
+        .ok_or(ApiError::ResponseError)?;
+
+    output::print(&annotations["annotations"], COLUMNS)?;
+    Ok(())
+}
+
+/// Delete the annotations of a report
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-builds-and-deployments/#api-insights-latest-projects-projectkey-repos-repositoryslug-commits-commitid-reports-key-annotations-delete)
+pub async fn delete(
+    commit_args: &CommitArgs,
+    delete_args: &InsightsAnnotationsDeleteArgs,
+    client: &Client,
+) -> CommandResult {
+    let uri = format!("{}/annotations", report_uri(commit_args, &delete_args.key));
+    let params: Vec<(&str, String)> = delete_args
+        .external_id
+        .iter()
+        .map(|id| ("externalId", id.clone()))
+        .collect();
+
+    let request = rest::request(client, reqwest::Method::DELETE, &uri).query(&params);
+    rest::send(client, request).await?;
+
+    output::message("Annotations deleted.");
+    Ok(())
+}
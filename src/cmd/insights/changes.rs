@@ -0,0 +1,76 @@
+//! The lines a pull request touches, to limit annotations and coverage to them.
+
+use crate::bitbucket::PullRequestArgs;
+use crate::cmd::pull_request_changes::get::changed_paths;
+use crate::rest;
+use bitbucket_server_rs::client::{ApiError, Client};
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The added or changed lines of each file, by path
+pub type ChangedLines = BTreeMap<String, BTreeSet<u32>>;
+
+#[derive(Deserialize)]
+struct Diff {
+    #[serde(default)]
+    diffs: Vec<FileDiff>,
+}
+
+#[derive(Deserialize)]
+struct FileDiff {
+    #[serde(default)]
+    hunks: Vec<Hunk>,
+}
+
+#[derive(Deserialize)]
+struct Hunk {
+    #[serde(default)]
+    segments: Vec<Segment>,
+}
+
+#[derive(Deserialize)]
+struct Segment {
+    #[serde(rename = "type")]
+    segment_type: String,
+    #[serde(default)]
+    lines: Vec<Line>,
+}
+
+#[derive(Deserialize)]
+struct Line {
+    destination: u32,
+}
+
+/// Fetch the lines a pull request adds or changes. Files come from the pull request's change
+/// listing, and their lines from the diff of each file.
+pub async fn changed_lines(
+    pull_request: &PullRequestArgs,
+    client: &Client,
+) -> Result<ChangedLines, ApiError> {
+    let mut changed = ChangedLines::new();
+
+    for path in changed_paths(pull_request, client).await? {
+        let uri = format!(
+            "api/latest/projects/{}/repos/{}/pull-requests/{}/diff/{}",
+            pull_request.project_key,
+            pull_request.repository_slug,
+            pull_request.pull_request_id,
+            rest::encode_path(&path)
+        );
+        let diff: Diff = rest::get(client, &uri, &[("contextLines", "0".to_string())])
+            .await?
+            .ok_or(ApiError::ResponseError)?;
+
+        let lines = diff
+            .diffs
+            .iter()
+            .flat_map(|diff| &diff.hunks)
+            .flat_map(|hunk| &hunk.segments)
+            .filter(|segment| segment.segment_type == "ADDED")
+            .flat_map(|segment| segment.lines.iter().map(|line| line.destination))
+            .collect();
+        changed.insert(path, lines);
+    }
+
+    Ok(changed)
+}
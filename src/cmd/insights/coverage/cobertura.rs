@@ -0,0 +1,49 @@
+//! Cobertura XML, written by coverage.py, gcovr, JaCoCo converters, `cargo tarpaulin` and others.
+
+use crate::cmd::insights::coverage::Coverage;
+use std::path::Path;
+
+/// Parse a Cobertura report. File names are relative to the first `<source>`, when there is one.
+pub fn parse(content: &str) -> Result<Coverage, String> {
+    let document = roxmltree::Document::parse(content).map_err(|e| e.to_string())?;
+    let mut coverage = Coverage::default();
+    let source = document
+        .descendants()
+        .find(|node| node.has_tag_name("source"))
+        .and_then(|node| node.text())
+        .map(str::trim)
+        .filter(|source| !source.is_empty());
+
+    for class in document
+        .descendants()
+        .filter(|node| node.has_tag_name("class"))
+    {
+        let filename = class
+            .attribute("filename")
+            .ok_or("a class has no filename")?;
+        let path = match source {
+            Some(source) => Path::new(source)
+                .join(filename)
+                .to_string_lossy()
+                .to_string(),
+            None => filename.to_string(),
+        };
+
+        // the lines of the methods repeat those of the class
+        let lines = class
+            .children()
+            .filter(|node| node.has_tag_name("lines"))
+            .flat_map(|lines| lines.children())
+            .filter(|node| node.has_tag_name("line"));
+        for line in lines {
+            let number = line.attribute("number").and_then(|n| n.parse().ok());
+            let hits = line.attribute("hits").and_then(|n| n.parse().ok());
+            match (number, hits) {
+                (Some(number), Some(hits)) => coverage.add(&path, number, hits),
+                _ => return Err(format!("invalid line in {}", filename)),
+            }
+        }
+    }
+
+    Ok(coverage)
+}
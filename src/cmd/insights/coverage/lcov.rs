@@ -0,0 +1,32 @@
+//! The LCOV tracefile format, written by `genhtml`'s tools, `cargo llvm-cov --lcov`, jest, c8
+//! and many others.
+
+use crate::cmd::insights::coverage::Coverage;
+
+/// Parse a tracefile. Only line coverage (`DA` records) is read.
+pub fn parse(content: &str) -> Result<Coverage, String> {
+    let mut coverage = Coverage::default();
+    let mut file: Option<String> = None;
+
+    for (number, line) in content.lines().enumerate() {
+        let line = line.trim();
+        let invalid = || format!("line {}: invalid record `{}`", number + 1, line);
+
+        if let Some(path) = line.strip_prefix("SF:") {
+            file = Some(path.to_string());
+        } else if let Some(record) = line.strip_prefix("DA:") {
+            let path = file.as_ref().ok_or_else(invalid)?;
+            let mut fields = record.split(',');
+            let line_number = fields.next().and_then(|n| n.parse().ok());
+            let hits = fields.next().and_then(|n| n.parse().ok());
+            match (line_number, hits) {
+                (Some(line_number), Some(hits)) => coverage.add(path, line_number, hits),
+                _ => return Err(invalid()),
+            }
+        } else if line == "end_of_record" {
+            file = None;
+        }
+    }
+
+    Ok(coverage)
+}
@@ -0,0 +1,253 @@
+pub mod cobertura;
+pub mod lcov;
+
+use crate::bitbucket::{CommitArgs, PullRequestArgs};
+use crate::cmd::insights::changes::{changed_lines, ChangedLines};
+use crate::cmd::insights::import::{relative_path, working_dirs};
+use crate::cmd::insights::{
+    publish, Annotation, AnnotationType, DataField, DataType, Report, ReportResult, Severity,
+};
+use crate::cmd::{CommandError, CommandResult};
+use crate::output;
+use bitbucket_server_rs::client::Client;
+use clap_derive::Args;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Args)]
+pub struct InsightsCoverageArgs {
+    /// An LCOV tracefile
+    #[arg(
+        long,
+        required_unless_present = "cobertura",
+        conflicts_with = "cobertura"
+    )]
+    pub lcov: Option<PathBuf>,
+    /// A Cobertura XML report
+    #[arg(long)]
+    pub cobertura: Option<PathBuf>,
+    /// The key of the report
+    #[arg(long, default_value = "coverage")]
+    pub key: String,
+    /// The title of the report
+    #[arg(long, default_value = "Coverage")]
+    pub title: String,
+    /// The pull request whose changed lines the diff coverage is computed on, in the same
+    /// repository
+    #[arg(long)]
+    pub pull_request: Option<String>,
+    /// The report fails when less than this percentage of the changed lines is covered
+    #[arg(long, requires = "pull_request")]
+    pub min_diff_coverage: Option<f64>,
+    /// Annotate the changed lines that are not covered
+    #[arg(long, requires = "pull_request")]
+    pub annotate_uncovered: bool,
+    /// The root of the repository, which paths are made relative to. Defaults to the git
+    /// repository around the working directory.
+    #[arg(long)]
+    pub repo_root: Option<PathBuf>,
+    /// A link to the full coverage report
+    #[arg(long)]
+    pub link: Option<String>,
+    /// The URL of the logo to show with the report
+    #[arg(long)]
+    pub logo_url: Option<String>,
+}
+
+/// Parses a coverage report
+pub type Parser = fn(&str) -> Result<Coverage, String>;
+
+/// Line coverage: the hit count of every instrumented line, by path
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Coverage {
+    pub files: BTreeMap<String, BTreeMap<u32, u64>>,
+}
+
+impl Coverage {
+    /// Record hits of a line. A file can be listed more than once, e.g. once per test run.
+    pub fn add(&mut self, path: &str, line: u32, hits: u64) {
+        *self
+            .files
+            .entry(path.to_string())
+            .or_default()
+            .entry(line)
+            .or_default() += hits;
+    }
+
+    /// The number of covered and of instrumented lines
+    pub fn total(&self) -> (usize, usize) {
+        self.files
+            .values()
+            .flat_map(|lines| lines.values())
+            .fold((0, 0), |(covered, total), hits| {
+                (covered + usize::from(*hits > 0), total + 1)
+            })
+    }
+
+    /// The changed lines that are instrumented, with whether they are covered
+    pub fn changed(&self, changed: &ChangedLines) -> Vec<(String, u32, bool)> {
+        changed
+            .iter()
+            .filter_map(|(path, lines)| Some((path, lines, self.files.get(path)?)))
+            .flat_map(|(path, lines, hits)| {
+                lines
+                    .iter()
+                    .filter_map(move |line| Some((path.clone(), *line, *hits.get(line)? > 0)))
+            })
+            .collect()
+    }
+}
+
+/// Publish the total coverage of a commit, and the coverage of the lines a pull request changes,
+/// as a report
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-builds-and-deployments/#api-insights-latest-projects-projectkey-repos-repositoryslug-commits-commitid-reports-key-put)
+pub async fn handle(
+    commit_args: &CommitArgs,
+    coverage_args: &InsightsCoverageArgs,
+    client: &Client,
+) -> CommandResult {
+    let (file, parse): (&Path, Parser) = match (&coverage_args.lcov, &coverage_args.cobertura) {
+        (Some(file), _) => (file, lcov::parse),
+        (None, Some(file)) => (file, cobertura::parse),
+        (None, None) => {
+            return Err(CommandError::ArgumentError(vec![
+                "--lcov or --cobertura is required".to_string(),
+            ]))
+        }
+    };
+    let content = std::fs::read_to_string(file).map_err(|e| {
+        CommandError::ArgumentError(vec![format!("Unable to read {}: {}", file.display(), e)])
+    })?;
+    let parsed = parse(&content).map_err(|e| {
+        CommandError::ArgumentError(vec![format!(
+            "Invalid coverage report {}: {}",
+            file.display(),
+            e
+        )])
+    })?;
+
+    let (cwd, root) = working_dirs(coverage_args.repo_root.as_deref())?;
+    let mut coverage = Coverage::default();
+    for (path, lines) in parsed.files {
+        let path = relative_path(&path, &cwd, &root);
+        for (line, hits) in lines {
+            coverage.add(&path, line, hits);
+        }
+    }
+
+    let changed = match &coverage_args.pull_request {
+        Some(pull_request_id) => {
+            let pull_request = PullRequestArgs {
+                pull_request_id: pull_request_id.clone(),
+                repository_slug: commit_args.repository_slug.clone(),
+                project_key: commit_args.project_key.clone(),
+            };
+            Some(coverage.changed(&changed_lines(&pull_request, client).await?))
+        }
+        None => None,
+    };
+
+    let annotations: Vec<Annotation> = match (coverage_args.annotate_uncovered, &changed) {
+        (true, Some(changed)) => changed
+            .iter()
+            .filter(|(_, _, covered)| !covered)
+            .map(|(path, line, _)| Annotation {
+                path: path.clone(),
+                line: Some(*line),
+                message: "This changed line is not covered by tests.".to_string(),
+                severity: Severity::Low,
+                annotation_type: Some(AnnotationType::CodeSmell),
+                link: None,
+                external_id: Some(format!("uncovered:{}:{}", path, line)),
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    let report = report(coverage_args, &coverage, changed.as_deref());
+    let stored = publish(
+        commit_args,
+        &coverage_args.key,
+        &report,
+        &annotations,
+        client,
+    )
+    .await?;
+
+    output::print(&stored, super::REPORT_COLUMNS)?;
+    Ok(())
+}
+
+fn report(
+    coverage_args: &InsightsCoverageArgs,
+    coverage: &Coverage,
+    changed: Option<&[(String, u32, bool)]>,
+) -> Report {
+    let (covered, total) = coverage.total();
+    let mut details = format!(
+        "{} of {} lines covered ({}%).",
+        covered,
+        total,
+        percentage(covered, total)
+    );
+    let mut data = vec![DataField {
+        title: "Coverage".to_string(),
+        data_type: DataType::Percentage,
+        value: json!(percentage(covered, total)),
+    }];
+    let mut result = None;
+
+    if let Some(changed) = changed {
+        let changed_covered = changed.iter().filter(|(_, _, covered)| *covered).count();
+        let changed_total = changed.len();
+
+        if changed_total == 0 {
+            details.push_str(" No changed line is instrumented.");
+        } else {
+            let diff_coverage = percentage(changed_covered, changed_total);
+            details.push_str(&format!(
+                " {} of {} changed lines covered ({}%).",
+                changed_covered, changed_total, diff_coverage
+            ));
+            data.push(DataField {
+                title: "Diff coverage".to_string(),
+                data_type: DataType::Percentage,
+                value: json!(diff_coverage),
+            });
+        }
+        data.push(DataField {
+            title: "Changed lines covered".to_string(),
+            data_type: DataType::Text,
+            value: json!(format!("{}/{}", changed_covered, changed_total)),
+        });
+
+        // nothing to cover is nothing missed, and the rounding is only for display
+        result = coverage_args.min_diff_coverage.map(|min| {
+            let exact = changed_covered as f64 * 100.0 / changed_total as f64;
+            match changed_total > 0 && exact < min {
+                true => ReportResult::Fail,
+                false => ReportResult::Pass,
+            }
+        });
+    }
+
+    Report {
+        title: coverage_args.title.clone(),
+        details: Some(details),
+        result,
+        data,
+        reporter: None,
+        link: coverage_args.link.clone(),
+        logo_url: coverage_args.logo_url.clone(),
+    }
+}
+
+/// A percentage with one decimal, 100 when there is nothing to cover
+fn percentage(covered: usize, total: usize) -> f64 {
+    match total {
+        0 => 100.0,
+        _ => (covered as f64 * 1000.0 / total as f64).round() / 10.0,
+    }
+}
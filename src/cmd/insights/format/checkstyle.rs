@@ -0,0 +1,42 @@
+//! Checkstyle XML, which many linters besides Checkstyle can emit.
+
+use crate::cmd::insights::format::{severity, Findings};
+use crate::cmd::insights::{Annotation, AnnotationType};
+
+/// Parse a Checkstyle report
+pub fn parse(content: &str) -> Result<Findings, String> {
+    let document = roxmltree::Document::parse(content).map_err(|e| e.to_string())?;
+    let mut findings = Findings {
+        tool: Some("checkstyle".to_string()),
+        annotations: Vec::new(),
+    };
+
+    for file in document
+        .descendants()
+        .filter(|node| node.has_tag_name("file"))
+    {
+        let Some(path) = file.attribute("name") else {
+            continue;
+        };
+
+        for error in file.children().filter(|node| node.has_tag_name("error")) {
+            let level = error.attribute("severity").unwrap_or("error");
+            if level == "ignore" {
+                continue;
+            }
+            let source = error.attribute("source").map(str::to_string);
+
+            findings.annotations.push(Annotation {
+                path: path.to_string(),
+                line: error.attribute("line").and_then(|line| line.parse().ok()),
+                message: error.attribute("message").unwrap_or_default().to_string(),
+                severity: severity(level),
+                annotation_type: Some(AnnotationType::CodeSmell),
+                link: None,
+                external_id: source,
+            });
+        }
+    }
+
+    Ok(findings)
+}
@@ -0,0 +1,90 @@
+//! The output of `cargo clippy --message-format json`: one JSON message per line.
+
+use crate::cmd::insights::format::{severity, Findings};
+use crate::cmd::insights::{Annotation, AnnotationType, Severity};
+use serde::Deserialize;
+use std::collections::HashSet;
+
+#[derive(Deserialize)]
+struct CargoMessage {
+    reason: String,
+    message: Option<Diagnostic>,
+}
+
+#[derive(Deserialize)]
+struct Diagnostic {
+    message: String,
+    level: String,
+    code: Option<Code>,
+    #[serde(default)]
+    spans: Vec<Span>,
+}
+
+#[derive(Deserialize)]
+struct Code {
+    code: String,
+}
+
+#[derive(Deserialize)]
+struct Span {
+    file_name: String,
+    line_start: u32,
+    is_primary: bool,
+}
+
+/// Parse the messages of a clippy run. Lines that are not JSON, such as cargo's progress
+/// output, are skipped.
+pub fn parse(content: &str) -> Result<Findings, String> {
+    let mut findings = Findings {
+        tool: Some("clippy".to_string()),
+        annotations: Vec::new(),
+    };
+    // a lint in code shared by several targets is reported once per target
+    let mut seen = HashSet::new();
+
+    for line in content.lines().filter(|line| line.starts_with('{')) {
+        let message: CargoMessage = serde_json::from_str(line).map_err(|e| e.to_string())?;
+        let Some(diagnostic) = message
+            .message
+            .filter(|_| message.reason == "compiler-message")
+        else {
+            continue;
+        };
+        // summaries such as "3 warnings emitted" have no location
+        let Some(span) = diagnostic.spans.iter().find(|span| span.is_primary) else {
+            continue;
+        };
+        if !seen.insert((
+            span.file_name.clone(),
+            span.line_start,
+            diagnostic.message.clone(),
+        )) {
+            continue;
+        }
+
+        let code = diagnostic.code.map(|code| code.code);
+        let severity = severity(&diagnostic.level);
+        findings.annotations.push(Annotation {
+            path: span.file_name.clone(),
+            line: Some(span.line_start),
+            message: diagnostic.message,
+            severity,
+            annotation_type: Some(match severity {
+                Severity::High => AnnotationType::Bug,
+                _ => AnnotationType::CodeSmell,
+            }),
+            link: code
+                .as_deref()
+                .and_then(|code| code.strip_prefix("clippy::"))
+                .map(|lint| {
+                    format!(
+                        "https://rust-lang.github.io/rust-clippy/master/index.html#{}",
+                        lint
+                    )
+                }),
+            external_id: code,
+        });
+    }
+
+    Ok(findings)
+}
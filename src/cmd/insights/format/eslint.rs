@@ -0,0 +1,58 @@
+//! The output of `eslint --format json`.
+
+use crate::cmd::insights::format::Findings;
+use crate::cmd::insights::{Annotation, AnnotationType, Severity};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FileResult {
+    file_path: String,
+    #[serde(default)]
+    messages: Vec<Message>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Message {
+    rule_id: Option<String>,
+    /// 1 for warnings, 2 for errors
+    severity: u8,
+    message: String,
+    line: Option<u32>,
+}
+
+/// Parse the results of an eslint run
+pub fn parse(content: &str) -> Result<Findings, String> {
+    let files: Vec<FileResult> = serde_json::from_str(content).map_err(|e| e.to_string())?;
+    let mut findings = Findings {
+        tool: Some("eslint".to_string()),
+        annotations: Vec::new(),
+    };
+
+    for file in files {
+        for message in file.messages {
+            // rules of plugins are prefixed with the plugin, and documented by it
+            let link = message
+                .rule_id
+                .as_ref()
+                .filter(|rule| !rule.contains('/'))
+                .map(|rule| format!("https://eslint.org/docs/latest/rules/{}", rule));
+
+            findings.annotations.push(Annotation {
+                path: file.file_path.clone(),
+                line: message.line,
+                message: message.message,
+                severity: match message.severity {
+                    2 => Severity::High,
+                    _ => Severity::Medium,
+                },
+                annotation_type: Some(AnnotationType::CodeSmell),
+                link,
+                external_id: message.rule_id,
+            });
+        }
+    }
+
+    Ok(findings)
+}
@@ -0,0 +1,56 @@
+//! JUnit XML test results, which most test runners can emit.
+
+use crate::cmd::insights::format::Findings;
+use crate::cmd::insights::{Annotation, AnnotationType, Severity};
+
+/// Parse test results. Every failed test is a finding, on the file and line of the test case
+/// when the runner records them, or on the whole commit otherwise.
+pub fn parse(content: &str) -> Result<Findings, String> {
+    let document = roxmltree::Document::parse(content).map_err(|e| e.to_string())?;
+    let mut findings = Findings {
+        tool: Some("junit".to_string()),
+        annotations: Vec::new(),
+    };
+
+    for testcase in document
+        .descendants()
+        .filter(|node| node.has_tag_name("testcase"))
+    {
+        let Some(failure) = testcase
+            .children()
+            .find(|node| node.has_tag_name("failure") || node.has_tag_name("error"))
+        else {
+            continue;
+        };
+
+        let name = match (testcase.attribute("classname"), testcase.attribute("name")) {
+            (Some(class), Some(name)) => format!("{}.{}", class, name),
+            (None, Some(name)) => name.to_string(),
+            (class, None) => class.unwrap_or("test").to_string(),
+        };
+        let reason = failure
+            .attribute("message")
+            .or_else(|| {
+                failure
+                    .text()
+                    .map(str::trim)
+                    .filter(|text| !text.is_empty())
+            })
+            .and_then(|reason| reason.lines().next())
+            .unwrap_or("failed");
+
+        findings.annotations.push(Annotation {
+            path: testcase.attribute("file").unwrap_or_default().to_string(),
+            line: testcase
+                .attribute("line")
+                .and_then(|line| line.parse().ok()),
+            message: format!("{}: {}", name, reason),
+            severity: Severity::High,
+            annotation_type: Some(AnnotationType::Bug),
+            link: None,
+            external_id: Some(name),
+        });
+    }
+
+    Ok(findings)
+}
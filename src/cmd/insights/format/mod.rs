@@ -0,0 +1,60 @@
+//! Parsers that turn the output of analysis tools into annotations.
+//!
+//! Each format has its own module with a [`Parser`]; adding a format means adding a module and
+//! a [`Format`] variant.
+
+pub mod checkstyle;
+pub mod clippy;
+pub mod eslint;
+pub mod junit;
+pub mod sarif;
+
+use crate::cmd::insights::{Annotation, Severity};
+use clap::ValueEnum;
+
+/// What a tool found
+#[derive(Debug, Clone, Default)]
+pub struct Findings {
+    /// The name of the tool, if the output has it
+    pub tool: Option<String>,
+    /// Annotations with the paths as the tool wrote them
+    pub annotations: Vec<Annotation>,
+}
+
+/// Parses the output of a tool
+pub type Parser = fn(&str) -> Result<Findings, String>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// SARIF 2.1, emitted by most security scanners
+    Sarif,
+    /// Checkstyle XML, also emitted by many linters for other languages
+    Checkstyle,
+    /// The output of `cargo clippy --message-format json`
+    Clippy,
+    /// The output of `eslint --format json`
+    Eslint,
+    /// JUnit XML test results. Every failed test is a finding.
+    Junit,
+}
+
+impl Format {
+    pub fn parser(&self) -> Parser {
+        match self {
+            Format::Sarif => sarif::parse,
+            Format::Checkstyle => checkstyle::parse,
+            Format::Clippy => clippy::parse,
+            Format::Eslint => eslint::parse,
+            Format::Junit => junit::parse,
+        }
+    }
+}
+
+/// The severity of the common `error`, `warning` and `info` levels
+fn severity(level: &str) -> Severity {
+    match level {
+        "error" => Severity::High,
+        "warning" => Severity::Medium,
+        _ => Severity::Low,
+    }
+}
@@ -0,0 +1,193 @@
+//! [SARIF 2.1](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html), which most
+//! security scanners and many linters emit.
+
+use crate::cmd::insights::format::{severity, Findings};
+use crate::cmd::insights::{Annotation, AnnotationType};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Deserialize)]
+struct Log {
+    #[serde(default)]
+    runs: Vec<Run>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Run {
+    tool: Tool,
+    #[serde(default)]
+    results: Vec<SarifResult>,
+    #[serde(default)]
+    original_uri_base_ids: HashMap<String, ArtifactLocation>,
+}
+
+#[derive(Deserialize)]
+struct Tool {
+    driver: Driver,
+}
+
+#[derive(Deserialize)]
+struct Driver {
+    name: String,
+    #[serde(default)]
+    rules: Vec<Rule>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Rule {
+    id: String,
+    help_uri: Option<String>,
+    default_configuration: Option<Configuration>,
+    #[serde(default)]
+    properties: Properties,
+}
+
+#[derive(Deserialize)]
+struct Configuration {
+    level: Option<String>,
+}
+
+#[derive(Default, Deserialize)]
+struct Properties {
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    rule_id: Option<String>,
+    rule_index: Option<usize>,
+    level: Option<String>,
+    message: Message,
+    #[serde(default)]
+    locations: Vec<Location>,
+}
+
+#[derive(Deserialize)]
+struct Message {
+    text: Option<String>,
+    markdown: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Location {
+    physical_location: Option<PhysicalLocation>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocation {
+    artifact_location: ArtifactLocation,
+    region: Option<Region>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ArtifactLocation {
+    uri: Option<String>,
+    uri_base_id: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Region {
+    start_line: Option<u32>,
+}
+
+/// Parse a SARIF log. Results without a file location are left out.
+pub fn parse(content: &str) -> Result<Findings, String> {
+    let log: Log = serde_json::from_str(content).map_err(|e| e.to_string())?;
+    let mut findings = Findings::default();
+
+    for run in log.runs {
+        findings
+            .tool
+            .get_or_insert_with(|| run.tool.driver.name.clone());
+        let rules = &run.tool.driver.rules;
+
+        for result in &run.results {
+            let rule = result
+                .rule_index
+                .and_then(|index| rules.get(index))
+                .or_else(|| {
+                    rules
+                        .iter()
+                        .find(|rule| Some(&rule.id) == result.rule_id.as_ref())
+                });
+            let Some(location) = result
+                .locations
+                .iter()
+                .find_map(|location| location.physical_location.as_ref())
+            else {
+                continue;
+            };
+            let Some(path) = resolve(&location.artifact_location, &run.original_uri_base_ids)
+            else {
+                continue;
+            };
+
+            // the level of a result defaults to the level of its rule, then to warning
+            let level = result
+                .level
+                .as_deref()
+                .or_else(|| rule.and_then(|r| r.default_configuration.as_ref()?.level.as_deref()))
+                .unwrap_or("warning");
+            let is_security = rule
+                .map(|rule| rule.properties.tags.iter().any(|tag| tag == "security"))
+                .unwrap_or_default();
+            let rule_id = result
+                .rule_id
+                .clone()
+                .or_else(|| rule.map(|r| r.id.clone()));
+            let text = result
+                .message
+                .text
+                .clone()
+                .or_else(|| result.message.markdown.clone())
+                .unwrap_or_default();
+
+            findings.annotations.push(Annotation {
+                path,
+                line: location
+                    .region
+                    .as_ref()
+                    .and_then(|region| region.start_line),
+                message: match &rule_id {
+                    Some(rule_id) => format!("{}: {}", rule_id, text),
+                    None => text,
+                },
+                severity: severity(level),
+                annotation_type: Some(match is_security {
+                    true => AnnotationType::Vulnerability,
+                    false => AnnotationType::CodeSmell,
+                }),
+                link: rule.and_then(|rule| rule.help_uri.clone()),
+                external_id: rule_id,
+            });
+        }
+    }
+
+    Ok(findings)
+}
+
+/// The URI of an artifact, prefixed with its base URI when it has one
+fn resolve(
+    location: &ArtifactLocation,
+    bases: &HashMap<String, ArtifactLocation>,
+) -> Option<String> {
+    let uri = location.uri.clone()?;
+    let base = location
+        .uri_base_id
+        .as_ref()
+        .and_then(|id| bases.get(id))
+        .and_then(|base| base.uri.as_ref());
+
+    Some(match base {
+        Some(base) => format!("{}/{}", base.trim_end_matches('/'), uri),
+        None => uri,
+    })
+}
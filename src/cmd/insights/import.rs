@@ -0,0 +1,251 @@
+use crate::bitbucket::{CommitArgs, PullRequestArgs};
+use crate::cmd::insights::changes::changed_lines;
+use crate::cmd::insights::format::{Findings, Format};
+use crate::cmd::insights::{
+    publish, Annotation, DataField, DataType, Report, ReportResult, Severity,
+};
+use crate::cmd::{CommandError, CommandResult};
+use crate::output;
+use bitbucket_server_rs::client::Client;
+use clap::ValueEnum;
+use clap_derive::Args;
+use serde_json::json;
+use std::collections::HashSet;
+use std::path::{Component, Path, PathBuf};
+
+#[derive(Debug, Args)]
+pub struct InsightsImportArgs {
+    /// The file to import
+    #[arg(long, visible_alias = "sarif")]
+    pub file: PathBuf,
+    /// The format of the file
+    #[arg(long, default_value = "sarif")]
+    pub format: Format,
+    /// The key of the report, e.g. `security-scan`
+    #[arg(long)]
+    pub key: String,
+    /// The title of the report. Defaults to the name of the tool.
+    #[arg(long)]
+    pub title: Option<String>,
+    /// The report fails when a finding is at least this severe
+    #[arg(long, default_value = "high")]
+    pub fail_on: Severity,
+    /// The root of the repository, which paths are made relative to. Defaults to the git
+    /// repository around the working directory.
+    #[arg(long)]
+    pub repo_root: Option<PathBuf>,
+    /// Only annotate the lines the pull request adds or changes. Findings elsewhere are left
+    /// out of the report too.
+    #[arg(long, requires = "pull_request")]
+    pub changed_only: bool,
+    /// The pull request whose changes `--changed-only` keeps, in the same repository
+    #[arg(long)]
+    pub pull_request: Option<String>,
+    /// A link to the full results
+    #[arg(long)]
+    pub link: Option<String>,
+    /// The URL of the logo to show with the report
+    #[arg(long)]
+    pub logo_url: Option<String>,
+}
+
+/// Publish the findings of a tool as a report with annotations, replacing the report's previous
+/// annotations
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-builds-and-deployments/#api-insights-latest-projects-projectkey-repos-repositoryslug-commits-commitid-reports-key-put)
+pub async fn handle(
+    commit_args: &CommitArgs,
+    import_args: &InsightsImportArgs,
+    client: &Client,
+) -> CommandResult {
+    let content = std::fs::read_to_string(&import_args.file).map_err(|e| {
+        CommandError::ArgumentError(vec![format!(
+            "Unable to read {}: {}",
+            import_args.file.display(),
+            e
+        )])
+    })?;
+    let findings = import_args.format.parser()(&content).map_err(|e| {
+        CommandError::ArgumentError(vec![format!(
+            "Invalid {} file {}: {}",
+            import_args.format.to_possible_value().unwrap().get_name(),
+            import_args.file.display(),
+            e
+        )])
+    })?;
+
+    let (cwd, root) = working_dirs(import_args.repo_root.as_deref())?;
+    let mut annotations = findings.annotations.clone();
+    for annotation in annotations.iter_mut().filter(|a| !a.path.is_empty()) {
+        annotation.path = relative_path(&annotation.path, &cwd, &root);
+    }
+    if let (true, Some(pull_request_id)) = (import_args.changed_only, &import_args.pull_request) {
+        let pull_request = PullRequestArgs {
+            pull_request_id: pull_request_id.clone(),
+            repository_slug: commit_args.repository_slug.clone(),
+            project_key: commit_args.project_key.clone(),
+        };
+        let changed = changed_lines(&pull_request, client).await?;
+        // findings on a whole file are kept when the file changed
+        annotations.retain(|a| match (changed.get(&a.path), a.line) {
+            (Some(lines), Some(line)) => lines.contains(&line),
+            (Some(_), None) => true,
+            (None, _) => false,
+        });
+    }
+    unique_external_ids(&mut annotations);
+
+    let report = report(import_args, &findings, &annotations);
+    let stored = publish(commit_args, &import_args.key, &report, &annotations, client).await?;
+
+    output::print(&stored, super::REPORT_COLUMNS)?;
+    Ok(())
+}
+
+fn report(
+    import_args: &InsightsImportArgs,
+    findings: &Findings,
+    annotations: &[Annotation],
+) -> Report {
+    let count = |severity| {
+        annotations
+            .iter()
+            .filter(|a| a.severity == severity)
+            .count()
+    };
+    let failed = annotations
+        .iter()
+        .any(|a| a.severity >= import_args.fail_on);
+    let tool = findings.tool.clone();
+
+    Report {
+        title: import_args
+            .title
+            .clone()
+            .or_else(|| tool.clone())
+            .unwrap_or_else(|| import_args.key.clone()),
+        details: Some(format!(
+            "{} findings: {} high, {} medium and {} low severity.",
+            annotations.len(),
+            count(Severity::High),
+            count(Severity::Medium),
+            count(Severity::Low)
+        )),
+        result: Some(match failed {
+            true => ReportResult::Fail,
+            false => ReportResult::Pass,
+        }),
+        data: [
+            ("High", Severity::High),
+            ("Medium", Severity::Medium),
+            ("Low", Severity::Low),
+        ]
+        .into_iter()
+        .map(|(title, severity)| DataField {
+            title: title.to_string(),
+            data_type: DataType::Number,
+            value: json!(count(severity)),
+        })
+        .collect(),
+        reporter: tool,
+        link: import_args.link.clone(),
+        logo_url: import_args.logo_url.clone(),
+    }
+}
+
+/// External ids must be unique within a report, so tell findings of the same rule apart by
+/// their location
+fn unique_external_ids(annotations: &mut [Annotation]) {
+    let mut seen = HashSet::new();
+
+    for annotation in annotations.iter_mut() {
+        let Some(rule) = &annotation.external_id else {
+            continue;
+        };
+        let base = format!(
+            "{}:{}:{}",
+            rule,
+            annotation.path,
+            annotation.line.unwrap_or(0)
+        );
+        let mut id = base.clone();
+        let mut n = 1;
+        while !seen.insert(id.clone()) {
+            n += 1;
+            id = format!("{}#{}", base, n);
+        }
+        annotation.external_id = Some(id);
+    }
+}
+
+/// The working directory and the root of the repository, which is `repo_root` if given or the
+/// git repository around the working directory otherwise
+pub(crate) fn working_dirs(repo_root: Option<&Path>) -> Result<(PathBuf, PathBuf), CommandError> {
+    let cwd = std::env::current_dir().map_err(|e| CommandError::UnexpectedError(e.to_string()))?;
+    let root = match repo_root {
+        Some(root) => cwd.join(root),
+        None => enclosing_repo(&cwd),
+    };
+    Ok((cwd, root))
+}
+
+/// The git repository around `dir`, or `dir` itself when there is none
+fn enclosing_repo(dir: &Path) -> PathBuf {
+    dir.ancestors()
+        .find(|dir| dir.join(".git").exists())
+        .unwrap_or(dir)
+        .to_path_buf()
+}
+
+/// Make a path or `file://` URI written by a tool relative to the repository root. Relative
+/// paths are taken to be relative to the working directory. Paths outside the root are kept.
+pub fn relative_path(path: &str, cwd: &Path, root: &Path) -> String {
+    let path = path.strip_prefix("file://").unwrap_or(path);
+    let path = percent_decode(path).replace('\\', "/");
+
+    let absolute = normalize(&cwd.join(&path));
+    match absolute.strip_prefix(normalize(root)) {
+        Ok(relative) => relative.to_string_lossy().replace('\\', "/"),
+        Err(_) => path.trim_start_matches("./").to_string(),
+    }
+}
+
+/// Resolve `.` and `..` without touching the file system, as the files may not exist here
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).to_string()
+}
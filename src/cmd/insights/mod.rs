@@ -0,0 +1,225 @@
+pub mod annotations;
+pub mod changes;
+pub mod coverage;
+pub mod format;
+pub mod import;
+pub mod report;
+
+use crate::bitbucket::CommitArgs;
+use crate::cmd::CommandResult;
+use crate::rest;
+use bitbucket_server_rs::client::{ApiError, Client};
+use clap::ValueEnum;
+use clap_derive::{Args, Subcommand};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// The columns of a table of reports
+pub(crate) const REPORT_COLUMNS: &[&str] = &["key", "title", "result", "reporter", "details"];
+
+/// Common args for Code Insights operations
+#[derive(Debug, Args)]
+pub struct InsightsArgs {
+    /// Refers to the commit
+    #[command(flatten)]
+    commit_args: CommitArgs,
+    #[command(subcommand)]
+    command: InsightsSubcommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum InsightsSubcommands {
+    /// Manage the reports of the commit
+    Report(report::InsightsReportArgs),
+    /// Manage the annotations of the commit's reports
+    Annotations(annotations::InsightsAnnotationsArgs),
+    /// Publish the output of an analysis tool as a report with annotations
+    Import(import::InsightsImportArgs),
+    /// Publish line coverage, and the coverage of a pull request's changes, as a report
+    Coverage(coverage::InsightsCoverageArgs),
+}
+
+/// A Code Insights report, shown on the pull requests that contain the commit
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Report {
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<ReportResult>,
+    /// Up to 6 figures shown with the report
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub data: Vec<DataField>,
+    /// The tool that created the report
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reporter: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logo_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum ReportResult {
+    Pass,
+    Fail,
+}
+
+/// A figure shown with a report
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DataField {
+    pub title: String,
+    #[serde(rename = "type")]
+    pub data_type: DataType,
+    pub value: Value,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum DataType {
+    Boolean,
+    /// Milliseconds since the epoch
+    Date,
+    /// Milliseconds
+    Duration,
+    Link,
+    Number,
+    /// 0 to 100
+    Percentage,
+    Text,
+}
+
+/// A finding on a line of a file, shown inline in pull request diffs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Annotation {
+    /// The path of the file, relative to the repository root. Empty for the whole commit.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub path: String,
+    /// The line, or none for the whole file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
+    pub message: String,
+    pub severity: Severity,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub annotation_type: Option<AnnotationType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+    /// Identifies the annotation within its report, so that it can be deleted on its own
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Severity {
+    Low,
+    Medium,
+    High,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AnnotationType {
+    Vulnerability,
+    CodeSmell,
+    Bug,
+}
+
+/// Code Insights command handler
+pub async fn handle(args: &InsightsArgs, client: &Client) -> CommandResult {
+    let commit_args = &args.commit_args;
+
+    match &args.command {
+        InsightsSubcommands::Report(report_args) => {
+            report::handle(commit_args, report_args, client).await
+        }
+        InsightsSubcommands::Annotations(annotations_args) => {
+            annotations::handle(commit_args, annotations_args, client).await
+        }
+        InsightsSubcommands::Import(import_args) => {
+            import::handle(commit_args, import_args, client).await
+        }
+        InsightsSubcommands::Coverage(coverage_args) => {
+            coverage::handle(commit_args, coverage_args, client).await
+        }
+    }
+}
+
+/// Create or replace a report
+pub(crate) async fn put_report(
+    commit_args: &CommitArgs,
+    key: &str,
+    report: &Report,
+    client: &Client,
+) -> Result<Value, ApiError> {
+    rest::put(client, &report_uri(commit_args, key), report)
+        .await?
+        .ok_or(ApiError::ResponseError)
+}
+
+/// Add annotations to a report
+pub(crate) async fn add_annotations(
+    commit_args: &CommitArgs,
+    key: &str,
+    annotations: &[Annotation],
+    client: &Client,
+) -> Result<(), ApiError> {
+    let uri = format!("{}/annotations", report_uri(commit_args, key));
+    let _: Option<Value> = rest::post(client, &uri, &json!({ "annotations": annotations })).await?;
+    Ok(())
+}
+
+/// Bitbucket keeps at most this many annotations per report
+pub const MAX_ANNOTATIONS: usize = 1000;
+
+/// How many annotations to send per request
+const BATCH_SIZE: usize = 100;
+
+/// Replace a report and its annotations. Beyond [`MAX_ANNOTATIONS`], only the most severe
+/// annotations are kept, and the report's details say so.
+pub(crate) async fn publish(
+    commit_args: &CommitArgs,
+    key: &str,
+    report: &Report,
+    annotations: &[Annotation],
+    client: &Client,
+) -> Result<Value, ApiError> {
+    let mut report = report.clone();
+    let mut annotations = annotations.to_vec();
+    if annotations.len() > MAX_ANNOTATIONS {
+        // stable, so findings of the same severity keep the tool's order
+        annotations.sort_by_key(|annotation| std::cmp::Reverse(annotation.severity));
+        let note = format!(
+            "Only the {} most severe of {} findings are annotated.",
+            MAX_ANNOTATIONS,
+            annotations.len()
+        );
+        annotations.truncate(MAX_ANNOTATIONS);
+        report.details = Some(match report.details {
+            Some(details) => format!("{} {}", details, note),
+            None => note,
+        });
+    }
+
+    // replacing a report also deletes its annotations
+    let stored = put_report(commit_args, key, &report, client).await?;
+    for batch in annotations.chunks(BATCH_SIZE) {
+        add_annotations(commit_args, key, batch, client).await?;
+    }
+
+    Ok(stored)
+}
+
+fn commit_uri(commit_args: &CommitArgs) -> String {
+    format!(
+        "insights/latest/projects/{}/repos/{}/commits/{}",
+        commit_args.project_key, commit_args.repository_slug, commit_args.commit_id
+    )
+}
+
+fn report_uri(commit_args: &CommitArgs, key: &str) -> String {
+    format!("{}/reports/{}", commit_uri(commit_args), key)
+}
@@ -0,0 +1,178 @@
+use crate::bitbucket::CommitArgs;
+use crate::cmd::insights::{put_report, report_uri, DataField, DataType, Report, ReportResult};
+use crate::cmd::CommandResult;
+use crate::output;
+use crate::rest;
+use bitbucket_server_rs::client::{ApiError, Client};
+use clap::ValueEnum;
+use clap_derive::{Args, Subcommand};
+use serde_json::{json, Value};
+
+#[derive(Debug, Args)]
+pub struct InsightsReportArgs {
+    #[command(subcommand)]
+    command: InsightsReportSubcommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum InsightsReportSubcommands {
+    /// Create or replace a report
+    Put(InsightsReportPutArgs),
+    /// Get a report
+    Get(InsightsReportKeyArgs),
+    /// Delete a report and its annotations
+    Delete(InsightsReportKeyArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct InsightsReportKeyArgs {
+    /// The key of the report, e.g. `security-scan`
+    #[arg(long)]
+    pub key: String,
+}
+
+#[derive(Debug, Args)]
+pub struct InsightsReportPutArgs {
+    /// The key of the report, e.g. `security-scan`
+    #[arg(long)]
+    pub key: String,
+    /// The title of the report
+    #[arg(long)]
+    pub title: String,
+    /// A description of the report
+    #[arg(long)]
+    pub details: Option<String>,
+    /// Whether the report passed
+    #[arg(long)]
+    pub result: Option<ReportResult>,
+    /// A figure to show with the report, as `TITLE=VALUE` or `TITLE:TYPE=VALUE`. The type is
+    /// guessed from the value when not given. May be repeated up to 6 times.
+    #[arg(long, value_parser = parse_data_field)]
+    pub data: Vec<DataField>,
+    /// The tool that created the report
+    #[arg(long)]
+    pub reporter: Option<String>,
+    /// A link to the full results
+    #[arg(long)]
+    pub link: Option<String>,
+    /// The URL of the logo to show with the report
+    #[arg(long)]
+    pub logo_url: Option<String>,
+}
+
+/// Report command handler
+pub async fn handle(
+    commit_args: &CommitArgs,
+    report_args: &InsightsReportArgs,
+    client: &Client,
+) -> CommandResult {
+    match &report_args.command {
+        InsightsReportSubcommands::Put(put_args) => put(commit_args, put_args, client).await,
+        InsightsReportSubcommands::Get(key_args) => get(commit_args, key_args, client).await,
+        InsightsReportSubcommands::Delete(key_args) => delete(commit_args, key_args, client).await,
+    }
+}
+
+/// Create or replace a report
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-builds-and-deployments/#api-insights-latest-projects-projectkey-repos-repositoryslug-commits-commitid-reports-key-put)
+pub async fn put(
+    commit_args: &CommitArgs,
+    put_args: &InsightsReportPutArgs,
+    client: &Client,
+) -> CommandResult {
+    let report = Report {
+        title: put_args.title.clone(),
+        details: put_args.details.clone(),
+        result: put_args.result,
+        data: put_args.data.clone(),
+        reporter: put_args.reporter.clone(),
+        link: put_args.link.clone(),
+        logo_url: put_args.logo_url.clone(),
+    };
+    let stored = put_report(commit_args, &put_args.key, &report, client).await?;
+
+    output::print(&stored, super::REPORT_COLUMNS)?;
+    Ok(())
+}
+
+/// Get a report
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-builds-and-deployments/#api-insights-latest-projects-projectkey-repos-repositoryslug-commits-commitid-reports-key-get)
+pub async fn get(
+    commit_args: &CommitArgs,
+    key_args: &InsightsReportKeyArgs,
+    client: &Client,
+) -> CommandResult {
+    let report: Value = rest::get(client, &report_uri(commit_args, &key_args.key), &[])
+        .await?
+        .ok_or(ApiError::ResponseError)?;
+
+    output::print(&report, super::REPORT_COLUMNS)?;
+    Ok(())
+}
+
+/// Delete a report, along with its annotations
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-builds-and-deployments/#api-insights-latest-projects-projectkey-repos-repositoryslug-commits-commitid-reports-key-delete)
+pub async fn delete(
+    commit_args: &CommitArgs,
+    key_args: &InsightsReportKeyArgs,
+    client: &Client,
+) -> CommandResult {
+    rest::delete::<()>(client, &report_uri(commit_args, &key_args.key), None).await?;
+
+    output::message(format!("Report {} deleted.", key_args.key));
+    Ok(())
+}
+
+/// Parse `TITLE=VALUE` or `TITLE:TYPE=VALUE` into a data field
+pub fn parse_data_field(value: &str) -> Result<DataField, String> {
+    let (title, raw) = value
+        .split_once('=')
+        .ok_or_else(|| format!("invalid data `{}`, expected `TITLE=VALUE`", value))?;
+
+    let (title, data_type) = match title.rsplit_once(':') {
+        Some((title, data_type)) => match DataType::from_str(data_type, true) {
+            Ok(data_type) => (title, Some(data_type)),
+            Err(_) => return Err(format!("unknown data type `{}`", data_type)),
+        },
+        None => (title, None),
+    };
+    let data_type = data_type.unwrap_or_else(|| guess_type(raw));
+
+    let number = || {
+        raw.parse::<f64>()
+            .map(|n| json!(n))
+            .map_err(|_| format!("`{}` is not a number", raw))
+    };
+    let value = match data_type {
+        DataType::Boolean => json!(raw
+            .parse::<bool>()
+            .map_err(|_| format!("`{}` is not true or false", raw))?),
+        DataType::Date | DataType::Duration => json!(raw
+            .parse::<u64>()
+            .map_err(|_| format!("`{}` is not a number of milliseconds", raw))?),
+        DataType::Number | DataType::Percentage => number()?,
+        DataType::Link => json!({ "linktext": raw, "href": raw }),
+        DataType::Text => json!(raw),
+    };
+
+    Ok(DataField {
+        title: title.to_string(),
+        data_type,
+        value,
+    })
+}
+
+fn guess_type(value: &str) -> DataType {
+    if value.parse::<f64>().is_ok() {
+        DataType::Number
+    } else if value.parse::<bool>().is_ok() {
+        DataType::Boolean
+    } else if value.starts_with("http://") || value.starts_with("https://") {
+        DataType::Link
+    } else {
+        DataType::Text
+    }
+}
@@ -1,10 +1,40 @@
+pub mod access_token;
+pub mod api;
+pub mod auth;
+pub mod branch;
+pub mod branch_restrictions;
 pub mod build_status;
+pub mod default_reviewers;
+pub mod file;
+pub mod insights;
+pub mod permissions;
+pub mod project;
 pub mod pull_request_changes;
+pub mod repo;
+pub mod tag;
+pub mod webhook;
 
+use access_token::AccessTokenArgs;
+use api::ApiArgs;
+use auth::AuthArgs;
+use branch::BranchArgs;
+use branch_restrictions::BranchRestrictionsArgs;
 use build_status::BuildStatusArgs;
+use default_reviewers::DefaultReviewersArgs;
+use file::FileArgs;
+use insights::InsightsArgs;
 use bitbucket_server_rs::client::{ApiError, Client};
 use clap::{Parser, Subcommand};
+use crate::config::AuthScheme;
+use crate::output::{self, OutputFormat};
+use permissions::PermissionsArgs;
+use project::ProjectCommandArgs;
 use pull_request_changes::get::PullRequestChangesArgs;
+use repo::RepoArgs;
+use std::io::Write;
+use std::path::PathBuf;
+use tag::TagArgs;
+use webhook::WebhookArgs;
 use std::process::exit;
 
 #[derive(Debug, Parser)]
@@ -16,6 +46,9 @@ pub struct Command {
     #[command(flatten)]
     pub server_args: ServerArgs,
 
+    #[command(flatten)]
+    pub output_args: OutputArgs,
+
     #[command(subcommand)]
     pub(crate) command: Subcommands,
 }
@@ -26,9 +59,40 @@ pub struct ServerArgs {
     #[arg(long, global = true)]
     pub server: Option<String>,
 
-    /// The API token to use for authentication. NOTE: This is not secure. Use the BITBUCKET_API_TOKEN environment variable instead.
+    /// The API token to use for authentication. NOTE: This is not secure. Use the BITBUCKET_API_TOKEN environment variable or `auth login` instead.
     #[arg(long, global = true)]
     pub api_token: Option<String>,
+
+    /// How to authenticate. `basic` sends `--username` with the API token or password.
+    #[arg(long, global = true, value_enum, default_value_t = AuthScheme::Bearer)]
+    pub auth: AuthScheme,
+
+    /// The username for `--auth basic`. Alternatively, set the BITBUCKET_USERNAME environment variable.
+    #[arg(long, global = true)]
+    pub username: Option<String>,
+
+    /// Read the API token, or the password with `--auth basic`, from a file
+    #[arg(long, global = true, conflicts_with = "api_token_stdin")]
+    pub api_token_file: Option<PathBuf>,
+
+    /// Read the API token, or the password with `--auth basic`, from the first line of stdin
+    #[arg(long, global = true)]
+    pub api_token_stdin: bool,
+}
+
+#[derive(Debug, Parser)]
+pub struct OutputArgs {
+    /// The format of the results. Defaults to JSON on a single line.
+    #[arg(short, long, global = true, value_enum)]
+    pub output: Option<OutputFormat>,
+
+    /// A JMESPath expression selecting what to print of the results, e.g. `values[?state==`FAILED`].key`
+    #[arg(short, long, global = true)]
+    pub query: Option<String>,
+
+    /// A minijinja template to print the results with instead of an output format, or `@` and the path of a template file
+    #[arg(long, global = true, conflicts_with = "output")]
+    pub template: Option<String>,
 }
 
 #[derive(Debug, Subcommand)]
@@ -38,9 +102,57 @@ pub enum Subcommands {
 
     /// Manage pull request changes
     PullRequestChanges(PullRequestChangesArgs),
+
+    /// Manage branches
+    Branch(BranchArgs),
+
+    /// Manage tags
+    Tag(TagArgs),
+
+    /// Browse repository files without cloning
+    File(FileArgs),
+
+    /// Manage repositories
+    Repo(RepoArgs),
+
+    /// Manage projects
+    Project(ProjectCommandArgs),
+
+    /// Manage project and repository permissions
+    Permissions(PermissionsArgs),
+
+    /// Manage branch restrictions, also known as branch permissions
+    BranchRestrictions(BranchRestrictionsArgs),
+
+    /// Manage default reviewer conditions
+    DefaultReviewers(DefaultReviewersArgs),
+
+    /// Manage webhooks and inspect their deliveries
+    Webhook(WebhookArgs),
+
+    /// Publish Code Insights reports and annotations on commits
+    Insights(InsightsArgs),
+
+    /// Call any REST endpoint, for what the other commands do not cover
+    Api(ApiArgs),
+
+    /// Save, remove and check the token of a server
+    Auth(AuthArgs),
+
+    /// Manage the HTTP access tokens of a user, project or repository
+    AccessToken(AccessTokenArgs),
 }
 
 pub async fn handle(client: Client, args: Command) {
+    let options = output::Options {
+        format: args.output_args.output,
+        query: args.output_args.query.clone(),
+        template: args.output_args.template.clone(),
+    };
+    if let Err(e) = output::init(&options) {
+        return handle_error(e);
+    }
+
     let result = match args.command {
         Subcommands::BuildStatus(args) => {
             build_status::handle(&args, &client).await
@@ -48,6 +160,45 @@ pub async fn handle(client: Client, args: Command) {
         Subcommands::PullRequestChanges(args) => {
             pull_request_changes::handle(&args, &client).await
         }
+        Subcommands::Branch(args) => {
+            branch::handle(&args, &client).await
+        }
+        Subcommands::Tag(args) => {
+            tag::handle(&args, &client).await
+        }
+        Subcommands::File(args) => {
+            file::handle(&args, &client).await
+        }
+        Subcommands::Repo(args) => {
+            repo::handle(&args, &client).await
+        }
+        Subcommands::Project(args) => {
+            project::handle(&args, &client).await
+        }
+        Subcommands::Permissions(args) => {
+            permissions::handle(&args, &client).await
+        }
+        Subcommands::BranchRestrictions(args) => {
+            branch_restrictions::handle(&args, &client).await
+        }
+        Subcommands::DefaultReviewers(args) => {
+            default_reviewers::handle(&args, &client).await
+        }
+        Subcommands::Webhook(args) => {
+            webhook::handle(&args, &client).await
+        }
+        Subcommands::Insights(args) => {
+            insights::handle(&args, &client).await
+        }
+        Subcommands::Api(args) => {
+            api::handle(&args, &client).await
+        }
+        Subcommands::Auth(auth_args) => {
+            auth::handle(&auth_args, &args.server_args, &client).await
+        }
+        Subcommands::AccessToken(args) => {
+            access_token::handle(&args, &client).await
+        }
     };
 
     match result {
@@ -69,6 +220,8 @@ pub enum CommandError {
     ArgumentError(Vec<String>),
     /// other unexpected error
     UnexpectedError(String),
+    /// The server does not match the desired state
+    DriftDetected(String),
 }
 
 impl std::fmt::Display for CommandError {
@@ -77,6 +230,7 @@ impl std::fmt::Display for CommandError {
             CommandError::ApiError(e) => write!(f, "API error: {:?}", e),
             CommandError::ArgumentError(errors) => write!(f, "Argument error: {:?}", errors),
             CommandError::UnexpectedError(e) => write!(f, "Unexpected error: {}", e),
+            CommandError::DriftDetected(e) => write!(f, "Drift detected: {}", e),
         }
     }
 }
@@ -90,8 +244,23 @@ impl From<ApiError> for CommandError {
     }
 }
 
-/// handle CommandError
-fn handle_error(error: CommandError) {
+/// Ask a yes/no question on stderr. Anything but an explicit yes, including a closed stdin, is a no.
+pub(crate) fn confirm(prompt: &str) -> Result<bool, CommandError> {
+    eprint!("{} [y/N] ", prompt);
+    std::io::stderr()
+        .flush()
+        .map_err(|e| CommandError::UnexpectedError(e.to_string()))?;
+
+    let mut answer = String::new();
+    std::io::stdin()
+        .read_line(&mut answer)
+        .map_err(|e| CommandError::UnexpectedError(e.to_string()))?;
+
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// Print a CommandError and exit with its code
+pub fn handle_error(error: CommandError) {
     match error {
         CommandError::ArgumentError(errors) => {
             eprintln!("Invalid arguments: {:?}", errors);
@@ -102,7 +271,7 @@ fn handle_error(error: CommandError) {
             exit(11)
         }
         CommandError::ApiError(ApiError::Unauthorized) => {
-            eprintln!("Unauthorized. Please check your API token.");
+            eprintln!("Unauthorized. Please check your credentials.");
             exit(12)
         }
         CommandError::ApiError(ApiError::ResponseError) => {
@@ -132,6 +301,10 @@ fn handle_error(error: CommandError) {
             eprintln!("Unable to deserialize response: {}", message);
             exit(31)
         }
+        CommandError::DriftDetected(e) => {
+            eprintln!("Drift detected: {}", e);
+            exit(2)
+        }
         CommandError::UnexpectedError(e) => {
             eprintln!("Unexpected error: {}", e);
             exit(101)
@@ -0,0 +1,181 @@
+use crate::bitbucket::ScopeArgs;
+use crate::cmd::permissions::{list, set_default, Grant, PrincipalType};
+use crate::cmd::repo;
+use crate::cmd::{CommandError, CommandResult};
+use crate::output::{self, OutputFormat};
+use crate::rest;
+use bitbucket_server_rs::client::{ApiError, Client};
+use clap_derive::Args;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Args)]
+pub struct PermissionsAuditArgs {
+    /// Also list the members of every group with a grant. Requires admin permission.
+    #[arg(long)]
+    pub expand_groups: bool,
+}
+
+/// A single grant in the audit
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct AuditRow {
+    /// `PROJECT` or `REPOSITORY`
+    pub scope: &'static str,
+    pub repository: String,
+    /// `USER`, `GROUP`, `DEFAULT` for all logged in users or `PUBLIC` for anonymous access
+    pub principal_type: &'static str,
+    pub principal: String,
+    /// The group a user was granted the permission through, if any
+    pub via_group: String,
+    pub permission: String,
+}
+
+#[derive(Deserialize)]
+struct GroupMember {
+    name: String,
+}
+
+const HEADER: [&str; 6] = [
+    "scope",
+    "repository",
+    "principal_type",
+    "principal",
+    "via_group",
+    "permission",
+];
+
+/// Write every grant in a project and its repositories to stdout, as CSV unless another output
+/// format is chosen
+pub async fn handle(
+    scope: &ScopeArgs,
+    audit_args: &PermissionsAuditArgs,
+    client: &Client,
+) -> CommandResult {
+    if scope.repository_slug.is_some() {
+        return Err(CommandError::ArgumentError(vec![
+            "The audit covers a whole project. Omit --repository-slug.".to_string(),
+        ]));
+    }
+
+    let rows = audit(&scope.project_key, audit_args.expand_groups, client).await?;
+
+    output::print_or(&rows, &HEADER, OutputFormat::Csv)
+}
+
+/// Collect the grants of a project, then of each of its repositories
+pub async fn audit(
+    project_key: &str,
+    expand_groups: bool,
+    client: &Client,
+) -> Result<Vec<AuditRow>, ApiError> {
+    let mut rows = Vec::new();
+    let mut members = HashMap::new();
+
+    for permission in ["PROJECT_READ", "PROJECT_WRITE"] {
+        if set_default::is_allowed(project_key, permission, client).await? {
+            rows.push(AuditRow {
+                scope: "PROJECT",
+                repository: String::new(),
+                principal_type: "DEFAULT",
+                principal: "all-users".to_string(),
+                via_group: String::new(),
+                permission: permission.to_string(),
+            });
+        }
+    }
+
+    let project_scope = ScopeArgs {
+        project_key: project_key.to_string(),
+        repository_slug: None,
+    };
+    let grants = list::fetch(&project_scope, client).await?;
+    push_grants(&mut rows, "PROJECT", "", grants, expand_groups, &mut members, client).await?;
+
+    for repository in repo::list::fetch_project(project_key, client).await? {
+        if repository.public {
+            rows.push(AuditRow {
+                scope: "REPOSITORY",
+                repository: repository.slug.clone(),
+                principal_type: "PUBLIC",
+                principal: "anonymous".to_string(),
+                via_group: String::new(),
+                permission: "REPO_READ".to_string(),
+            });
+        }
+
+        let repository_scope = ScopeArgs {
+            project_key: project_key.to_string(),
+            repository_slug: Some(repository.slug.clone()),
+        };
+        let grants = list::fetch(&repository_scope, client).await?;
+        push_grants(
+            &mut rows,
+            "REPOSITORY",
+            &repository.slug,
+            grants,
+            expand_groups,
+            &mut members,
+            client,
+        )
+        .await?;
+    }
+
+    Ok(rows)
+}
+
+async fn push_grants(
+    rows: &mut Vec<AuditRow>,
+    scope: &'static str,
+    repository: &str,
+    grants: Vec<Grant>,
+    expand_groups: bool,
+    members: &mut HashMap<String, Vec<String>>,
+    client: &Client,
+) -> Result<(), ApiError> {
+    for grant in grants {
+        let principal_type = match grant.principal_type {
+            PrincipalType::User => "USER",
+            PrincipalType::Group => "GROUP",
+        };
+
+        if expand_groups && grant.principal_type == PrincipalType::Group {
+            // groups usually have grants on many repositories, so only look their members up once
+            if !members.contains_key(&grant.name) {
+                let group_members = group_members(&grant.name, client).await?;
+                members.insert(grant.name.clone(), group_members);
+            }
+            for member in &members[&grant.name] {
+                rows.push(AuditRow {
+                    scope,
+                    repository: repository.to_string(),
+                    principal_type: "USER",
+                    principal: member.clone(),
+                    via_group: grant.name.clone(),
+                    permission: grant.permission.clone(),
+                });
+            }
+        }
+
+        rows.push(AuditRow {
+            scope,
+            repository: repository.to_string(),
+            principal_type,
+            principal: grant.name,
+            via_group: String::new(),
+            permission: grant.permission,
+        });
+    }
+
+    Ok(())
+}
+
+async fn group_members(group: &str, client: &Client) -> Result<Vec<String>, ApiError> {
+    let members: Vec<GroupMember> = rest::get_paged(
+        client,
+        "api/latest/admin/groups/more-members",
+        &[("context", group.to_string())],
+    )
+    .await?;
+
+    Ok(members.into_iter().map(|member| member.name).collect())
+}
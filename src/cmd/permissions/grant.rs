@@ -0,0 +1,49 @@
+use crate::bitbucket::ScopeArgs;
+use crate::cmd::permissions::{permission_name, uri, Permission, PrincipalArgs, PrincipalType};
+use crate::cmd::CommandResult;
+use crate::output;
+use crate::rest;
+use bitbucket_server_rs::client::{ApiError, Client};
+use clap_derive::Args;
+use reqwest::Method;
+
+#[derive(Debug, Args)]
+pub struct PermissionsGrantArgs {
+    /// The user or group to grant the permission to
+    #[command(flatten)]
+    pub principal: PrincipalArgs,
+    /// The permission to grant. Replaces any permission the user or group already has.
+    #[arg(long, value_enum)]
+    pub permission: Permission,
+}
+
+/// Grant a permission to a user or group
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-permission-management/#api-api-latest-projects-projectkey-permissions-users-put)
+pub async fn handle(
+    scope: &ScopeArgs,
+    grant_args: &PermissionsGrantArgs,
+    client: &Client,
+) -> CommandResult {
+    let (principal_type, name) = grant_args.principal.principal();
+    let permission = permission_name(scope, grant_args.permission);
+
+    grant(scope, principal_type, name, grant_args.permission, client).await?;
+
+    output::message(format!("Granted {} to {}.", permission, name));
+    Ok(())
+}
+
+/// Grant `permission` to a user or group, replacing the permission it already has
+pub(crate) async fn grant(
+    scope: &ScopeArgs,
+    principal_type: PrincipalType,
+    name: &str,
+    permission: Permission,
+    client: &Client,
+) -> Result<(), ApiError> {
+    let request = rest::request(client, Method::PUT, &uri(scope, principal_type))
+        .query(&[("name", name), ("permission", permission_name(scope, permission))]);
+
+    rest::send(client, request).await.map(|_| ())
+}
@@ -0,0 +1,42 @@
+use crate::bitbucket::ScopeArgs;
+use crate::cmd::permissions::{uri, Grant, GroupGrant, PrincipalType, UserGrant};
+use crate::cmd::CommandResult;
+use crate::output;
+use crate::rest;
+use bitbucket_server_rs::client::{ApiError, Client};
+
+/// The columns of the table output
+const COLUMNS: &[&str] = &["principalType", "name", "displayName", "permission"];
+
+/// List the users and groups with an explicit permission on the project or repository
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-permission-management/#api-api-latest-projects-projectkey-permissions-users-get)
+pub async fn handle(scope: &ScopeArgs, client: &Client) -> CommandResult {
+    let grants = fetch(scope, client).await?;
+
+    output::print(&grants, COLUMNS)?;
+    Ok(())
+}
+
+/// Fetch the user grants followed by the group grants
+pub(crate) async fn fetch(scope: &ScopeArgs, client: &Client) -> Result<Vec<Grant>, ApiError> {
+    let users: Vec<UserGrant> =
+        rest::get_paged(client, &uri(scope, PrincipalType::User), &[]).await?;
+    let groups: Vec<GroupGrant> =
+        rest::get_paged(client, &uri(scope, PrincipalType::Group), &[]).await?;
+
+    let users = users.into_iter().map(|grant| Grant {
+        principal_type: PrincipalType::User,
+        name: grant.user.name,
+        display_name: grant.user.display_name,
+        permission: grant.permission,
+    });
+    let groups = groups.into_iter().map(|grant| Grant {
+        principal_type: PrincipalType::Group,
+        name: grant.group.name,
+        display_name: None,
+        permission: grant.permission,
+    });
+
+    Ok(users.chain(groups).collect())
+}
@@ -0,0 +1,175 @@
+pub mod audit;
+pub mod grant;
+pub mod list;
+pub mod revoke;
+pub mod set_default;
+
+use crate::bitbucket::ScopeArgs;
+use crate::cmd::CommandResult;
+use bitbucket_server_rs::client::Client;
+use clap::ValueEnum;
+use clap_derive::{Args, Subcommand};
+use serde::{Deserialize, Serialize};
+
+/// Common args for permission operations
+#[derive(Debug, Args)]
+pub struct PermissionsArgs {
+    /// Refers to the project, or to a repository in it
+    #[command(flatten)]
+    scope: ScopeArgs,
+    #[command(subcommand)]
+    command: PermissionsSubcommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum PermissionsSubcommands {
+    /// List the user and group grants
+    List,
+    /// Grant a permission to a user or group
+    Grant(grant::PermissionsGrantArgs),
+    /// Revoke all permissions of a user or group
+    Revoke(revoke::PermissionsRevokeArgs),
+    /// Set the permission every logged in user has on the project
+    SetDefault(set_default::PermissionsSetDefaultArgs),
+    /// Write every grant in the project and its repositories as CSV
+    Audit(audit::PermissionsAuditArgs),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Permission {
+    Read,
+    Write,
+    Admin,
+}
+
+impl std::fmt::Display for Permission {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Permission::Read => write!(f, "read"),
+            Permission::Write => write!(f, "write"),
+            Permission::Admin => write!(f, "admin"),
+        }
+    }
+}
+
+impl Permission {
+    /// Read a permission such as `REPO_WRITE` or `PROJECT_READ`
+    pub fn from_api(permission: &str) -> Option<Permission> {
+        match permission.rsplit('_').next()? {
+            "READ" => Some(Permission::Read),
+            "WRITE" => Some(Permission::Write),
+            "ADMIN" => Some(Permission::Admin),
+            _ => None,
+        }
+    }
+}
+
+/// Who a permission is granted to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum PrincipalType {
+    User,
+    Group,
+}
+
+impl PrincipalType {
+    fn endpoint(&self) -> &'static str {
+        match self {
+            PrincipalType::User => "users",
+            PrincipalType::Group => "groups",
+        }
+    }
+}
+
+/// A user or group and the permission granted to it
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Grant {
+    pub principal_type: PrincipalType,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    /// e.g. `PROJECT_ADMIN` or `REPO_WRITE`
+    pub permission: String,
+}
+
+/// A principal named with `--user` or `--group`
+#[derive(Debug, Args)]
+#[group(required = true, multiple = false)]
+pub struct PrincipalArgs {
+    /// The name of the user
+    #[arg(long)]
+    pub user: Option<String>,
+    /// The name of the group
+    #[arg(long)]
+    pub group: Option<String>,
+}
+
+impl PrincipalArgs {
+    fn principal(&self) -> (PrincipalType, &str) {
+        match (&self.user, &self.group) {
+            (Some(user), _) => (PrincipalType::User, user),
+            (None, Some(group)) => (PrincipalType::Group, group),
+            // clap requires exactly one of the two
+            (None, None) => unreachable!("either --user or --group is required"),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct UserGrant {
+    user: Principal,
+    permission: String,
+}
+
+#[derive(Deserialize)]
+struct GroupGrant {
+    group: Principal,
+    permission: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Principal {
+    name: String,
+    display_name: Option<String>,
+}
+
+/// URI of the permissions resource for users or groups on a project or repository
+fn uri(scope: &ScopeArgs, principal_type: PrincipalType) -> String {
+    format!(
+        "api/latest/{}/permissions/{}",
+        scope.path(),
+        principal_type.endpoint()
+    )
+}
+
+/// The Bitbucket name of a permission on a project or repository, e.g. `REPO_WRITE`
+fn permission_name(scope: &ScopeArgs, permission: Permission) -> &'static str {
+    match (&scope.repository_slug, permission) {
+        (Some(_), Permission::Read) => "REPO_READ",
+        (Some(_), Permission::Write) => "REPO_WRITE",
+        (Some(_), Permission::Admin) => "REPO_ADMIN",
+        (None, Permission::Read) => "PROJECT_READ",
+        (None, Permission::Write) => "PROJECT_WRITE",
+        (None, Permission::Admin) => "PROJECT_ADMIN",
+    }
+}
+
+/// Permissions command handler
+pub async fn handle(args: &PermissionsArgs, client: &Client) -> CommandResult {
+    let scope = &args.scope;
+
+    match &args.command {
+        PermissionsSubcommands::List => list::handle(scope, client).await,
+        PermissionsSubcommands::Grant(grant_args) => grant::handle(scope, grant_args, client).await,
+        PermissionsSubcommands::Revoke(revoke_args) => {
+            revoke::handle(scope, revoke_args, client).await
+        }
+        PermissionsSubcommands::SetDefault(set_default_args) => {
+            set_default::handle(scope, set_default_args, client).await
+        }
+        PermissionsSubcommands::Audit(audit_args) => audit::handle(scope, audit_args, client).await,
+    }
+}
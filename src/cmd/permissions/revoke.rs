@@ -0,0 +1,44 @@
+use crate::bitbucket::ScopeArgs;
+use crate::cmd::permissions::{uri, PrincipalArgs, PrincipalType};
+use crate::cmd::CommandResult;
+use crate::output;
+use crate::rest;
+use bitbucket_server_rs::client::{ApiError, Client};
+use clap_derive::Args;
+use reqwest::Method;
+
+#[derive(Debug, Args)]
+pub struct PermissionsRevokeArgs {
+    /// The user or group to revoke all permissions from
+    #[command(flatten)]
+    pub principal: PrincipalArgs,
+}
+
+/// Revoke all permissions of a user or group
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-permission-management/#api-api-latest-projects-projectkey-permissions-users-delete)
+pub async fn handle(
+    scope: &ScopeArgs,
+    revoke_args: &PermissionsRevokeArgs,
+    client: &Client,
+) -> CommandResult {
+    let (principal_type, name) = revoke_args.principal.principal();
+
+    revoke(scope, principal_type, name, client).await?;
+
+    output::message(format!("Revoked the permissions of {}.", name));
+    Ok(())
+}
+
+/// Revoke every permission of a user or group
+pub(crate) async fn revoke(
+    scope: &ScopeArgs,
+    principal_type: PrincipalType,
+    name: &str,
+    client: &Client,
+) -> Result<(), ApiError> {
+    let request = rest::request(client, Method::DELETE, &uri(scope, principal_type))
+        .query(&[("name", name)]);
+
+    rest::send(client, request).await.map(|_| ())
+}
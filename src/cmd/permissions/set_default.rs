@@ -0,0 +1,87 @@
+use crate::bitbucket::ScopeArgs;
+use crate::cmd::{CommandError, CommandResult};
+use crate::output;
+use crate::rest;
+use bitbucket_server_rs::client::{ApiError, Client};
+use clap::ValueEnum;
+use clap_derive::Args;
+use reqwest::Method;
+use serde::Deserialize;
+
+#[derive(Debug, Args)]
+pub struct PermissionsSetDefaultArgs {
+    /// The permission every logged in user has on the project
+    #[arg(value_enum)]
+    pub permission: DefaultPermission,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DefaultPermission {
+    Read,
+    Write,
+    None,
+}
+
+#[derive(Deserialize)]
+struct Permitted {
+    permitted: bool,
+}
+
+/// Set the default permission of a project
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-permission-management/#api-api-latest-projects-projectkey-permissions-permission-all-post)
+pub async fn handle(
+    scope: &ScopeArgs,
+    set_default_args: &PermissionsSetDefaultArgs,
+    client: &Client,
+) -> CommandResult {
+    if scope.repository_slug.is_some() {
+        return Err(CommandError::ArgumentError(vec![
+            "The default permission can only be set on a project".to_string(),
+        ]));
+    }
+
+    // write implies read, so granting read alone means taking write away
+    let (read, write) = match set_default_args.permission {
+        DefaultPermission::Read => (true, false),
+        DefaultPermission::Write => (true, true),
+        DefaultPermission::None => (false, false),
+    };
+    allow(&scope.project_key, "PROJECT_WRITE", write, client).await?;
+    allow(&scope.project_key, "PROJECT_READ", read, client).await?;
+
+    output::message(format!("Default permission of project {} set to {:?}.",
+        scope.project_key, set_default_args.permission));
+    Ok(())
+}
+
+async fn allow(
+    project_key: &str,
+    permission: &str,
+    allow: bool,
+    client: &Client,
+) -> Result<(), ApiError> {
+    let uri = format!(
+        "api/latest/projects/{}/permissions/{}/all",
+        project_key, permission
+    );
+    let request =
+        rest::request(client, Method::POST, &uri).query(&[("allow", allow.to_string())]);
+
+    rest::send(client, request).await.map(|_| ())
+}
+
+/// Whether every logged in user has `permission` on the project
+pub(crate) async fn is_allowed(
+    project_key: &str,
+    permission: &str,
+    client: &Client,
+) -> Result<bool, ApiError> {
+    let uri = format!(
+        "api/latest/projects/{}/permissions/{}/all",
+        project_key, permission
+    );
+    let permitted: Option<Permitted> = rest::get(client, &uri, &[]).await?;
+
+    Ok(permitted.is_some_and(|p| p.permitted))
+}
@@ -0,0 +1,67 @@
+use crate::bitbucket::ProjectArgs;
+use crate::cmd::project::uri;
+use crate::cmd::{CommandError, CommandResult};
+use crate::output;
+use crate::rest;
+use bitbucket_server_rs::client::Client;
+use clap_derive::{Args, Subcommand};
+use reqwest::multipart::{Form, Part};
+use reqwest::Method;
+use std::path::PathBuf;
+
+#[derive(Debug, Args)]
+pub struct ProjectAvatarArgs {
+    #[command(subcommand)]
+    command: ProjectAvatarSubcommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum ProjectAvatarSubcommands {
+    /// Upload a new avatar
+    Set(ProjectAvatarSetArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct ProjectAvatarSetArgs {
+    /// Refers to the project
+    #[command(flatten)]
+    pub project_args: ProjectArgs,
+    /// The image to upload. e.g. `logo.png`
+    pub file: PathBuf,
+}
+
+/// Project avatar command handler
+pub async fn handle(avatar_args: &ProjectAvatarArgs, client: &Client) -> CommandResult {
+    match &avatar_args.command {
+        ProjectAvatarSubcommands::Set(set_args) => set(set_args, client).await,
+    }
+}
+
+/// Upload a project avatar
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-project/#api-api-latest-projects-projectkey-avatar-png-post)
+pub async fn set(set_args: &ProjectAvatarSetArgs, client: &Client) -> CommandResult {
+    let content = std::fs::read(&set_args.file).map_err(|e| {
+        CommandError::ArgumentError(vec![format!(
+            "Unable to read {}: {}",
+            set_args.file.display(),
+            e
+        )])
+    })?;
+    let file_name = set_args
+        .file
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let uri = format!("{}/avatar.png", uri(&set_args.project_args));
+    let request = rest::request(client, Method::POST, &uri)
+        // multipart uploads are rejected without it, as a CSRF protection
+        .header("X-Atlassian-Token", "no-check")
+        .multipart(Form::new().part("avatar", Part::bytes(content).file_name(file_name)));
+
+    rest::send(client, request).await?;
+
+    output::message(format!("Avatar of project {} updated.", set_args.project_args.project_key));
+    Ok(())
+}
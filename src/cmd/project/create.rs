@@ -0,0 +1,44 @@
+use crate::cmd::project::Project;
+use crate::cmd::CommandResult;
+use crate::output;
+use crate::rest;
+use bitbucket_server_rs::client::Client;
+use clap_derive::Args;
+use serde_json::json;
+
+#[derive(Debug, Args)]
+pub struct ProjectCreateArgs {
+    /// The project key. e.g. `PAY`
+    #[arg(short = 'k', long)]
+    pub project_key: String,
+    /// The name of the project
+    #[arg(long)]
+    pub name: String,
+    /// A description of the project
+    #[arg(long)]
+    pub description: Option<String>,
+    /// Whether the project is readable without logging in
+    #[arg(long)]
+    pub public: Option<bool>,
+}
+
+/// Create a project
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-project/#api-api-latest-projects-post)
+pub async fn handle(create_args: &ProjectCreateArgs, client: &Client) -> CommandResult {
+    let mut body = json!({
+        "key": create_args.project_key,
+        "name": create_args.name,
+    });
+    if let Some(description) = &create_args.description {
+        body["description"] = json!(description);
+    }
+    if let Some(public) = create_args.public {
+        body["public"] = json!(public);
+    }
+
+    let project: Option<Project> = rest::post(client, "api/latest/projects", &body).await?;
+
+    output::print(&project, super::COLUMNS)?;
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use crate::bitbucket::ProjectArgs;
+use crate::cmd::project::uri;
+use crate::cmd::{confirm, CommandError, CommandResult};
+use crate::output;
+use crate::rest;
+use bitbucket_server_rs::client::Client;
+use clap_derive::Args;
+
+#[derive(Debug, Args)]
+pub struct ProjectDeleteArgs {
+    /// Refers to the project
+    #[command(flatten)]
+    pub project_args: ProjectArgs,
+    /// Delete without asking for confirmation
+    #[arg(short, long)]
+    pub yes: bool,
+}
+
+/// Delete a project, after confirmation
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-project/#api-api-latest-projects-projectkey-delete)
+pub async fn handle(delete_args: &ProjectDeleteArgs, client: &Client) -> CommandResult {
+    let project_key = &delete_args.project_args.project_key;
+
+    if !delete_args.yes && !confirm(&format!("Delete project {}?", project_key))? {
+        return Err(CommandError::ArgumentError(vec![
+            "Deletion was not confirmed. Use --yes to skip the confirmation.".to_string(),
+        ]));
+    }
+
+    rest::delete::<()>(client, &uri(&delete_args.project_args), None).await?;
+
+    output::message(format!("Project {} deleted.", project_key));
+    Ok(())
+}
@@ -0,0 +1,16 @@
+use crate::bitbucket::ProjectArgs;
+use crate::cmd::project::{uri, Project};
+use crate::cmd::CommandResult;
+use crate::output;
+use crate::rest;
+use bitbucket_server_rs::client::Client;
+
+/// Get a project
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-project/#api-api-latest-projects-projectkey-get)
+pub async fn handle(project_args: &ProjectArgs, client: &Client) -> CommandResult {
+    let project: Option<Project> = rest::get(client, &uri(project_args), &[]).await?;
+
+    output::print(&project, super::COLUMNS)?;
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use crate::cmd::project::Project;
+use crate::cmd::CommandResult;
+use crate::output;
+use crate::rest;
+use bitbucket_server_rs::client::Client;
+use clap::ValueEnum;
+use clap_derive::Args;
+
+#[derive(Debug, Args)]
+pub struct ProjectListArgs {
+    /// Only list projects whose name contains this text
+    #[arg(long)]
+    pub name: Option<String>,
+    /// Only list projects you have at least this permission on
+    #[arg(long, value_enum)]
+    pub permission: Option<ProjectPermission>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ProjectPermission {
+    ProjectRead,
+    ProjectWrite,
+    ProjectAdmin,
+}
+
+impl ProjectPermission {
+    pub fn as_param(&self) -> &'static str {
+        match self {
+            ProjectPermission::ProjectRead => "PROJECT_READ",
+            ProjectPermission::ProjectWrite => "PROJECT_WRITE",
+            ProjectPermission::ProjectAdmin => "PROJECT_ADMIN",
+        }
+    }
+}
+
+/// List projects
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-project/#api-api-latest-projects-get)
+pub async fn handle(list_args: &ProjectListArgs, client: &Client) -> CommandResult {
+    let mut params = vec![];
+    if let Some(name) = &list_args.name {
+        params.push(("name", name.to_string()));
+    }
+    if let Some(permission) = list_args.permission {
+        params.push(("permission", permission.as_param().to_string()));
+    }
+
+    let projects: Vec<Project> = rest::get_paged(client, "api/latest/projects", &params).await?;
+
+    output::print(&projects, super::COLUMNS)?;
+    Ok(())
+}
@@ -0,0 +1,73 @@
+pub mod avatar;
+pub mod create;
+pub mod delete;
+pub mod get;
+pub mod list;
+pub mod update;
+
+use crate::bitbucket::ProjectArgs;
+use crate::cmd::CommandResult;
+use bitbucket_server_rs::client::Client;
+use clap_derive::{Args, Subcommand};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The columns of a table of projects
+pub(crate) const COLUMNS: &[&str] = &["key", "name", "description", "public", "type"];
+
+#[derive(Debug, Args)]
+pub struct ProjectCommandArgs {
+    #[command(subcommand)]
+    command: ProjectSubcommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum ProjectSubcommands {
+    /// List the projects visible to you
+    List(list::ProjectListArgs),
+    /// Get a project
+    Get(ProjectArgs),
+    /// Create a project
+    Create(create::ProjectCreateArgs),
+    /// Update a project
+    Update(update::ProjectUpdateArgs),
+    /// Delete a project. Only empty projects can be deleted.
+    Delete(delete::ProjectDeleteArgs),
+    /// Manage the project avatar
+    Avatar(avatar::ProjectAvatarArgs),
+}
+
+/// A project as returned by the Bitbucket API
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Project {
+    pub id: u64,
+    pub key: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub public: bool,
+    /// `NORMAL`, or `PERSONAL` for a user's personal project
+    #[serde(rename = "type")]
+    pub project_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub links: Option<Value>,
+}
+
+/// Project command handler
+pub async fn handle(args: &ProjectCommandArgs, client: &Client) -> CommandResult {
+    match &args.command {
+        ProjectSubcommands::List(list_args) => list::handle(list_args, client).await,
+        ProjectSubcommands::Get(project_args) => get::handle(project_args, client).await,
+        ProjectSubcommands::Create(create_args) => create::handle(create_args, client).await,
+        ProjectSubcommands::Update(update_args) => update::handle(update_args, client).await,
+        ProjectSubcommands::Delete(delete_args) => delete::handle(delete_args, client).await,
+        ProjectSubcommands::Avatar(avatar_args) => avatar::handle(avatar_args, client).await,
+    }
+}
+
+/// URI of a single project
+pub(crate) fn uri(project_args: &ProjectArgs) -> String {
+    format!("api/latest/projects/{}", project_args.project_key)
+}
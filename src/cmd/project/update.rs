@@ -0,0 +1,52 @@
+use crate::bitbucket::ProjectArgs;
+use crate::cmd::project::{uri, Project};
+use crate::cmd::CommandResult;
+use crate::output;
+use crate::rest;
+use bitbucket_server_rs::client::Client;
+use clap_derive::Args;
+use serde_json::json;
+
+#[derive(Debug, Args)]
+pub struct ProjectUpdateArgs {
+    /// Refers to the project
+    #[command(flatten)]
+    pub project_args: ProjectArgs,
+    /// The new key of the project
+    #[arg(long)]
+    pub new_key: Option<String>,
+    /// The new name of the project
+    #[arg(long)]
+    pub name: Option<String>,
+    /// A description of the project
+    #[arg(long)]
+    pub description: Option<String>,
+    /// Whether the project is readable without logging in
+    #[arg(long)]
+    pub public: Option<bool>,
+}
+
+/// Update a project. Only the given fields are changed.
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-project/#api-api-latest-projects-projectkey-put)
+pub async fn handle(update_args: &ProjectUpdateArgs, client: &Client) -> CommandResult {
+    let mut body = json!({});
+    if let Some(new_key) = &update_args.new_key {
+        body["key"] = json!(new_key);
+    }
+    if let Some(name) = &update_args.name {
+        body["name"] = json!(name);
+    }
+    if let Some(description) = &update_args.description {
+        body["description"] = json!(description);
+    }
+    if let Some(public) = update_args.public {
+        body["public"] = json!(public);
+    }
+
+    let project: Option<Project> =
+        rest::put(client, &uri(&update_args.project_args), &body).await?;
+
+    output::print(&project, super::COLUMNS)?;
+    Ok(())
+}
@@ -1,8 +1,13 @@
 use crate::bitbucket::PullRequestArgs;
-use crate::cmd::{CommandError, CommandResult};
-use bitbucket_server_rs::client::{ApiRequest, Client};
+use crate::cmd::CommandResult;
+use crate::output;
+use crate::rest;
+use bitbucket_server_rs::api::pull_request_changes_get::PullRequestChanges;
+use bitbucket_server_rs::client::{ApiError, Client};
 use clap_derive::Args;
-use serde_json;
+
+/// The columns of the table output
+const COLUMNS: &[&str] = &["type", "path.toString", "srcPath.toString", "nodeType"];
 
 #[derive(Debug, Args)]
 pub struct PullRequestChangesArgs {
@@ -39,54 +44,70 @@ pub struct PullRequestChangesArgs {
 ///
 /// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-pull-requests/#api-api-latest-projects-projectkey-repos-repositoryslug-pull-requests-pullrequestid-changes-get)
 pub async fn handle(args: &PullRequestChangesArgs, client: &Client) -> CommandResult {
-    let client = client.clone();
-    let mut builder = client.api().pull_request_changes_get(
-        &args.pull_request.project_key,
-        &args.pull_request.repository_slug,
-        &args.pull_request.pull_request_id.to_string(),
-    );
-
-    // Add optional parameters
+    let mut params = Vec::new();
     if let Some(since_id) = &args.since_id {
-        builder.since_id(since_id);
+        params.push(("sinceId", since_id.clone()));
     }
-    
     if let Some(change_scope) = &args.change_scope {
-        builder.change_scope(change_scope);
+        params.push(("changeScope", change_scope.clone()));
     }
-    
     if let Some(until_id) = &args.until_id {
-        builder.until_id(until_id);
+        params.push(("untilId", until_id.clone()));
     }
-    
     if let Some(start) = args.start {
-        builder.start(start);
+        params.push(("start", start.to_string()));
     }
-    
     if let Some(limit) = args.limit {
-        builder.limit(limit);
+        params.push(("limit", limit.to_string()));
     }
-    
     if let Some(with_comments) = args.with_comments {
-        builder.with_comments(with_comments);
+        params.push(("withComments", with_comments.to_string()));
     }
 
-    // Build and send the request
-    let response = builder
-        .build()
-        .map_err(|e| CommandError::UnexpectedError(
-            format!("Failed to build request: {}", e),
-        ))?
-        .send()
-        .await;
+    let changes: Option<PullRequestChanges> =
+        rest::get(client, &changes_uri(&args.pull_request), &params).await?;
+
+    output::print(&changes, COLUMNS)
+}
+
+fn changes_uri(pull_request: &PullRequestArgs) -> String {
+    format!(
+        "api/latest/projects/{}/repos/{}/pull-requests/{}/changes",
+        pull_request.project_key, pull_request.repository_slug, pull_request.pull_request_id
+    )
+}
+
+/// Fetch the paths of the files a pull request adds or changes. Deleted files are left out.
+pub(crate) async fn changed_paths(
+    pull_request: &PullRequestArgs,
+    client: &Client,
+) -> Result<Vec<String>, ApiError> {
+    const LIMIT: u32 = 500;
+    let mut paths = Vec::new();
+    let mut start = 0;
+
+    // the library does not expose the paging fields, so stop at the first page that is not full
+    loop {
+        let changes: PullRequestChanges = rest::get(
+            client,
+            &changes_uri(pull_request),
+            &[("start", start.to_string()), ("limit", LIMIT.to_string())],
+        )
+        .await?
+        .ok_or(ApiError::ResponseError)?;
+        let values = changes.values.unwrap_or_default();
+        let count = values.len() as u32;
+
+        paths.extend(
+            values
+                .into_iter()
+                .filter(|change| change.change_type != "DELETE")
+                .map(|change| change.path.to_string),
+        );
 
-    // Handle the response
-    match response {
-        Ok(changes) => {
-            let json = serde_json::json!(changes).to_string();
-            println!("{}", json);
-            Ok(())
+        if count < LIMIT {
+            return Ok(paths);
         }
-        Err(err) => Err(err.into()),
+        start += count;
     }
 }
@@ -0,0 +1,70 @@
+use crate::cmd::repo::manifest::plan::{plan, summary, Action};
+use crate::cmd::repo::manifest::{remote, Manifest};
+use crate::cmd::{confirm, CommandError, CommandResult};
+use crate::output;
+use bitbucket_server_rs::client::Client;
+use clap_derive::Args;
+use std::path::PathBuf;
+
+#[derive(Debug, Args)]
+pub struct RepoApplyArgs {
+    /// The YAML manifest describing the desired settings of the repository
+    #[arg(short, long)]
+    pub file: PathBuf,
+    /// Apply the changes without asking for confirmation
+    #[arg(long, conflicts_with = "detect_drift")]
+    pub auto_approve: bool,
+    /// Only show the plan, and exit with code 2 if the repository differs from the manifest
+    #[arg(long)]
+    pub detect_drift: bool,
+}
+
+/// Show the changes needed to make a repository match a manifest, then apply them
+pub async fn handle(apply_args: &RepoApplyArgs, client: &Client) -> CommandResult {
+    let manifest = Manifest::load(&apply_args.file)?;
+    let repository_args = manifest.repository_args();
+    let name = format!("{}/{}", manifest.project, manifest.repository);
+
+    let current = remote::fetch(&repository_args, &manifest.sections(), client).await?;
+    let changes = plan(&manifest, &current);
+
+    if changes.is_empty() {
+        output::message(format!("No changes. {} matches the manifest.", name));
+        return Ok(());
+    }
+
+    output::message(format!("{}:", name));
+    for change in &changes {
+        output::message(format!("  {}", change));
+    }
+    output::message(summary(&changes));
+
+    if apply_args.detect_drift {
+        return Err(CommandError::DriftDetected(format!(
+            "{} differs from {}",
+            name,
+            apply_args.file.display()
+        )));
+    }
+
+    if !apply_args.auto_approve && !confirm("Apply these changes?")? {
+        return Err(CommandError::ArgumentError(vec![
+            "Apply was not confirmed. Use --auto-approve to skip the confirmation.".to_string(),
+        ]));
+    }
+
+    for change in &changes {
+        for operation in &change.operations {
+            remote::execute(&repository_args, operation, client).await?;
+        }
+    }
+
+    let count = |action| changes.iter().filter(|c| c.action == action).count();
+    output::message(format!(
+        "Apply complete! {} added, {} changed, {} destroyed.",
+        count(Action::Create),
+        count(Action::Update),
+        count(Action::Delete)
+    ));
+    Ok(())
+}
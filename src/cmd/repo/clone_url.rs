@@ -0,0 +1,47 @@
+use crate::bitbucket::RepositoryArgs;
+use crate::cmd::repo::get;
+use crate::cmd::{CommandError, CommandResult};
+use crate::output;
+use bitbucket_server_rs::client::Client;
+use clap::ValueEnum;
+use clap_derive::Args;
+
+#[derive(Debug, Args)]
+pub struct RepoCloneUrlArgs {
+    /// Refers to the repository
+    #[command(flatten)]
+    pub repository_args: RepositoryArgs,
+    /// The protocol to clone with
+    #[arg(long, value_enum, default_value_t = CloneProtocol::Http)]
+    pub protocol: CloneProtocol,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CloneProtocol {
+    Http,
+    Ssh,
+}
+
+/// Print the clone URL of a repository
+pub async fn handle(clone_url_args: &RepoCloneUrlArgs, client: &Client) -> CommandResult {
+    let repository = get::fetch(&clone_url_args.repository_args, client).await?;
+
+    let protocol = match clone_url_args.protocol {
+        CloneProtocol::Http => "http",
+        CloneProtocol::Ssh => "ssh",
+    };
+    let link = repository
+        .links
+        .clone
+        .iter()
+        .find(|link| link.name.as_deref() == Some(protocol))
+        .ok_or_else(|| {
+            CommandError::UnexpectedError(format!(
+                "The server does not provide a {} clone URL",
+                protocol
+            ))
+        })?;
+
+    output::message(&link.href);
+    Ok(())
+}
@@ -0,0 +1,146 @@
+use crate::bitbucket::RepositoryArgs;
+use crate::cmd::repo::export::export;
+use crate::cmd::repo::manifest::plan::{plan, summary, Change};
+use crate::cmd::repo::manifest::{remote, Manifest, Section};
+use crate::cmd::{CommandError, CommandResult};
+use crate::output;
+use bitbucket_server_rs::client::Client;
+use clap::ValueEnum;
+use clap_derive::Args;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+#[derive(Debug, Args)]
+pub struct RepoCopySettingsArgs {
+    /// The repository to copy the settings from, as `PROJECT/repository`
+    #[arg(long, value_parser = parse_repository)]
+    pub from: RepositoryArgs,
+    /// A repository to copy the settings to, as `PROJECT/repository`. May be repeated.
+    #[arg(long, value_parser = parse_repository, required = true)]
+    pub to: Vec<RepositoryArgs>,
+    /// Only copy this section. May be repeated. Every section but the default branch is copied
+    /// by default.
+    #[arg(long, value_enum)]
+    pub section: Vec<Section>,
+    /// Only show the changes each target needs
+    #[arg(long)]
+    pub dry_run: bool,
+    /// The maximum number of target repositories to process at the same time
+    #[arg(long, default_value_t = 4)]
+    pub parallelism: usize,
+}
+
+/// Make the settings of each target repository match those of a template repository. A failure
+/// on one target does not stop the others.
+pub async fn handle(copy_args: &RepoCopySettingsArgs, client: &Client) -> CommandResult {
+    let sections: Vec<Section> = match copy_args.section.is_empty() {
+        true => Section::value_variants()
+            .iter()
+            .copied()
+            .filter(|section| *section != Section::DefaultBranch)
+            .collect(),
+        false => copy_args.section.clone(),
+    };
+    let template = Arc::new(export(&copy_args.from, &sections, client).await?);
+
+    let permits = Arc::new(Semaphore::new(copy_args.parallelism.max(1)));
+    let mut tasks = JoinSet::new();
+
+    for (index, target) in copy_args.to.iter().cloned().enumerate() {
+        let template = template.clone();
+        let permits = permits.clone();
+        let client = client.clone();
+        let dry_run = copy_args.dry_run;
+
+        tasks.spawn(async move {
+            let result = match permits.acquire_owned().await {
+                Ok(_permit) => copy(&template, &target, dry_run, &client).await,
+                Err(e) => Err(CommandError::UnexpectedError(e.to_string())),
+            };
+            (index, target, result)
+        });
+    }
+
+    let mut results = Vec::new();
+    while let Some(result) = tasks.join_next().await {
+        results.push(result.map_err(|e| CommandError::UnexpectedError(e.to_string()))?);
+    }
+    results.sort_by_key(|(index, _, _)| *index);
+
+    // report every target, in the order they were given, and fail with all of their errors
+    let mut failures = Vec::new();
+    for (_, target, result) in results {
+        let name = format!("{}/{}", target.project_key, target.repository_slug);
+        match result {
+            Ok(changes) if changes.is_empty() => output::message(format!(
+                "No changes. {} matches {}.",
+                name,
+                template_name(copy_args)
+            )),
+            Ok(changes) => {
+                output::message(format!("{}:", name));
+                for change in &changes {
+                    output::message(format!("  {}", change));
+                }
+                match copy_args.dry_run {
+                    true => output::message(summary(&changes)),
+                    false => output::message(format!("Applied. {}", summary(&changes))),
+                }
+            }
+            Err(e) => failures.push(format!("{}: {}", name, e)),
+        }
+    }
+
+    match failures.is_empty() {
+        true => Ok(()),
+        false => Err(CommandError::UnexpectedError(failures.join("\n"))),
+    }
+}
+
+/// Plan the changes that make `target` match the template and, unless this is a dry run,
+/// apply them
+async fn copy(
+    template: &Manifest,
+    target: &RepositoryArgs,
+    dry_run: bool,
+    client: &Client,
+) -> Result<Vec<Change>, CommandError> {
+    let current = remote::fetch(target, &template.sections(), client).await?;
+    let changes = plan(template, &current);
+
+    if !dry_run {
+        for change in &changes {
+            for operation in &change.operations {
+                remote::execute(target, operation, client).await?;
+            }
+        }
+    }
+
+    Ok(changes)
+}
+
+fn template_name(copy_args: &RepoCopySettingsArgs) -> String {
+    format!(
+        "{}/{}",
+        copy_args.from.project_key, copy_args.from.repository_slug
+    )
+}
+
+/// Parse a repository given as `PROJECT/repository`
+fn parse_repository(value: &str) -> Result<RepositoryArgs, String> {
+    match value.split_once('/') {
+        Some((project_key, repository_slug))
+            if !project_key.is_empty() && !repository_slug.is_empty() =>
+        {
+            Ok(RepositoryArgs {
+                project_key: project_key.to_string(),
+                repository_slug: repository_slug.to_string(),
+            })
+        }
+        _ => Err(format!(
+            "invalid repository `{}`, expected PROJECT/repository",
+            value
+        )),
+    }
+}
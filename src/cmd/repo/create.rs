@@ -0,0 +1,60 @@
+use crate::cmd::repo::Repository;
+use crate::cmd::CommandResult;
+use crate::output;
+use crate::rest;
+use bitbucket_server_rs::client::Client;
+use clap_derive::Args;
+use serde_json::json;
+
+#[derive(Debug, Args)]
+pub struct RepoCreateArgs {
+    /// The project key
+    #[arg(short = 'k', long)]
+    pub project_key: String,
+    /// The name of the repository. The slug is derived from it.
+    pub name: String,
+    /// The SCM of the repository
+    #[arg(long, default_value = "git")]
+    pub scm: String,
+    /// Whether the repository can be forked
+    #[arg(long)]
+    pub forkable: Option<bool>,
+    /// The default branch of the repository. e.g. `main`
+    #[arg(long)]
+    pub default_branch: Option<String>,
+    /// Whether the repository is readable without logging in
+    #[arg(long)]
+    pub public: Option<bool>,
+    /// A description of the repository
+    #[arg(long)]
+    pub description: Option<String>,
+}
+
+/// Create a repository
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-repository/#api-api-latest-projects-projectkey-repos-post)
+pub async fn handle(create_args: &RepoCreateArgs, client: &Client) -> CommandResult {
+    let uri = format!("api/latest/projects/{}/repos", create_args.project_key);
+
+    let mut body = json!({
+        "name": create_args.name,
+        "scmId": create_args.scm,
+    });
+    if let Some(forkable) = create_args.forkable {
+        body["forkable"] = json!(forkable);
+    }
+    if let Some(default_branch) = &create_args.default_branch {
+        body["defaultBranch"] = json!(default_branch);
+    }
+    if let Some(public) = create_args.public {
+        body["public"] = json!(public);
+    }
+    if let Some(description) = &create_args.description {
+        body["description"] = json!(description);
+    }
+
+    let repository: Option<Repository> = rest::post(client, &uri, &body).await?;
+
+    output::print(&repository, super::COLUMNS)?;
+    Ok(())
+}
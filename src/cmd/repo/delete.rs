@@ -0,0 +1,39 @@
+use crate::bitbucket::RepositoryArgs;
+use crate::cmd::repo::uri;
+use crate::cmd::{confirm, CommandError, CommandResult};
+use crate::output;
+use crate::rest;
+use bitbucket_server_rs::client::Client;
+use clap_derive::Args;
+
+#[derive(Debug, Args)]
+pub struct RepoDeleteArgs {
+    /// Refers to the repository
+    #[command(flatten)]
+    pub repository_args: RepositoryArgs,
+    /// Delete without asking for confirmation
+    #[arg(short, long)]
+    pub yes: bool,
+}
+
+/// Delete a repository, after confirmation
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-repository/#api-api-latest-projects-projectkey-repos-repositoryslug-delete)
+pub async fn handle(delete_args: &RepoDeleteArgs, client: &Client) -> CommandResult {
+    let repository_args = &delete_args.repository_args;
+    let name = format!(
+        "{}/{}",
+        repository_args.project_key, repository_args.repository_slug
+    );
+
+    if !delete_args.yes && !confirm(&format!("Delete repository {}?", name))? {
+        return Err(CommandError::ArgumentError(vec![
+            "Deletion was not confirmed. Use --yes to skip the confirmation.".to_string(),
+        ]));
+    }
+
+    rest::delete::<()>(client, &uri(repository_args), None).await?;
+
+    output::message(format!("Repository {} scheduled for deletion.", name));
+    Ok(())
+}
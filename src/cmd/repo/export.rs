@@ -0,0 +1,124 @@
+use crate::bitbucket::RepositoryArgs;
+use crate::cmd::repo::list;
+use crate::cmd::repo::manifest::{remote, Manifest, Section};
+use crate::cmd::{CommandError, CommandResult};
+use crate::output::{self, OutputFormat};
+use bitbucket_server_rs::client::Client;
+use clap::ValueEnum;
+use clap_derive::Args;
+use std::path::PathBuf;
+
+#[derive(Debug, Args)]
+pub struct RepoExportArgs {
+    /// The project key
+    #[arg(short = 'k', long)]
+    pub project_key: String,
+    /// The repository slug. e.g. `my-repository-name`. Without it, every repository in the
+    /// project is exported.
+    #[arg(short, long)]
+    pub repository_slug: Option<String>,
+    /// The format of the files written to `--output-dir`. On stdout, manifests are printed as
+    /// YAML unless `--output` says otherwise.
+    #[arg(long, value_enum, default_value_t = ExportFormat::Yaml, requires = "output_dir")]
+    pub format: ExportFormat,
+    /// Write one file per repository, named after its slug, to this directory instead of stdout.
+    /// Required when exporting a whole project.
+    #[arg(long)]
+    pub output_dir: Option<PathBuf>,
+    /// Only export this section. May be repeated. Every section is exported by default.
+    #[arg(long, value_enum)]
+    pub section: Vec<Section>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    Yaml,
+    Json,
+}
+
+impl ExportFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Yaml => "yaml",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+/// Export the settings of a repository, or of every repository in a project, as manifests that
+/// `repo apply` accepts
+pub async fn handle(export_args: &RepoExportArgs, client: &Client) -> CommandResult {
+    let repository_slugs = match &export_args.repository_slug {
+        Some(repository_slug) => vec![repository_slug.clone()],
+        None if export_args.output_dir.is_none() => {
+            return Err(CommandError::ArgumentError(vec![
+                "--output-dir is required when exporting a whole project".to_string(),
+            ]))
+        }
+        None => list::fetch_project(&export_args.project_key, client)
+            .await?
+            .into_iter()
+            .map(|repository| repository.slug)
+            .collect(),
+    };
+
+    let sections = match export_args.section.is_empty() {
+        true => Section::value_variants().to_vec(),
+        false => export_args.section.clone(),
+    };
+
+    if let Some(output_dir) = &export_args.output_dir {
+        std::fs::create_dir_all(output_dir)
+            .map_err(|e| CommandError::UnexpectedError(e.to_string()))?;
+    }
+
+    for repository_slug in repository_slugs {
+        let repository_args = RepositoryArgs {
+            project_key: export_args.project_key.clone(),
+            repository_slug,
+        };
+        let manifest = export(&repository_args, &sections, client).await?;
+
+        match &export_args.output_dir {
+            Some(output_dir) => {
+                let content = serialize(&manifest, export_args.format)?;
+                let file = output_dir.join(format!(
+                    "{}.{}",
+                    repository_args.repository_slug,
+                    export_args.format.extension()
+                ));
+                std::fs::write(&file, content)
+                    .map_err(|e| CommandError::UnexpectedError(e.to_string()))?;
+                output::message(format!(
+                    "Exported {}/{} to {}.",
+                    repository_args.project_key,
+                    repository_args.repository_slug,
+                    file.display()
+                ));
+            }
+            None => output::print_or(&manifest, &[], OutputFormat::Yaml)?,
+        }
+    }
+
+    Ok(())
+}
+
+/// Fetch the given sections of a repository's settings as a manifest
+pub(crate) async fn export(
+    repository_args: &RepositoryArgs,
+    sections: &[Section],
+    client: &Client,
+) -> Result<Manifest, CommandError> {
+    let remote = remote::fetch(repository_args, sections, client).await?;
+    Ok(remote.into_manifest(repository_args))
+}
+
+fn serialize(manifest: &Manifest, format: ExportFormat) -> Result<String, CommandError> {
+    match format {
+        ExportFormat::Yaml => serde_yaml::to_string(manifest)
+            .map_err(|e| CommandError::UnexpectedError(e.to_string())),
+        ExportFormat::Json => serde_json::to_string_pretty(manifest)
+            .map(|json| json + "\n")
+            .map_err(|e| CommandError::UnexpectedError(e.to_string())),
+    }
+}
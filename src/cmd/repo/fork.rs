@@ -0,0 +1,40 @@
+use crate::bitbucket::RepositoryArgs;
+use crate::cmd::repo::{uri, Repository};
+use crate::cmd::CommandResult;
+use crate::output;
+use crate::rest;
+use bitbucket_server_rs::client::Client;
+use clap_derive::Args;
+use serde_json::json;
+
+#[derive(Debug, Args)]
+pub struct RepoForkArgs {
+    /// Refers to the repository to fork
+    #[command(flatten)]
+    pub repository_args: RepositoryArgs,
+    /// The project to fork into. Defaults to your personal project.
+    #[arg(long)]
+    pub target_project: Option<String>,
+    /// The name of the fork. Defaults to the name of the repository.
+    #[arg(long)]
+    pub name: Option<String>,
+}
+
+/// Fork a repository
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-repository/#api-api-latest-projects-projectkey-repos-repositoryslug-post)
+pub async fn handle(fork_args: &RepoForkArgs, client: &Client) -> CommandResult {
+    let mut body = json!({});
+    if let Some(name) = &fork_args.name {
+        body["name"] = json!(name);
+    }
+    if let Some(target_project) = &fork_args.target_project {
+        body["project"] = json!({ "key": target_project });
+    }
+
+    let repository: Option<Repository> =
+        rest::post(client, &uri(&fork_args.repository_args), &body).await?;
+
+    output::print(&repository, super::COLUMNS)?;
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use crate::bitbucket::RepositoryArgs;
+use crate::cmd::repo::{uri, Repository};
+use crate::cmd::CommandResult;
+use crate::output;
+use crate::rest;
+use bitbucket_server_rs::client::{ApiError, Client};
+
+/// Get a repository
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-repository/#api-api-latest-projects-projectkey-repos-repositoryslug-get)
+pub async fn handle(repository_args: &RepositoryArgs, client: &Client) -> CommandResult {
+    let repository = fetch(repository_args, client).await?;
+
+    output::print(&repository, super::COLUMNS)?;
+    Ok(())
+}
+
+/// Fetch a repository
+pub(crate) async fn fetch(
+    repository_args: &RepositoryArgs,
+    client: &Client,
+) -> Result<Repository, ApiError> {
+    rest::get(client, &uri(repository_args), &[])
+        .await?
+        .ok_or(ApiError::ResponseError)
+}
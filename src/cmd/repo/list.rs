@@ -0,0 +1,85 @@
+use crate::cmd::repo::Repository;
+use crate::cmd::CommandResult;
+use crate::output;
+use crate::rest;
+use bitbucket_server_rs::client::{ApiError, Client};
+use clap::ValueEnum;
+use clap_derive::Args;
+
+#[derive(Debug, Args)]
+pub struct RepoListArgs {
+    /// Only list repositories in this project. Without it, every repository visible to you is listed.
+    #[arg(short = 'k', long)]
+    pub project_key: Option<String>,
+    /// Only list repositories whose name contains this text
+    #[arg(long)]
+    pub name: Option<String>,
+    /// Only list repositories you have at least this permission on
+    #[arg(long, value_enum)]
+    pub permission: Option<RepositoryPermission>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum RepositoryPermission {
+    RepoRead,
+    RepoWrite,
+    RepoAdmin,
+}
+
+impl RepositoryPermission {
+    pub fn as_param(&self) -> &'static str {
+        match self {
+            RepositoryPermission::RepoRead => "REPO_READ",
+            RepositoryPermission::RepoWrite => "REPO_WRITE",
+            RepositoryPermission::RepoAdmin => "REPO_ADMIN",
+        }
+    }
+}
+
+/// List repositories
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-repository/#api-api-latest-repos-get)
+pub async fn handle(list_args: &RepoListArgs, client: &Client) -> CommandResult {
+    let repositories = match (&list_args.project_key, list_args.permission) {
+        // the project endpoint is cheaper, but it cannot filter by permission
+        (Some(project_key), None) => fetch_project(project_key, client)
+            .await?
+            .into_iter()
+            .filter(|repository| match &list_args.name {
+                Some(name) => repository.name.to_lowercase().contains(&name.to_lowercase()),
+                None => true,
+            })
+            .collect(),
+        (project_key, permission) => {
+            let mut params = vec![];
+            if let Some(name) = &list_args.name {
+                params.push(("name", name.to_string()));
+            }
+            if let Some(permission) = permission {
+                params.push(("permission", permission.as_param().to_string()));
+            }
+
+            let repositories: Vec<Repository> =
+                rest::get_paged(client, "api/latest/repos", &params).await?;
+            repositories
+                .into_iter()
+                .filter(|repository| match project_key {
+                    Some(project_key) => &repository.project.key == project_key,
+                    None => true,
+                })
+                .collect::<Vec<_>>()
+        }
+    };
+
+    output::print(&repositories, super::COLUMNS)?;
+    Ok(())
+}
+
+/// Fetch every repository in a project
+pub(crate) async fn fetch_project(
+    project_key: &str,
+    client: &Client,
+) -> Result<Vec<Repository>, ApiError> {
+    let uri = format!("api/latest/projects/{}/repos", project_key);
+    rest::get_paged(client, &uri, &[]).await
+}
@@ -0,0 +1,238 @@
+//! The desired state of a repository's settings, as read from a YAML manifest.
+//!
+//! Every section is optional. A section that is left out is not managed, so whatever is
+//! configured on the server for it is kept. A section that is present is authoritative: settings
+//! on the server that it does not list are removed.
+
+pub mod plan;
+pub mod remote;
+
+use crate::bitbucket::{RefMatcher, RepositoryArgs};
+pub use crate::cmd::branch_restrictions::{BranchRestriction, RestrictionType};
+pub use crate::cmd::default_reviewers::DefaultReviewers;
+pub use crate::cmd::webhook::Webhook;
+use crate::cmd::permissions::Permission;
+use crate::cmd::CommandError;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct Manifest {
+    /// The project key
+    pub project: String,
+    /// The repository slug
+    pub repository: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_branch: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch_restrictions: Option<Vec<BranchRestriction>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_reviewers: Option<Vec<DefaultReviewers>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub merge_strategies: Option<MergeStrategies>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub merge_checks: Option<MergeChecks>,
+    /// Repository hooks and merge check hooks by key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hooks: Option<BTreeMap<String, Hook>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhooks: Option<Vec<Webhook>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub required_builds: Option<Vec<RequiredBuild>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub permissions: Option<Permissions>,
+}
+
+/// The sections of a manifest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Section {
+    DefaultBranch,
+    BranchRestrictions,
+    DefaultReviewers,
+    MergeStrategies,
+    MergeChecks,
+    Hooks,
+    Webhooks,
+    RequiredBuilds,
+    Permissions,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct MergeStrategies {
+    /// e.g. `no-ff`, `ff-only` or `squash`
+    pub default: String,
+    /// The strategies users can pick from. Must include the default.
+    pub enabled: Vec<String>,
+}
+
+/// The pull request settings that must be met before merging. Only the fields that are given are
+/// managed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct MergeChecks {
+    /// The minimum number of approvals
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub required_approvers: Option<u32>,
+    /// Whether every reviewer must approve
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub required_all_approvers: Option<bool>,
+    /// Whether every task must be resolved
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub required_all_tasks_complete: Option<bool>,
+    /// The minimum number of successful builds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub required_successful_builds: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct Hook {
+    #[serde(default = "active_default")]
+    pub enabled: bool,
+    /// The hook's own settings. Left as they are when not given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub settings: Option<Value>,
+}
+
+fn active_default() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct RequiredBuild {
+    pub ref_matcher: RefMatcher,
+    /// Keys of the builds that must succeed before a pull request can be merged
+    pub build_parent_keys: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exempt_ref_matcher: Option<RefMatcher>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct Permissions {
+    #[serde(default)]
+    pub users: BTreeMap<String, Permission>,
+    #[serde(default)]
+    pub groups: BTreeMap<String, Permission>,
+}
+
+impl Manifest {
+    /// Read a manifest from a YAML or JSON file
+    pub fn load(path: &Path) -> Result<Manifest, CommandError> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            CommandError::ArgumentError(vec![format!("Unable to read {}: {}", path.display(), e)])
+        })?;
+        // JSON is valid YAML, so exported JSON manifests can be applied too
+        let mut manifest: Manifest = serde_yaml::from_str(&content).map_err(|e| {
+            CommandError::ArgumentError(vec![format!("Invalid manifest {}: {}", path.display(), e)])
+        })?;
+
+        manifest.normalize();
+        manifest.validate()?;
+        Ok(manifest)
+    }
+
+    /// The sections this manifest manages
+    pub fn sections(&self) -> Vec<Section> {
+        let present = [
+            (Section::DefaultBranch, self.default_branch.is_some()),
+            (Section::BranchRestrictions, self.branch_restrictions.is_some()),
+            (Section::DefaultReviewers, self.default_reviewers.is_some()),
+            (Section::MergeStrategies, self.merge_strategies.is_some()),
+            (Section::MergeChecks, self.merge_checks.is_some()),
+            (Section::Hooks, self.hooks.is_some()),
+            (Section::Webhooks, self.webhooks.is_some()),
+            (Section::RequiredBuilds, self.required_builds.is_some()),
+            (Section::Permissions, self.permissions.is_some()),
+        ];
+
+        present
+            .into_iter()
+            .filter(|(_, present)| *present)
+            .map(|(section, _)| section)
+            .collect()
+    }
+
+    pub fn repository_args(&self) -> RepositoryArgs {
+        RepositoryArgs {
+            project_key: self.project.clone(),
+            repository_slug: self.repository.clone(),
+        }
+    }
+
+    /// Sort lists and canonicalize ref names so that equal settings compare equal
+    pub fn normalize(&mut self) {
+        if let Some(name) = &mut self.default_branch {
+            if let Some(short) = name.strip_prefix("refs/heads/") {
+                *name = short.to_string();
+            }
+        }
+        if let Some(restrictions) = &mut self.branch_restrictions {
+            for restriction in restrictions.iter_mut() {
+                restriction.matcher.normalize();
+                restriction.users.sort();
+                restriction.groups.sort();
+                restriction.access_keys.sort();
+            }
+            restrictions.sort();
+        }
+        if let Some(conditions) = &mut self.default_reviewers {
+            for condition in conditions.iter_mut() {
+                condition.source.normalize();
+                condition.target.normalize();
+                condition.reviewers.sort();
+            }
+            conditions.sort();
+        }
+        if let Some(merge_strategies) = &mut self.merge_strategies {
+            merge_strategies.enabled.sort();
+        }
+        if let Some(webhooks) = &mut self.webhooks {
+            for webhook in webhooks.iter_mut() {
+                webhook.events.sort();
+            }
+            webhooks.sort();
+        }
+        if let Some(required_builds) = &mut self.required_builds {
+            for required_build in required_builds.iter_mut() {
+                required_build.ref_matcher.normalize();
+                if let Some(exempt) = &mut required_build.exempt_ref_matcher {
+                    exempt.normalize();
+                }
+                required_build.build_parent_keys.sort();
+            }
+            required_builds.sort();
+        }
+    }
+
+    fn validate(&self) -> Result<(), CommandError> {
+        let mut errors = Vec::new();
+
+        if let Some(merge_strategies) = &self.merge_strategies {
+            if !merge_strategies.enabled.contains(&merge_strategies.default) {
+                errors.push(format!(
+                    "mergeStrategies: the default strategy {} must be enabled",
+                    merge_strategies.default
+                ));
+            }
+        }
+        if let Some(webhooks) = &self.webhooks {
+            for pair in webhooks.windows(2) {
+                if pair[0].name == pair[1].name {
+                    errors.push(format!("webhooks: {} is listed more than once", pair[0].name));
+                }
+            }
+        }
+
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(CommandError::ArgumentError(errors)),
+        }
+    }
+}
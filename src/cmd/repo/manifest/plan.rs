@@ -0,0 +1,466 @@
+//! Working out the changes that bring a repository in line with a manifest.
+
+use crate::cmd::permissions::{Permission, PrincipalType};
+use crate::cmd::repo::manifest::remote::{Operation, Remote};
+use crate::cmd::repo::manifest::{
+    BranchRestriction, DefaultReviewers, Manifest, MergeStrategies, RequiredBuild,
+};
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Create,
+    Update,
+    Delete,
+}
+
+/// A difference between the manifest and the server, and the operations that resolve it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    pub action: Action,
+    pub description: String,
+    pub operations: Vec<Operation>,
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let symbol = match self.action {
+            Action::Create => '+',
+            Action::Update => '~',
+            Action::Delete => '-',
+        };
+        write!(f, "{} {}", symbol, self.description)
+    }
+}
+
+/// One line summing up a plan, e.g. `Plan: 1 to add, 0 to change, 2 to destroy.`
+pub fn summary(changes: &[Change]) -> String {
+    let count = |action| changes.iter().filter(|c| c.action == action).count();
+    format!(
+        "Plan: {} to add, {} to change, {} to destroy.",
+        count(Action::Create),
+        count(Action::Update),
+        count(Action::Delete)
+    )
+}
+
+/// The changes needed to make the fetched sections of `remote` match `desired`
+pub fn plan(desired: &Manifest, remote: &Remote) -> Vec<Change> {
+    let mut changes = Vec::new();
+
+    if let (Some(name), Some(current)) = (&desired.default_branch, &remote.default_branch) {
+        match current {
+            Some(current) if current == name => {}
+            Some(current) => changes.push(Change {
+                action: Action::Update,
+                description: format!("default branch: {} -> {}", current, name),
+                operations: vec![Operation::SetDefaultBranch(name.clone())],
+            }),
+            None => changes.push(Change {
+                action: Action::Create,
+                description: format!("default branch: {}", name),
+                operations: vec![Operation::SetDefaultBranch(name.clone())],
+            }),
+        }
+    }
+
+    if let (Some(desired), Some(current)) =
+        (&desired.branch_restrictions, &remote.branch_restrictions)
+    {
+        let diff = diff(desired, current, |a, b| {
+            a.restriction_type == b.restriction_type && a.matcher == b.matcher
+        });
+        let name = |r: &BranchRestriction| {
+            format!(
+                "branch restriction {} on {}",
+                r.restriction_type.as_param(),
+                r.matcher
+            )
+        };
+
+        for restriction in diff.create {
+            changes.push(Change {
+                action: Action::Create,
+                description: name(restriction),
+                operations: vec![Operation::CreateBranchRestriction(restriction.clone())],
+            });
+        }
+        // restrictions cannot be updated in place, so they are replaced
+        for (id, old, new) in diff.update {
+            changes.push(Change {
+                action: Action::Update,
+                description: with_details(
+                    name(new),
+                    &[
+                        changed("users", &old.users.join(", "), &new.users.join(", ")),
+                        changed("groups", &old.groups.join(", "), &new.groups.join(", ")),
+                        changed(
+                            "access keys",
+                            &join(&old.access_keys),
+                            &join(&new.access_keys),
+                        ),
+                    ],
+                ),
+                operations: vec![
+                    Operation::DeleteBranchRestriction(id),
+                    Operation::CreateBranchRestriction(new.clone()),
+                ],
+            });
+        }
+        for (id, restriction) in diff.delete {
+            changes.push(Change {
+                action: Action::Delete,
+                description: name(restriction),
+                operations: vec![Operation::DeleteBranchRestriction(id)],
+            });
+        }
+    }
+
+    if let (Some(desired), Some(current)) = (&desired.default_reviewers, &remote.default_reviewers)
+    {
+        let diff = diff(desired, current, |a, b| {
+            a.source == b.source && a.target == b.target
+        });
+        let name =
+            |c: &DefaultReviewers| format!("default reviewers from {} to {}", c.source, c.target);
+
+        for condition in diff.create {
+            changes.push(Change {
+                action: Action::Create,
+                description: name(condition),
+                operations: vec![Operation::CreateDefaultReviewers(condition.clone())],
+            });
+        }
+        for (id, old, new) in diff.update {
+            changes.push(Change {
+                action: Action::Update,
+                description: with_details(
+                    name(new),
+                    &[
+                        changed(
+                            "reviewers",
+                            &old.reviewers.join(", "),
+                            &new.reviewers.join(", "),
+                        ),
+                        changed(
+                            "required approvals",
+                            &old.required_approvals.to_string(),
+                            &new.required_approvals.to_string(),
+                        ),
+                    ],
+                ),
+                operations: vec![Operation::UpdateDefaultReviewers(id, new.clone())],
+            });
+        }
+        for (id, condition) in diff.delete {
+            changes.push(Change {
+                action: Action::Delete,
+                description: name(condition),
+                operations: vec![Operation::DeleteDefaultReviewers(id)],
+            });
+        }
+    }
+
+    if let Some(desired) = &desired.merge_strategies {
+        if remote.merge_strategies.as_ref() != Some(desired) {
+            let describe = |m: &MergeStrategies| {
+                format!("default {}, enabled {}", m.default, m.enabled.join(", "))
+            };
+            changes.push(Change {
+                action: Action::Update,
+                description: match &remote.merge_strategies {
+                    Some(current) => format!(
+                        "merge strategies: {} -> {}",
+                        describe(current),
+                        describe(desired)
+                    ),
+                    None => format!("merge strategies: {}", describe(desired)),
+                },
+                operations: vec![Operation::SetMergeStrategies(desired.clone())],
+            });
+        }
+    }
+
+    if let Some(desired) = &desired.merge_checks {
+        let current = remote.merge_checks.clone().unwrap_or_default();
+        let field = |name, old: Option<String>, new: Option<String>| match new {
+            Some(new) => changed(name, &old.unwrap_or_default(), &new),
+            None => None,
+        };
+        let details = [
+            field(
+                "required approvers",
+                current.required_approvers.map(|v| v.to_string()),
+                desired.required_approvers.map(|v| v.to_string()),
+            ),
+            field(
+                "all approvers",
+                current.required_all_approvers.map(|v| v.to_string()),
+                desired.required_all_approvers.map(|v| v.to_string()),
+            ),
+            field(
+                "all tasks complete",
+                current.required_all_tasks_complete.map(|v| v.to_string()),
+                desired.required_all_tasks_complete.map(|v| v.to_string()),
+            ),
+            field(
+                "successful builds",
+                current.required_successful_builds.map(|v| v.to_string()),
+                desired.required_successful_builds.map(|v| v.to_string()),
+            ),
+        ];
+        if details.iter().any(Option::is_some) {
+            changes.push(Change {
+                action: Action::Update,
+                description: with_details("merge checks".to_string(), &details),
+                operations: vec![Operation::SetMergeChecks(desired.clone())],
+            });
+        }
+    }
+
+    if let (Some(desired), Some(current)) = (&desired.hooks, &remote.hooks) {
+        for (key, hook) in desired {
+            let existing = current.get(key);
+            let enabled = existing.is_some_and(|existing| existing.enabled);
+            let settings_changed = hook.settings.is_some()
+                && hook.settings != existing.and_then(|existing| existing.settings.clone());
+
+            if hook.enabled && !enabled {
+                changes.push(Change {
+                    action: Action::Create,
+                    description: format!("hook {}", key),
+                    operations: vec![Operation::EnableHook(key.clone(), hook.settings.clone())],
+                });
+            } else if !hook.enabled && enabled {
+                changes.push(Change {
+                    action: Action::Delete,
+                    description: format!("hook {}", key),
+                    operations: vec![Operation::DisableHook(key.clone())],
+                });
+            } else if hook.enabled && settings_changed {
+                changes.push(Change {
+                    action: Action::Update,
+                    description: format!("hook {} (settings)", key),
+                    operations: vec![Operation::SetHookSettings(
+                        key.clone(),
+                        hook.settings.clone().unwrap_or_default(),
+                    )],
+                });
+            }
+        }
+        for (key, hook) in current {
+            if hook.enabled && !desired.contains_key(key) {
+                changes.push(Change {
+                    action: Action::Delete,
+                    description: format!("hook {}", key),
+                    operations: vec![Operation::DisableHook(key.clone())],
+                });
+            }
+        }
+    }
+
+    if let (Some(desired), Some(current)) = (&desired.webhooks, &remote.webhooks) {
+        let diff = diff(desired, current, |a, b| a.name == b.name);
+
+        for webhook in diff.create {
+            changes.push(Change {
+                action: Action::Create,
+                description: format!("webhook {}", webhook.name),
+                operations: vec![Operation::CreateWebhook(webhook.clone())],
+            });
+        }
+        for (id, old, new) in diff.update {
+            changes.push(Change {
+                action: Action::Update,
+                description: with_details(
+                    format!("webhook {}", new.name),
+                    &[
+                        changed("url", &old.url, &new.url),
+                        changed("events", &old.events.join(", "), &new.events.join(", ")),
+                        changed("active", &old.active.to_string(), &new.active.to_string()),
+                    ],
+                ),
+                operations: vec![Operation::UpdateWebhook(id, new.clone())],
+            });
+        }
+        for (id, webhook) in diff.delete {
+            changes.push(Change {
+                action: Action::Delete,
+                description: format!("webhook {}", webhook.name),
+                operations: vec![Operation::DeleteWebhook(id)],
+            });
+        }
+    }
+
+    if let (Some(desired), Some(current)) = (&desired.required_builds, &remote.required_builds) {
+        let diff = diff(desired, current, |a, b| a.ref_matcher == b.ref_matcher);
+        let name = |r: &RequiredBuild| format!("required builds on {}", r.ref_matcher);
+        let exempt = |r: &RequiredBuild| {
+            r.exempt_ref_matcher
+                .as_ref()
+                .map(|m| m.to_string())
+                .unwrap_or_default()
+        };
+
+        for required_build in diff.create {
+            changes.push(Change {
+                action: Action::Create,
+                description: format!(
+                    "{} ({})",
+                    name(required_build),
+                    required_build.build_parent_keys.join(", ")
+                ),
+                operations: vec![Operation::CreateRequiredBuild(required_build.clone())],
+            });
+        }
+        for (id, old, new) in diff.update {
+            changes.push(Change {
+                action: Action::Update,
+                description: with_details(
+                    name(new),
+                    &[
+                        changed(
+                            "builds",
+                            &old.build_parent_keys.join(", "),
+                            &new.build_parent_keys.join(", "),
+                        ),
+                        changed("exempt", &exempt(old), &exempt(new)),
+                    ],
+                ),
+                operations: vec![Operation::UpdateRequiredBuild(id, new.clone())],
+            });
+        }
+        for (id, required_build) in diff.delete {
+            changes.push(Change {
+                action: Action::Delete,
+                description: name(required_build),
+                operations: vec![Operation::DeleteRequiredBuild(id)],
+            });
+        }
+    }
+
+    if let (Some(desired), Some(current)) = (&desired.permissions, &remote.permissions) {
+        plan_grants(
+            &mut changes,
+            PrincipalType::User,
+            &desired.users,
+            &current.users,
+        );
+        plan_grants(
+            &mut changes,
+            PrincipalType::Group,
+            &desired.groups,
+            &current.groups,
+        );
+    }
+
+    changes
+}
+
+fn plan_grants(
+    changes: &mut Vec<Change>,
+    principal_type: PrincipalType,
+    desired: &BTreeMap<String, Permission>,
+    current: &BTreeMap<String, Permission>,
+) {
+    let kind = match principal_type {
+        PrincipalType::User => "user",
+        PrincipalType::Group => "group",
+    };
+
+    for (name, permission) in desired {
+        let grant = Operation::Grant(principal_type, name.clone(), *permission);
+        match current.get(name) {
+            Some(current) if current == permission => {}
+            Some(current) => changes.push(Change {
+                action: Action::Update,
+                description: format!(
+                    "{} permission {}: {} -> {}",
+                    kind, name, current, permission
+                ),
+                operations: vec![grant],
+            }),
+            None => changes.push(Change {
+                action: Action::Create,
+                description: format!("{} permission {}: {}", kind, name, permission),
+                operations: vec![grant],
+            }),
+        }
+    }
+    for (name, permission) in current {
+        if !desired.contains_key(name) {
+            changes.push(Change {
+                action: Action::Delete,
+                description: format!("{} permission {}: {}", kind, name, permission),
+                operations: vec![Operation::Revoke(principal_type, name.clone())],
+            });
+        }
+    }
+}
+
+struct Diff<'a, T> {
+    create: Vec<&'a T>,
+    update: Vec<(u64, &'a T, &'a T)>,
+    delete: Vec<(u64, &'a T)>,
+}
+
+/// Pair desired items with server side items that `same` says are the same setting. Unpaired
+/// desired items are created, pairs that differ are updated and unpaired server items deleted.
+fn diff<'a, T: PartialEq>(
+    desired: &'a [T],
+    current: &'a [(u64, T)],
+    same: impl Fn(&T, &T) -> bool,
+) -> Diff<'a, T> {
+    let mut paired = vec![false; current.len()];
+    let mut result = Diff {
+        create: Vec::new(),
+        update: Vec::new(),
+        delete: Vec::new(),
+    };
+
+    for item in desired {
+        let found = current
+            .iter()
+            .enumerate()
+            .find(|(i, (_, existing))| !paired[*i] && same(existing, item));
+        match found {
+            Some((i, (id, existing))) => {
+                paired[i] = true;
+                if existing != item {
+                    result.update.push((*id, existing, item));
+                }
+            }
+            None => result.create.push(item),
+        }
+    }
+    for (i, (id, existing)) in current.iter().enumerate() {
+        if !paired[i] {
+            result.delete.push((*id, existing));
+        }
+    }
+
+    result
+}
+
+/// A change description followed by the details that changed, e.g. `webhook ci (url: [a] -> [b])`
+fn with_details(name: String, details: &[Option<String>]) -> String {
+    let details: Vec<&str> = details.iter().flatten().map(String::as_str).collect();
+    format!("{} ({})", name, details.join("; "))
+}
+
+/// `field: old -> new`, or nothing when the value did not change
+fn changed(field: &str, old: &str, new: &str) -> Option<String> {
+    match old == new {
+        true => None,
+        false => Some(format!("{}: [{}] -> [{}]", field, old, new)),
+    }
+}
+
+fn join(values: &[u64]) -> String {
+    values
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,525 @@
+//! Reading and changing the settings of a repository on the server.
+
+use crate::bitbucket::{RefMatcher, RepositoryArgs, ScopeArgs};
+use crate::cmd::{branch, branch_restrictions, default_reviewers, webhook};
+use crate::cmd::permissions::{self, Permission, PrincipalType};
+use crate::cmd::repo::manifest::{
+    BranchRestriction, DefaultReviewers, Hook, Manifest, MergeChecks, MergeStrategies,
+    Permissions, RequiredBuild, Section, Webhook,
+};
+use crate::rest;
+use bitbucket_server_rs::client::{ApiError, Client};
+use reqwest::Method;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+/// The settings of a repository as configured on the server. Sections that were not fetched are
+/// `None`. Items that can be changed individually keep their server side id.
+#[derive(Debug, Default)]
+pub struct Remote {
+    pub default_branch: Option<Option<String>>,
+    pub branch_restrictions: Option<Vec<(u64, BranchRestriction)>>,
+    pub default_reviewers: Option<Vec<(u64, DefaultReviewers)>>,
+    pub merge_strategies: Option<MergeStrategies>,
+    pub merge_checks: Option<MergeChecks>,
+    pub hooks: Option<BTreeMap<String, Hook>>,
+    pub webhooks: Option<Vec<(u64, Webhook)>>,
+    pub required_builds: Option<Vec<(u64, RequiredBuild)>>,
+    pub permissions: Option<Permissions>,
+}
+
+/// A change to a single setting
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operation {
+    SetDefaultBranch(String),
+    CreateBranchRestriction(BranchRestriction),
+    DeleteBranchRestriction(u64),
+    CreateDefaultReviewers(DefaultReviewers),
+    UpdateDefaultReviewers(u64, DefaultReviewers),
+    DeleteDefaultReviewers(u64),
+    SetMergeStrategies(MergeStrategies),
+    SetMergeChecks(MergeChecks),
+    EnableHook(String, Option<Value>),
+    DisableHook(String),
+    SetHookSettings(String, Value),
+    CreateWebhook(Webhook),
+    UpdateWebhook(u64, Webhook),
+    DeleteWebhook(u64),
+    CreateRequiredBuild(RequiredBuild),
+    UpdateRequiredBuild(u64, RequiredBuild),
+    DeleteRequiredBuild(u64),
+    Grant(PrincipalType, String, Permission),
+    Revoke(PrincipalType, String),
+}
+
+#[derive(Deserialize)]
+struct ApiScope {
+    #[serde(rename = "type")]
+    scope_type: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiPullRequestSettings {
+    merge_config: Option<ApiMergeConfig>,
+    required_approvers: Option<u32>,
+    required_all_approvers: Option<bool>,
+    required_all_tasks_complete: Option<bool>,
+    required_successful_builds: Option<u32>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiMergeConfig {
+    default_strategy: ApiMergeStrategy,
+    strategies: Vec<ApiMergeStrategy>,
+}
+
+#[derive(Deserialize)]
+struct ApiMergeStrategy {
+    id: String,
+    #[serde(default = "enabled_default")]
+    enabled: bool,
+}
+
+fn enabled_default() -> bool {
+    true
+}
+
+#[derive(Deserialize)]
+struct ApiHook {
+    details: ApiHookDetails,
+    #[serde(default)]
+    enabled: bool,
+    scope: Option<ApiScope>,
+}
+
+#[derive(Deserialize)]
+struct ApiHookDetails {
+    key: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiRequiredBuild {
+    id: u64,
+    #[serde(default)]
+    build_parent_keys: Vec<String>,
+    ref_matcher: Value,
+    exempt_ref_matcher: Option<Value>,
+}
+
+/// Fetch the given sections of a repository's settings
+pub async fn fetch(
+    repository_args: &RepositoryArgs,
+    sections: &[Section],
+    client: &Client,
+) -> Result<Remote, ApiError> {
+    let mut remote = Remote::default();
+
+    for section in sections {
+        match section {
+            Section::DefaultBranch => {
+                remote.default_branch = Some(default_branch(repository_args, client).await?)
+            }
+            Section::BranchRestrictions => {
+                remote.branch_restrictions =
+                    Some(branch_restrictions(repository_args, client).await?)
+            }
+            Section::DefaultReviewers => {
+                remote.default_reviewers = Some(default_reviewers(repository_args, client).await?)
+            }
+            Section::MergeStrategies => {
+                remote.merge_strategies = pull_request_settings(repository_args, client)
+                    .await?
+                    .and_then(|settings| settings.merge_config)
+                    .map(merge_strategies)
+            }
+            Section::MergeChecks => {
+                remote.merge_checks = pull_request_settings(repository_args, client)
+                    .await?
+                    .map(merge_checks)
+            }
+            Section::Hooks => remote.hooks = Some(hooks(repository_args, client).await?),
+            Section::Webhooks => remote.webhooks = Some(webhooks(repository_args, client).await?),
+            Section::RequiredBuilds => {
+                remote.required_builds = Some(required_builds(repository_args, client).await?)
+            }
+            Section::Permissions => {
+                remote.permissions = Some(grants(repository_args, client).await?)
+            }
+        }
+    }
+
+    Ok(remote)
+}
+
+fn without_ids<T>(items: Vec<(u64, T)>) -> Vec<T> {
+    items.into_iter().map(|(_, item)| item).collect()
+}
+
+impl Remote {
+    /// The fetched settings as a manifest for the given repository
+    pub fn into_manifest(self, repository_args: &RepositoryArgs) -> Manifest {
+        let mut manifest = Manifest {
+            project: repository_args.project_key.clone(),
+            repository: repository_args.repository_slug.clone(),
+            default_branch: self.default_branch.flatten(),
+            branch_restrictions: self.branch_restrictions.map(without_ids),
+            default_reviewers: self.default_reviewers.map(without_ids),
+            merge_strategies: self.merge_strategies,
+            merge_checks: self.merge_checks,
+            hooks: self.hooks,
+            webhooks: self.webhooks.map(without_ids),
+            required_builds: self.required_builds.map(without_ids),
+            permissions: self.permissions,
+        };
+        manifest.normalize();
+        manifest
+    }
+}
+
+/// Apply a single change to a repository
+pub async fn execute(
+    repository_args: &RepositoryArgs,
+    operation: &Operation,
+    client: &Client,
+) -> Result<(), ApiError> {
+    match operation {
+        Operation::SetDefaultBranch(name) => {
+            branch::default::update(repository_args, name, client).await
+        }
+        Operation::CreateBranchRestriction(restriction) => {
+            let scope = ScopeArgs::from(repository_args);
+            branch_restrictions::create(&scope, restriction, client)
+                .await
+                .map(|_| ())
+        }
+        Operation::DeleteBranchRestriction(id) => {
+            branch_restrictions::delete(&ScopeArgs::from(repository_args), *id, client).await
+        }
+        Operation::CreateDefaultReviewers(condition) => {
+            let scope = ScopeArgs::from(repository_args);
+            default_reviewers::create(&scope, condition, client)
+                .await
+                .map(|_| ())
+        }
+        Operation::UpdateDefaultReviewers(id, condition) => {
+            let scope = ScopeArgs::from(repository_args);
+            default_reviewers::update(&scope, *id, condition, client)
+                .await
+                .map(|_| ())
+        }
+        Operation::DeleteDefaultReviewers(id) => {
+            default_reviewers::delete(&ScopeArgs::from(repository_args), *id, client).await
+        }
+        Operation::SetMergeStrategies(merge_strategies) => {
+            let strategies: Vec<Value> = merge_strategies
+                .enabled
+                .iter()
+                .map(|id| json!({ "id": id }))
+                .collect();
+            let body = json!({
+                "mergeConfig": {
+                    "defaultStrategy": { "id": merge_strategies.default },
+                    "strategies": strategies,
+                }
+            });
+            let _: Option<Value> = rest::post(
+                client,
+                &uri(repository_args, "api", "settings/pull-requests"),
+                &body,
+            )
+            .await?;
+            Ok(())
+        }
+        Operation::SetMergeChecks(merge_checks) => {
+            let _: Option<Value> = rest::post(
+                client,
+                &uri(repository_args, "api", "settings/pull-requests"),
+                merge_checks,
+            )
+            .await?;
+            Ok(())
+        }
+        Operation::EnableHook(key, settings) => {
+            let path = format!("settings/hooks/{}/enabled", key);
+            let request = rest::request(client, Method::PUT, &uri(repository_args, "api", &path));
+            let request = match settings {
+                Some(settings) => request.json(settings),
+                None => request,
+            };
+            rest::send(client, request).await.map(|_| ())
+        }
+        Operation::DisableHook(key) => {
+            let path = format!("settings/hooks/{}/enabled", key);
+            rest::delete::<()>(client, &uri(repository_args, "api", &path), None).await
+        }
+        Operation::SetHookSettings(key, settings) => {
+            let path = format!("settings/hooks/{}/settings", key);
+            let _: Option<Value> =
+                rest::put(client, &uri(repository_args, "api", &path), settings).await?;
+            Ok(())
+        }
+        Operation::CreateWebhook(spec) => {
+            let scope = ScopeArgs::from(repository_args);
+            webhook::create(&scope, spec, None, client).await.map(|_| ())
+        }
+        Operation::UpdateWebhook(id, spec) => {
+            let scope = ScopeArgs::from(repository_args);
+            webhook::update(&scope, *id, |webhook| *webhook = spec.clone(), None, client)
+                .await
+                .map(|_| ())
+        }
+        Operation::DeleteWebhook(id) => {
+            webhook::delete(&ScopeArgs::from(repository_args), *id, client).await
+        }
+        Operation::CreateRequiredBuild(required_build) => {
+            let _: Option<Value> = rest::post(
+                client,
+                &uri(repository_args, "required-builds", "condition"),
+                &required_build_body(required_build),
+            )
+            .await?;
+            Ok(())
+        }
+        Operation::UpdateRequiredBuild(id, required_build) => {
+            let path = format!("condition/{}", id);
+            let _: Option<Value> = rest::put(
+                client,
+                &uri(repository_args, "required-builds", &path),
+                &required_build_body(required_build),
+            )
+            .await?;
+            Ok(())
+        }
+        Operation::DeleteRequiredBuild(id) => {
+            let path = format!("condition/{}", id);
+            rest::delete::<()>(
+                client,
+                &uri(repository_args, "required-builds", &path),
+                None,
+            )
+            .await
+        }
+        Operation::Grant(principal_type, name, permission) => {
+            permissions::grant::grant(
+                &ScopeArgs::from(repository_args),
+                *principal_type,
+                name,
+                *permission,
+                client,
+            )
+            .await
+        }
+        Operation::Revoke(principal_type, name) => {
+            permissions::revoke::revoke(
+                &ScopeArgs::from(repository_args),
+                *principal_type,
+                name,
+                client,
+            )
+            .await
+        }
+    }
+}
+
+/// URI of a repository resource of one of the Bitbucket REST APIs, e.g. `branch-permissions`
+fn uri(repository_args: &RepositoryArgs, api: &str, path: &str) -> String {
+    format!(
+        "{}/latest/projects/{}/repos/{}/{}",
+        api, repository_args.project_key, repository_args.repository_slug, path
+    )
+}
+
+/// Whether a setting is defined on the repository itself rather than inherited from the project
+fn is_repository_scope(scope: Option<&str>) -> bool {
+    scope.is_none_or(|scope| scope == "REPOSITORY")
+}
+
+fn ref_matcher(matcher: &Value) -> Result<RefMatcher, ApiError> {
+    RefMatcher::from_api(matcher)
+        .ok_or_else(|| ApiError::DeserializationError(format!("unknown ref matcher {}", matcher)))
+}
+
+async fn default_branch(
+    repository_args: &RepositoryArgs,
+    client: &Client,
+) -> Result<Option<String>, ApiError> {
+    match branch::default::fetch(repository_args, client).await {
+        Ok(branch) => Ok(Some(branch.display_id)),
+        // an empty repository has no default branch yet
+        Err(ApiError::HttpClientError(404, _)) | Err(ApiError::ResponseError) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+async fn branch_restrictions(
+    repository_args: &RepositoryArgs,
+    client: &Client,
+) -> Result<Vec<(u64, BranchRestriction)>, ApiError> {
+    let restrictions =
+        branch_restrictions::list::fetch(&ScopeArgs::from(repository_args), client).await?;
+
+    Ok(restrictions
+        .into_iter()
+        .filter(|restriction| is_repository_scope(restriction.scope.as_deref()))
+        .map(|restriction| (restriction.id, restriction.restriction))
+        .collect())
+}
+
+async fn default_reviewers(
+    repository_args: &RepositoryArgs,
+    client: &Client,
+) -> Result<Vec<(u64, DefaultReviewers)>, ApiError> {
+    let conditions =
+        default_reviewers::list::fetch(&ScopeArgs::from(repository_args), client).await?;
+
+    Ok(conditions
+        .into_iter()
+        .filter(|condition| is_repository_scope(condition.scope.as_deref()))
+        .map(|condition| (condition.id, condition.condition))
+        .collect())
+}
+
+async fn pull_request_settings(
+    repository_args: &RepositoryArgs,
+    client: &Client,
+) -> Result<Option<ApiPullRequestSettings>, ApiError> {
+    rest::get(
+        client,
+        &uri(repository_args, "api", "settings/pull-requests"),
+        &[],
+    )
+    .await
+}
+
+fn merge_strategies(merge_config: ApiMergeConfig) -> MergeStrategies {
+    let mut enabled: Vec<String> = merge_config
+        .strategies
+        .into_iter()
+        .filter(|strategy| strategy.enabled)
+        .map(|strategy| strategy.id)
+        .collect();
+    enabled.sort();
+
+    MergeStrategies {
+        default: merge_config.default_strategy.id,
+        enabled,
+    }
+}
+
+fn merge_checks(settings: ApiPullRequestSettings) -> MergeChecks {
+    MergeChecks {
+        required_approvers: settings.required_approvers,
+        required_all_approvers: settings.required_all_approvers,
+        required_all_tasks_complete: settings.required_all_tasks_complete,
+        required_successful_builds: settings.required_successful_builds,
+    }
+}
+
+/// The hooks enabled on the repository itself, with their settings
+async fn hooks(
+    repository_args: &RepositoryArgs,
+    client: &Client,
+) -> Result<BTreeMap<String, Hook>, ApiError> {
+    let hooks: Vec<ApiHook> =
+        rest::get_paged(client, &uri(repository_args, "api", "settings/hooks"), &[]).await?;
+
+    let mut result = BTreeMap::new();
+    for hook in hooks {
+        let scope = hook.scope.as_ref().map(|scope| scope.scope_type.as_str());
+        if !hook.enabled || !is_repository_scope(scope) {
+            continue;
+        }
+        let path = format!("settings/hooks/{}/settings", hook.details.key);
+        let settings: Option<Value> =
+            rest::get(client, &uri(repository_args, "api", &path), &[]).await?;
+
+        result.insert(
+            hook.details.key,
+            Hook {
+                enabled: true,
+                settings: settings.filter(|settings| !settings.is_null()),
+            },
+        );
+    }
+
+    Ok(result)
+}
+
+async fn webhooks(
+    repository_args: &RepositoryArgs,
+    client: &Client,
+) -> Result<Vec<(u64, Webhook)>, ApiError> {
+    let webhooks = webhook::list::fetch(&ScopeArgs::from(repository_args), client).await?;
+
+    Ok(webhooks
+        .into_iter()
+        .map(|webhook| (webhook.id, webhook.webhook))
+        .collect())
+}
+
+async fn required_builds(
+    repository_args: &RepositoryArgs,
+    client: &Client,
+) -> Result<Vec<(u64, RequiredBuild)>, ApiError> {
+    let conditions: Vec<ApiRequiredBuild> = rest::get_paged(
+        client,
+        &uri(repository_args, "required-builds", "conditions"),
+        &[],
+    )
+    .await?;
+
+    let mut result = Vec::new();
+    for condition in conditions {
+        let mut build_parent_keys = condition.build_parent_keys;
+        build_parent_keys.sort();
+
+        result.push((
+            condition.id,
+            RequiredBuild {
+                ref_matcher: ref_matcher(&condition.ref_matcher)?,
+                build_parent_keys,
+                exempt_ref_matcher: condition
+                    .exempt_ref_matcher
+                    .as_ref()
+                    .map(ref_matcher)
+                    .transpose()?,
+            },
+        ));
+    }
+
+    Ok(result)
+}
+
+async fn grants(
+    repository_args: &RepositoryArgs,
+    client: &Client,
+) -> Result<Permissions, ApiError> {
+    let mut result = Permissions::default();
+
+    for grant in permissions::list::fetch(&ScopeArgs::from(repository_args), client).await? {
+        let permission = Permission::from_api(&grant.permission).ok_or_else(|| {
+            ApiError::DeserializationError(format!("unknown permission {}", grant.permission))
+        })?;
+        match grant.principal_type {
+            PrincipalType::User => result.users.insert(grant.name, permission),
+            PrincipalType::Group => result.groups.insert(grant.name, permission),
+        };
+    }
+
+    Ok(result)
+}
+
+fn required_build_body(required_build: &RequiredBuild) -> Value {
+    let mut body = json!({
+        "buildParentKeys": required_build.build_parent_keys,
+        "refMatcher": required_build.ref_matcher.to_api(),
+    });
+    if let Some(exempt) = &required_build.exempt_ref_matcher {
+        body["exemptRefMatcher"] = exempt.to_api();
+    }
+    body
+}
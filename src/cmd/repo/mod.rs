@@ -0,0 +1,130 @@
+pub mod apply;
+pub mod clone_url;
+pub mod copy_settings;
+pub mod create;
+pub mod delete;
+pub mod export;
+pub mod fork;
+pub mod get;
+pub mod list;
+pub mod manifest;
+pub mod update;
+
+use crate::bitbucket::RepositoryArgs;
+use crate::cmd::CommandResult;
+use bitbucket_server_rs::client::Client;
+use clap_derive::{Args, Subcommand};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The columns of a table of repositories
+pub(crate) const COLUMNS: &[&str] = &["project.key", "slug", "name", "state", "public", "archived"];
+
+#[derive(Debug, Args)]
+pub struct RepoArgs {
+    #[command(subcommand)]
+    command: RepoSubcommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum RepoSubcommands {
+    /// List the repositories of a project, or every repository visible to you
+    List(list::RepoListArgs),
+    /// Get a repository
+    Get(RepositoryArgs),
+    /// Create a repository
+    Create(create::RepoCreateArgs),
+    /// Update a repository
+    Update(update::RepoUpdateArgs),
+    /// Fork a repository into your personal project or another project
+    Fork(fork::RepoForkArgs),
+    /// Delete a repository
+    Delete(delete::RepoDeleteArgs),
+    /// Print the URL to clone a repository with
+    CloneUrl(clone_url::RepoCloneUrlArgs),
+    /// Make the settings of a repository match a YAML manifest
+    Apply(apply::RepoApplyArgs),
+    /// Export the settings of a repository, or of every repository in a project, as manifests
+    Export(export::RepoExportArgs),
+    /// Copy the settings of a template repository to other repositories
+    CopySettings(copy_settings::RepoCopySettingsArgs),
+}
+
+/// A repository as returned by the Bitbucket API
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Repository {
+    pub id: u64,
+    pub slug: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub scm_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    #[serde(default)]
+    pub forkable: bool,
+    #[serde(default)]
+    pub public: bool,
+    #[serde(default)]
+    pub archived: bool,
+    pub project: RepositoryProject,
+    /// The repository this one was forked from, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub origin: Option<Value>,
+    #[serde(default)]
+    pub links: Links,
+}
+
+/// The project a repository belongs to
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepositoryProject {
+    pub key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// `NORMAL`, or `PERSONAL` for a user's personal project
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub project_type: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Links {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub clone: Vec<Link>,
+    #[serde(rename = "self", default, skip_serializing_if = "Vec::is_empty")]
+    pub self_links: Vec<Link>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Link {
+    pub href: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// Repo command handler
+pub async fn handle(args: &RepoArgs, client: &Client) -> CommandResult {
+    match &args.command {
+        RepoSubcommands::List(list_args) => list::handle(list_args, client).await,
+        RepoSubcommands::Get(repository_args) => get::handle(repository_args, client).await,
+        RepoSubcommands::Create(create_args) => create::handle(create_args, client).await,
+        RepoSubcommands::Update(update_args) => update::handle(update_args, client).await,
+        RepoSubcommands::Fork(fork_args) => fork::handle(fork_args, client).await,
+        RepoSubcommands::Delete(delete_args) => delete::handle(delete_args, client).await,
+        RepoSubcommands::CloneUrl(clone_url_args) => clone_url::handle(clone_url_args, client).await,
+        RepoSubcommands::Apply(apply_args) => apply::handle(apply_args, client).await,
+        RepoSubcommands::Export(export_args) => export::handle(export_args, client).await,
+        RepoSubcommands::CopySettings(copy_args) => copy_settings::handle(copy_args, client).await,
+    }
+}
+
+/// URI of a single repository
+pub(crate) fn uri(repository_args: &RepositoryArgs) -> String {
+    format!(
+        "api/latest/projects/{}/repos/{}",
+        repository_args.project_key, repository_args.repository_slug
+    )
+}
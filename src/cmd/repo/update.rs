@@ -0,0 +1,58 @@
+use crate::bitbucket::RepositoryArgs;
+use crate::cmd::repo::{uri, Repository};
+use crate::cmd::CommandResult;
+use crate::output;
+use crate::rest;
+use bitbucket_server_rs::client::Client;
+use clap_derive::Args;
+use serde_json::json;
+
+#[derive(Debug, Args)]
+pub struct RepoUpdateArgs {
+    /// Refers to the repository
+    #[command(flatten)]
+    pub repository_args: RepositoryArgs,
+    /// The new name of the repository. This also changes the slug.
+    #[arg(long)]
+    pub name: Option<String>,
+    /// Whether the repository can be forked
+    #[arg(long)]
+    pub forkable: Option<bool>,
+    /// The default branch of the repository. e.g. `main`
+    #[arg(long)]
+    pub default_branch: Option<String>,
+    /// Whether the repository is readable without logging in
+    #[arg(long)]
+    pub public: Option<bool>,
+    /// A description of the repository
+    #[arg(long)]
+    pub description: Option<String>,
+}
+
+/// Update a repository. Only the given fields are changed.
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-repository/#api-api-latest-projects-projectkey-repos-repositoryslug-put)
+pub async fn handle(update_args: &RepoUpdateArgs, client: &Client) -> CommandResult {
+    let mut body = json!({});
+    if let Some(name) = &update_args.name {
+        body["name"] = json!(name);
+    }
+    if let Some(forkable) = update_args.forkable {
+        body["forkable"] = json!(forkable);
+    }
+    if let Some(default_branch) = &update_args.default_branch {
+        body["defaultBranch"] = json!(default_branch);
+    }
+    if let Some(public) = update_args.public {
+        body["public"] = json!(public);
+    }
+    if let Some(description) = &update_args.description {
+        body["description"] = json!(description);
+    }
+
+    let repository: Option<Repository> =
+        rest::put(client, &uri(&update_args.repository_args), &body).await?;
+
+    output::print(&repository, super::COLUMNS)?;
+    Ok(())
+}
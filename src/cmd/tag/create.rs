@@ -0,0 +1,48 @@
+use crate::bitbucket::RepositoryArgs;
+use crate::cmd::tag::Tag;
+use crate::cmd::CommandResult;
+use crate::output;
+use crate::rest;
+use bitbucket_server_rs::client::Client;
+use clap_derive::Args;
+use serde_json::json;
+
+#[derive(Debug, Args)]
+pub struct TagCreateArgs {
+    /// The name of the new tag. e.g. `v1.2.0`
+    pub name: String,
+    /// The commit ID to tag
+    #[arg(short, long)]
+    pub commit_id: String,
+    /// The tag message. When given, an annotated tag is created instead of a lightweight one.
+    #[arg(short, long)]
+    pub message: Option<String>,
+}
+
+/// Create a tag
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-repository/#api-api-latest-projects-projectkey-repos-repositoryslug-tags-post)
+pub async fn handle(
+    repository_args: &RepositoryArgs,
+    create_args: &TagCreateArgs,
+    client: &Client,
+) -> CommandResult {
+    let uri = format!(
+        "api/latest/projects/{}/repos/{}/tags",
+        repository_args.project_key, repository_args.repository_slug
+    );
+
+    let tag: Option<Tag> = rest::post(
+        client,
+        &uri,
+        &json!({
+            "name": create_args.name,
+            "startPoint": create_args.commit_id,
+            "message": create_args.message,
+        }),
+    )
+    .await?;
+
+    output::print(&tag, super::COLUMNS)?;
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use crate::bitbucket::RepositoryArgs;
+use crate::cmd::CommandResult;
+use crate::output;
+use crate::rest;
+use bitbucket_server_rs::client::Client;
+use clap_derive::Args;
+
+#[derive(Debug, Args)]
+pub struct TagDeleteArgs {
+    /// The name of the tag to delete. e.g. `v1.2.0`
+    pub name: String,
+}
+
+/// Delete a tag
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-repository/#api-git-latest-projects-projectkey-repos-repositoryslug-tags-name-delete)
+pub async fn handle(
+    repository_args: &RepositoryArgs,
+    delete_args: &TagDeleteArgs,
+    client: &Client,
+) -> CommandResult {
+    let uri = format!(
+        "git/latest/projects/{}/repos/{}/tags/{}",
+        repository_args.project_key,
+        repository_args.repository_slug,
+        rest::encode_path(&delete_args.name)
+    );
+
+    rest::delete::<()>(client, &uri, None).await?;
+
+    output::message(format!("Tag {} deleted.", delete_args.name));
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use crate::bitbucket::RepositoryArgs;
+use crate::cmd::tag::Tag;
+use crate::cmd::CommandResult;
+use crate::output;
+use crate::rest;
+use bitbucket_server_rs::client::Client;
+use clap_derive::Args;
+
+#[derive(Debug, Args)]
+pub struct TagGetArgs {
+    /// The name of the tag. e.g. `v1.2.0`
+    pub name: String,
+}
+
+/// Get a single tag
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-repository/#api-api-latest-projects-projectkey-repos-repositoryslug-tags-name-get)
+pub async fn handle(
+    repository_args: &RepositoryArgs,
+    get_args: &TagGetArgs,
+    client: &Client,
+) -> CommandResult {
+    let uri = format!(
+        "api/latest/projects/{}/repos/{}/tags/{}",
+        repository_args.project_key,
+        repository_args.repository_slug,
+        rest::encode_path(&get_args.name)
+    );
+
+    let tag: Option<Tag> = rest::get(client, &uri, &[]).await?;
+
+    output::print(&tag, super::COLUMNS)?;
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use crate::bitbucket::{RefOrder, RepositoryArgs};
+use crate::cmd::tag::Tag;
+use crate::cmd::CommandResult;
+use crate::output;
+use crate::rest;
+use bitbucket_server_rs::client::Client;
+use clap_derive::Args;
+
+#[derive(Debug, Args)]
+pub struct TagListArgs {
+    /// Only list tags starting with this prefix. e.g. `v1.`
+    #[arg(long)]
+    pub filter: Option<String>,
+    /// The order in which to list tags
+    #[arg(long, value_enum)]
+    pub order_by: Option<RefOrder>,
+}
+
+/// List the tags of a repository
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-repository/#api-api-latest-projects-projectkey-repos-repositoryslug-tags-get)
+pub async fn handle(
+    repository_args: &RepositoryArgs,
+    list_args: &TagListArgs,
+    client: &Client,
+) -> CommandResult {
+    let uri = format!(
+        "api/latest/projects/{}/repos/{}/tags",
+        repository_args.project_key, repository_args.repository_slug
+    );
+
+    let mut params = vec![];
+    if let Some(filter) = &list_args.filter {
+        params.push(("filterText", filter.to_string()));
+    }
+    if let Some(order_by) = list_args.order_by {
+        params.push(("orderBy", order_by.as_param().to_string()));
+    }
+
+    let tags: Vec<Tag> = rest::get_paged(client, &uri, &params).await?;
+
+    // the API matches the filter anywhere in the name, so narrow it down to a prefix
+    let tags: Vec<Tag> = match &list_args.filter {
+        Some(prefix) => tags
+            .into_iter()
+            .filter(|tag| tag.display_id.starts_with(prefix.as_str()))
+            .collect(),
+        None => tags,
+    };
+
+    output::print(&tags, super::COLUMNS)?;
+    Ok(())
+}
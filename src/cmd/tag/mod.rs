@@ -0,0 +1,69 @@
+pub mod create;
+pub mod delete;
+pub mod get;
+pub mod list;
+
+use crate::bitbucket::RepositoryArgs;
+use crate::cmd::CommandResult;
+use bitbucket_server_rs::client::Client;
+use clap_derive::{Args, Subcommand};
+use serde::{Deserialize, Serialize};
+
+/// The columns of a table of tags
+pub(crate) const COLUMNS: &[&str] = &["displayId", "latestCommit", "hash"];
+
+/// Common args for tag operations
+#[derive(Debug, Args)]
+pub struct TagArgs {
+    /// Refers to the repository
+    #[command(flatten)]
+    repository_args: RepositoryArgs,
+    #[command(subcommand)]
+    command: TagSubcommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum TagSubcommands {
+    /// List tags
+    List(list::TagListArgs),
+    /// Get a single tag
+    Get(get::TagGetArgs),
+    /// Create a lightweight or annotated tag on a commit
+    Create(create::TagCreateArgs),
+    /// Delete a tag
+    Delete(delete::TagDeleteArgs),
+}
+
+/// A tag as returned by the Bitbucket API
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Tag {
+    /// The fully qualified ref, e.g. `refs/tags/v1.2.0`
+    pub id: String,
+    /// The short name, e.g. `v1.2.0`
+    pub display_id: String,
+    #[serde(rename = "type")]
+    pub ref_type: String,
+    /// The tagged commit
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latest_commit: Option<String>,
+    /// The hash of the tag object. Only present for annotated tags.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
+}
+
+/// Tag command handler
+pub async fn handle(args: &TagArgs, client: &Client) -> CommandResult {
+    let repository_args = &args.repository_args;
+
+    match &args.command {
+        TagSubcommands::List(list_args) => list::handle(repository_args, list_args, client).await,
+        TagSubcommands::Get(get_args) => get::handle(repository_args, get_args, client).await,
+        TagSubcommands::Create(create_args) => {
+            create::handle(repository_args, create_args, client).await
+        }
+        TagSubcommands::Delete(delete_args) => {
+            delete::handle(repository_args, delete_args, client).await
+        }
+    }
+}
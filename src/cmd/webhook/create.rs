@@ -0,0 +1,54 @@
+use crate::bitbucket::ScopeArgs;
+use crate::cmd::webhook::{self, secret_from_env, Webhook};
+use crate::cmd::CommandResult;
+use crate::output;
+use bitbucket_server_rs::client::Client;
+use clap_derive::Args;
+
+#[derive(Debug, Args)]
+pub struct WebhookCreateArgs {
+    /// The name of the webhook
+    #[arg(long)]
+    pub name: String,
+    /// The URL to post events to
+    #[arg(long)]
+    pub url: String,
+    /// An event to subscribe to, e.g. `repo:refs_changed`, `pr:opened` or `pr:merged`. May be
+    /// repeated.
+    #[arg(long, required = true)]
+    pub event: Vec<String>,
+    /// The environment variable holding the secret to sign requests with
+    #[arg(long)]
+    pub secret_env: Option<String>,
+    /// Create the webhook inactive, so that it does not fire yet
+    #[arg(long)]
+    pub inactive: bool,
+}
+
+/// Create a webhook
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-repository/#api-api-latest-projects-projectkey-repos-repositoryslug-webhooks-post)
+pub async fn handle(
+    scope: &ScopeArgs,
+    create_args: &WebhookCreateArgs,
+    client: &Client,
+) -> CommandResult {
+    let secret = match &create_args.secret_env {
+        Some(name) => Some(secret_from_env(name)?),
+        None => None,
+    };
+    let mut events = create_args.event.clone();
+    events.sort();
+    events.dedup();
+
+    let spec = Webhook {
+        name: create_args.name.clone(),
+        url: create_args.url.clone(),
+        events,
+        active: !create_args.inactive,
+    };
+    let created = webhook::create(scope, &spec, secret.as_deref(), client).await?;
+
+    output::print(&created, super::COLUMNS)?;
+    Ok(())
+}
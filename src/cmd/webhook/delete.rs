@@ -0,0 +1,26 @@
+use crate::bitbucket::ScopeArgs;
+use crate::cmd::webhook;
+use crate::cmd::CommandResult;
+use crate::output;
+use bitbucket_server_rs::client::Client;
+use clap_derive::Args;
+
+#[derive(Debug, Args)]
+pub struct WebhookDeleteArgs {
+    /// The id of the webhook, as shown by `list`
+    pub id: u64,
+}
+
+/// Delete a webhook
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-repository/#api-api-latest-projects-projectkey-repos-repositoryslug-webhooks-webhookid-delete)
+pub async fn handle(
+    scope: &ScopeArgs,
+    delete_args: &WebhookDeleteArgs,
+    client: &Client,
+) -> CommandResult {
+    webhook::delete(scope, delete_args.id, client).await?;
+
+    output::message(format!("Webhook {} deleted.", delete_args.id));
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use crate::bitbucket::ScopeArgs;
+use crate::cmd::webhook::uri;
+use crate::cmd::CommandResult;
+use crate::output;
+use crate::rest;
+use bitbucket_server_rs::client::{ApiError, Client};
+use clap::ValueEnum;
+use clap_derive::Args;
+use serde_json::{json, Value};
+
+/// The columns of the table output
+const COLUMNS: &[&str] = &[
+    "latest.event",
+    "latest.result.outcome",
+    "latest.request.url",
+    "latest.response.statusCode",
+];
+
+#[derive(Debug, Args)]
+pub struct WebhookDeliveriesArgs {
+    /// The id of the webhook, as shown by `list`
+    pub id: u64,
+    /// Only consider deliveries of this event, e.g. `repo:refs_changed`
+    #[arg(long)]
+    pub event: Option<String>,
+    /// Only show the latest delivery with this outcome
+    #[arg(long)]
+    pub outcome: Option<Outcome>,
+}
+
+/// The outcome of a webhook delivery
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Outcome {
+    /// The URL answered with a 2xx status
+    Success,
+    /// The URL answered with another status
+    Failure,
+    /// The request could not be sent, e.g. the host did not resolve or timed out
+    Error,
+}
+
+impl Outcome {
+    pub fn as_param(&self) -> &'static str {
+        match self {
+            Outcome::Success => "SUCCESS",
+            Outcome::Failure => "FAILURE",
+            Outcome::Error => "ERROR",
+        }
+    }
+}
+
+/// Show the latest delivery of a webhook, with its request and response, and the delivery counts
+/// per outcome
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-repository/#api-api-latest-projects-projectkey-repos-repositoryslug-webhooks-webhookid-latest-get)
+pub async fn handle(
+    scope: &ScopeArgs,
+    deliveries_args: &WebhookDeliveriesArgs,
+    client: &Client,
+) -> CommandResult {
+    let id = deliveries_args.id;
+    let mut params = Vec::new();
+    if let Some(event) = &deliveries_args.event {
+        params.push(("event", event.clone()));
+    }
+
+    let statistics: Value = rest::get(
+        client,
+        &format!("{}/{}/statistics/summary", uri(scope), id),
+        &params,
+    )
+    .await?
+    .ok_or(ApiError::ResponseError)?;
+
+    if let Some(outcome) = deliveries_args.outcome {
+        params.push(("outcome", outcome.as_param().to_string()));
+    }
+    // no content when the webhook has not been delivered yet
+    let latest: Option<Value> =
+        rest::get(client, &format!("{}/{}/latest", uri(scope), id), &params).await?;
+
+    output::print(
+        &json!({ "latest": latest, "statistics": statistics }),
+        COLUMNS,
+    )?;
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use crate::bitbucket::ScopeArgs;
+use crate::cmd::webhook::{uri, ApiWebhook, ConfiguredWebhook};
+use crate::cmd::CommandResult;
+use crate::output;
+use crate::rest;
+use bitbucket_server_rs::client::{ApiError, Client};
+use clap_derive::Args;
+
+#[derive(Debug, Args)]
+pub struct WebhookGetArgs {
+    /// The id of the webhook, as shown by `list`
+    pub id: u64,
+}
+
+/// Get a webhook, with statistics on its deliveries
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-repository/#api-api-latest-projects-projectkey-repos-repositoryslug-webhooks-webhookid-get)
+pub async fn handle(
+    scope: &ScopeArgs,
+    get_args: &WebhookGetArgs,
+    client: &Client,
+) -> CommandResult {
+    let webhook = fetch(scope, get_args.id, true, client).await?;
+
+    output::print(&webhook, super::COLUMNS)?;
+    Ok(())
+}
+
+/// Fetch a webhook by id
+pub(crate) async fn fetch(
+    scope: &ScopeArgs,
+    id: u64,
+    statistics: bool,
+    client: &Client,
+) -> Result<ConfiguredWebhook, ApiError> {
+    let webhook: ApiWebhook = rest::get(
+        client,
+        &format!("{}/{}", uri(scope), id),
+        &[("statistics", statistics.to_string())],
+    )
+    .await?
+    .ok_or(ApiError::ResponseError)?;
+
+    Ok(webhook.into())
+}
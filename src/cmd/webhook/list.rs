@@ -0,0 +1,26 @@
+use crate::bitbucket::ScopeArgs;
+use crate::cmd::webhook::{uri, ApiWebhook, ConfiguredWebhook};
+use crate::cmd::CommandResult;
+use crate::output;
+use crate::rest;
+use bitbucket_server_rs::client::{ApiError, Client};
+
+/// List the webhooks of a project or repository
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-repository/#api-api-latest-projects-projectkey-repos-repositoryslug-webhooks-get)
+pub async fn handle(scope: &ScopeArgs, client: &Client) -> CommandResult {
+    let webhooks = fetch(scope, client).await?;
+
+    output::print(&webhooks, super::COLUMNS)?;
+    Ok(())
+}
+
+/// Fetch every webhook of a project or repository
+pub(crate) async fn fetch(
+    scope: &ScopeArgs,
+    client: &Client,
+) -> Result<Vec<ConfiguredWebhook>, ApiError> {
+    let webhooks: Vec<ApiWebhook> = rest::get_paged(client, &uri(scope), &[]).await?;
+
+    Ok(webhooks.into_iter().map(ConfiguredWebhook::from).collect())
+}
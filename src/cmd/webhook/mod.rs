@@ -0,0 +1,230 @@
+pub mod create;
+pub mod delete;
+pub mod deliveries;
+pub mod get;
+pub mod list;
+pub mod serve;
+pub mod test;
+pub mod update;
+
+use crate::bitbucket::ScopeArgs;
+use crate::cmd::{CommandError, CommandResult};
+use crate::rest;
+use bitbucket_server_rs::client::{ApiError, Client};
+use clap_derive::{Args, Subcommand};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// The columns of a table of webhooks
+pub(crate) const COLUMNS: &[&str] = &["id", "name", "url", "events", "active", "scope"];
+
+/// Common args for webhook operations
+#[derive(Debug, Args)]
+pub struct WebhookArgs {
+    /// The project key. Required by every subcommand but `serve`.
+    #[arg(short = 'k', long)]
+    project_key: Option<String>,
+    /// The repository slug. e.g. `my-repository-name`. Without it, the project is used.
+    #[arg(short, long, requires = "project_key")]
+    repository_slug: Option<String>,
+    #[command(subcommand)]
+    command: WebhookSubcommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum WebhookSubcommands {
+    /// List the webhooks
+    List,
+    /// Get a webhook and its delivery statistics
+    Get(get::WebhookGetArgs),
+    /// Create a webhook
+    Create(create::WebhookCreateArgs),
+    /// Change a webhook
+    Update(update::WebhookUpdateArgs),
+    /// Delete a webhook
+    Delete(delete::WebhookDeleteArgs),
+    /// Send a test request to a webhook's URL
+    Test(test::WebhookTestArgs),
+    /// Show the latest delivery of a webhook and its statistics
+    Deliveries(deliveries::WebhookDeliveriesArgs),
+    /// Receive webhook events and run commands or actions on them
+    Serve(serve::WebhookServeArgs),
+}
+
+/// A webhook, which posts the events it subscribes to to a URL
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct Webhook {
+    /// Webhooks are matched by name, so it must be unique within the repository
+    pub name: String,
+    pub url: String,
+    /// e.g. `repo:refs_changed` or `pr:merged`
+    pub events: Vec<String>,
+    #[serde(default = "active_default")]
+    pub active: bool,
+}
+
+fn active_default() -> bool {
+    true
+}
+
+/// A webhook as stored on the server
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfiguredWebhook {
+    pub id: u64,
+    /// `project` or `repository`, where the webhook is defined
+    pub scope: String,
+    #[serde(flatten)]
+    pub webhook: Webhook,
+    /// Delivery counts, only fetched by `get`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub statistics: Option<Value>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiWebhook {
+    id: u64,
+    name: String,
+    url: String,
+    #[serde(default)]
+    events: Vec<String>,
+    #[serde(default)]
+    active: bool,
+    #[serde(default)]
+    scope_type: String,
+    statistics: Option<Value>,
+}
+
+impl From<ApiWebhook> for ConfiguredWebhook {
+    fn from(webhook: ApiWebhook) -> Self {
+        let mut events = webhook.events;
+        events.sort();
+
+        ConfiguredWebhook {
+            id: webhook.id,
+            scope: webhook.scope_type,
+            webhook: Webhook {
+                name: webhook.name,
+                url: webhook.url,
+                events,
+                active: webhook.active,
+            },
+            statistics: webhook.statistics,
+        }
+    }
+}
+
+/// Webhook command handler
+pub async fn handle(args: &WebhookArgs, client: &Client) -> CommandResult {
+    let scope = match (&args.command, &args.project_key) {
+        (WebhookSubcommands::Serve(serve_args), _) => {
+            return serve::handle(serve_args, client).await
+        }
+        (_, Some(project_key)) => &ScopeArgs {
+            project_key: project_key.clone(),
+            repository_slug: args.repository_slug.clone(),
+        },
+        (_, None) => {
+            return Err(CommandError::ArgumentError(vec![
+                "--project-key is required".to_string(),
+            ]))
+        }
+    };
+
+    match &args.command {
+        WebhookSubcommands::List => list::handle(scope, client).await,
+        WebhookSubcommands::Get(get_args) => get::handle(scope, get_args, client).await,
+        WebhookSubcommands::Create(create_args) => create::handle(scope, create_args, client).await,
+        WebhookSubcommands::Update(update_args) => update::handle(scope, update_args, client).await,
+        WebhookSubcommands::Delete(delete_args) => delete::handle(scope, delete_args, client).await,
+        WebhookSubcommands::Test(test_args) => test::handle(scope, test_args, client).await,
+        WebhookSubcommands::Deliveries(deliveries_args) => {
+            deliveries::handle(scope, deliveries_args, client).await
+        }
+        WebhookSubcommands::Serve(_) => unreachable!("handled above"),
+    }
+}
+
+/// Create a webhook, signing its requests with `secret` if given
+pub(crate) async fn create(
+    scope: &ScopeArgs,
+    webhook: &Webhook,
+    secret: Option<&str>,
+    client: &Client,
+) -> Result<ConfiguredWebhook, ApiError> {
+    let created: ApiWebhook = rest::post(client, &uri(scope), &body(webhook, secret))
+        .await?
+        .ok_or(ApiError::ResponseError)?;
+
+    Ok(created.into())
+}
+
+/// Change a webhook. The API replaces the whole webhook, so it is fetched first and the fields
+/// that are not modelled, such as the configuration with the secret and
+/// `sslVerificationRequired`, are sent back as they are. The secret is kept unless a new one is
+/// given.
+pub(crate) async fn update(
+    scope: &ScopeArgs,
+    id: u64,
+    change: impl FnOnce(&mut Webhook),
+    secret: Option<&str>,
+    client: &Client,
+) -> Result<ConfiguredWebhook, ApiError> {
+    let uri = format!("{}/{}", uri(scope), id);
+    let mut current: Value = rest::get(client, &uri, &[])
+        .await?
+        .ok_or(ApiError::ResponseError)?;
+
+    let mut webhook = serde_json::from_value::<ApiWebhook>(current.clone())
+        .map(ConfiguredWebhook::from)
+        .map_err(|e| ApiError::DeserializationError(e.to_string()))?
+        .webhook;
+    change(&mut webhook);
+
+    if let (Some(fields), Value::Object(changed)) = (current.as_object_mut(), json!(webhook)) {
+        fields.extend(changed);
+    }
+    if let Some(secret) = secret {
+        match current
+            .get_mut("configuration")
+            .and_then(Value::as_object_mut)
+        {
+            Some(configuration) => {
+                configuration.insert("secret".to_string(), json!(secret));
+            }
+            None => current["configuration"] = json!({ "secret": secret }),
+        }
+    }
+
+    let updated: ApiWebhook = rest::put(client, &uri, &current)
+        .await?
+        .ok_or(ApiError::ResponseError)?;
+
+    Ok(updated.into())
+}
+
+/// Delete a webhook
+pub(crate) async fn delete(scope: &ScopeArgs, id: u64, client: &Client) -> Result<(), ApiError> {
+    rest::delete::<()>(client, &format!("{}/{}", uri(scope), id), None).await
+}
+
+fn body(webhook: &Webhook, secret: Option<&str>) -> Value {
+    let mut body = json!(webhook);
+    if let Some(secret) = secret {
+        body["configuration"] = json!({ "secret": secret });
+    }
+    body
+}
+
+/// Read a webhook secret from an environment variable, so that it stays out of the shell history
+pub(crate) fn secret_from_env(name: &str) -> Result<String, CommandError> {
+    std::env::var(name).map_err(|_| {
+        CommandError::ArgumentError(vec![format!("Environment variable {} is not set", name)])
+    })
+}
+
+fn uri(scope: &ScopeArgs) -> String {
+    format!("api/latest/{}/webhooks", scope.path())
+}
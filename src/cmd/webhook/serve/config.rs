@@ -0,0 +1,135 @@
+//! Which handlers run on which events, as read from a YAML file.
+
+use crate::bitbucket::matches_glob;
+use crate::cmd::webhook::serve::event::Env;
+use crate::cmd::CommandError;
+use serde::Deserialize;
+use std::path::Path;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct HandlersConfig {
+    pub handlers: Vec<Handler>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Handler {
+    /// Shown in the log. Defaults to the position of the handler in the file.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// The events to run on, e.g. `repo:refs_changed`, or `*` for every event
+    pub events: Vec<String>,
+    /// Only run for this repository, given as `PROJECT/repo`
+    #[serde(default)]
+    pub repository: Option<String>,
+    /// Only run for the branches matching this pattern, e.g. `main` or `release/*`. This is the
+    /// pushed branch, or the target branch of a pull request.
+    #[serde(default)]
+    pub branch: Option<String>,
+    #[serde(flatten)]
+    pub action: Action,
+}
+
+/// What a handler does. Every string may refer to the event's variables as `${BITBUCKET_...}`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Action {
+    /// Run a shell command with the event's variables in its environment
+    Run(String),
+    /// Comment on the pull request of the event
+    Comment(String),
+    /// Store a build status on the commit of the event
+    BuildStatus(BuildStatusAction),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct BuildStatusAction {
+    pub key: String,
+    /// `SUCCESSFUL`, `FAILED` or `INPROGRESS`
+    pub state: String,
+    pub url: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+impl HandlersConfig {
+    /// Read the handlers from a YAML file
+    pub fn load(path: &Path) -> Result<HandlersConfig, CommandError> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            CommandError::ArgumentError(vec![format!("Unable to read {}: {}", path.display(), e)])
+        })?;
+        let mut config: HandlersConfig = serde_yaml::from_str(&content).map_err(|e| {
+            CommandError::ArgumentError(vec![format!("Invalid handlers {}: {}", path.display(), e)])
+        })?;
+
+        let mut errors = Vec::new();
+        for (index, handler) in config.handlers.iter_mut().enumerate() {
+            let name = handler
+                .name
+                .get_or_insert_with(|| format!("handler {}", index + 1));
+            if handler.events.is_empty() {
+                errors.push(format!("{}: no events", name));
+            }
+        }
+
+        match errors.is_empty() {
+            true => Ok(config),
+            false => Err(CommandError::ArgumentError(errors)),
+        }
+    }
+}
+
+impl Handler {
+    pub fn name(&self) -> &str {
+        self.name.as_deref().unwrap_or("handler")
+    }
+
+    /// Whether the handler runs for an event with these variables
+    pub fn matches(&self, env: &Env) -> bool {
+        let var = |name| env.get(name).map(String::as_str).unwrap_or_default();
+
+        let event = var("BITBUCKET_EVENT");
+        if !self.events.iter().any(|e| e == "*" || e == event) {
+            return false;
+        }
+        if let Some(repository) = &self.repository {
+            let actual = format!(
+                "{}/{}",
+                var("BITBUCKET_PROJECT"),
+                var("BITBUCKET_REPOSITORY")
+            );
+            if !repository.eq_ignore_ascii_case(&actual) {
+                return false;
+            }
+        }
+        match &self.branch {
+            Some(pattern) => matches_glob(pattern, var("BITBUCKET_BRANCH")),
+            None => true,
+        }
+    }
+}
+
+/// Replace `${NAME}` with the value of the variable, or nothing if it is not set
+pub fn expand(template: &str, env: &Env) -> String {
+    let mut expanded = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("${") {
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        expanded.push_str(&rest[..start]);
+        let name = &rest[start + 2..start + end];
+        if let Some(value) = env.get(name) {
+            expanded.push_str(value);
+        }
+        rest = &rest[start + end + 1..];
+    }
+
+    expanded.push_str(rest);
+    expanded
+}
@@ -0,0 +1,159 @@
+//! The payloads Bitbucket posts to webhooks.
+//!
+//! Every event shares one shape: the fields an event does not have are left out of its payload.
+
+use crate::cmd::repo::Repository;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// The variables a handler runs with, e.g. `BITBUCKET_BRANCH`
+pub type Env = BTreeMap<&'static str, String>;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Event {
+    /// e.g. `repo:refs_changed` or `pr:opened`
+    pub event_key: String,
+    #[serde(default)]
+    pub date: Option<String>,
+    #[serde(default)]
+    pub actor: Option<User>,
+    /// Set by repository events. Pull request events carry it in their refs instead.
+    #[serde(default)]
+    pub repository: Option<Repository>,
+    /// The refs pushed by `repo:refs_changed`
+    #[serde(default)]
+    pub changes: Vec<RefChange>,
+    #[serde(default)]
+    pub pull_request: Option<PullRequest>,
+    #[serde(default)]
+    pub comment: Option<Comment>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct User {
+    pub name: String,
+    #[serde(default)]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub email_address: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RefChange {
+    /// e.g. `refs/heads/main`
+    pub ref_id: String,
+    pub from_hash: String,
+    pub to_hash: String,
+    /// `ADD`, `UPDATE` or `DELETE`
+    #[serde(rename = "type")]
+    pub change_type: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PullRequest {
+    pub id: u64,
+    pub title: String,
+    #[serde(default)]
+    pub state: Option<String>,
+    pub from_ref: PullRequestRef,
+    pub to_ref: PullRequestRef,
+    #[serde(default)]
+    pub author: Option<Participant>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PullRequestRef {
+    pub id: String,
+    pub display_id: String,
+    pub latest_commit: String,
+    pub repository: Repository,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Participant {
+    pub user: User,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Comment {
+    pub id: u64,
+    pub text: String,
+}
+
+impl Event {
+    /// The variables to run handlers with. A push yields one set per pushed ref, every other
+    /// event a single set.
+    pub fn envs(&self) -> Vec<Env> {
+        let mut env = Env::new();
+        env.insert("BITBUCKET_EVENT", self.event_key.clone());
+        if let Some(date) = &self.date {
+            env.insert("BITBUCKET_DATE", date.clone());
+        }
+        if let Some(actor) = &self.actor {
+            env.insert("BITBUCKET_ACTOR", actor.name.clone());
+        }
+
+        let repository = self
+            .repository
+            .as_ref()
+            .or_else(|| self.pull_request.as_ref().map(|pr| &pr.to_ref.repository));
+        if let Some(repository) = repository {
+            env.insert("BITBUCKET_PROJECT", repository.project.key.clone());
+            env.insert("BITBUCKET_REPOSITORY", repository.slug.clone());
+        }
+        if let Some(pull_request) = &self.pull_request {
+            env.insert("BITBUCKET_PR_ID", pull_request.id.to_string());
+            env.insert("BITBUCKET_PR_TITLE", pull_request.title.clone());
+            env.insert(
+                "BITBUCKET_PR_FROM_BRANCH",
+                pull_request.from_ref.display_id.clone(),
+            );
+            env.insert(
+                "BITBUCKET_PR_TO_BRANCH",
+                pull_request.to_ref.display_id.clone(),
+            );
+            if let Some(author) = &pull_request.author {
+                env.insert("BITBUCKET_PR_AUTHOR", author.user.name.clone());
+            }
+            // the branch a pull request goes into, and the commit it would merge
+            env.insert("BITBUCKET_BRANCH", pull_request.to_ref.display_id.clone());
+            env.insert(
+                "BITBUCKET_COMMIT",
+                pull_request.from_ref.latest_commit.clone(),
+            );
+        }
+        if let Some(comment) = &self.comment {
+            env.insert("BITBUCKET_COMMENT", comment.text.clone());
+        }
+
+        if self.changes.is_empty() {
+            return vec![env];
+        }
+
+        self.changes
+            .iter()
+            .map(|change| {
+                let mut env = env.clone();
+                let name = change
+                    .ref_id
+                    .strip_prefix("refs/heads/")
+                    .unwrap_or(&change.ref_id);
+                env.insert("BITBUCKET_REF", change.ref_id.clone());
+                env.insert("BITBUCKET_BRANCH", name.to_string());
+                env.insert("BITBUCKET_CHANGE_TYPE", change.change_type.clone());
+                env.insert("BITBUCKET_FROM_HASH", change.from_hash.clone());
+                env.insert("BITBUCKET_TO_HASH", change.to_hash.clone());
+                // a deleted ref has no commit to act on
+                if change.change_type != "DELETE" {
+                    env.insert("BITBUCKET_COMMIT", change.to_hash.clone());
+                }
+                env
+            })
+            .collect()
+    }
+}
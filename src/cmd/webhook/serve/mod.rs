@@ -0,0 +1,341 @@
+//! A small webhook receiver that runs handlers on the events Bitbucket posts to it.
+//!
+//! Requests are checked against the `X-Hub-Signature` header, an HMAC-SHA256 of the body keyed
+//! with the webhook's secret. They are answered as soon as the event is parsed, and the handlers
+//! run in the background, so slow commands do not make Bitbucket time out.
+
+pub mod config;
+pub mod event;
+
+use crate::cmd::webhook::secret_from_env;
+use crate::cmd::{CommandError, CommandResult};
+use crate::output;
+use crate::rest;
+use bitbucket_server_rs::api::build_status_post::BuildStatusPostPayload;
+use bitbucket_server_rs::client::Client;
+use clap_derive::Args;
+use config::{expand, Action, BuildStatusAction, Handler, HandlersConfig};
+use event::{Env, Event};
+use http_body_util::{BodyExt, Full, Limited};
+use hyper::body::{Bytes, Incoming};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Method, Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use log::{error, info, warn};
+use ring::hmac;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::net::{IpAddr, SocketAddr};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+
+/// The largest request body accepted, well above the payloads Bitbucket sends
+pub const MAX_BODY_SIZE: usize = 5 * 1024 * 1024;
+
+#[derive(Debug, Args)]
+pub struct WebhookServeArgs {
+    /// The port to listen on
+    #[arg(long, default_value_t = 8080)]
+    pub port: u16,
+    /// The address to listen on. Use `0.0.0.0` to receive requests from other machines.
+    #[arg(long, default_value = "127.0.0.1")]
+    pub bind: IpAddr,
+    /// The environment variable holding the webhook's secret. Requests that are not signed with
+    /// it are rejected.
+    #[arg(long, required_unless_present = "no_verify")]
+    pub secret_env: Option<String>,
+    /// Accept unsigned requests, e.g. to try handlers out with recorded payloads
+    #[arg(long, conflicts_with = "secret_env")]
+    pub no_verify: bool,
+    /// The YAML file that maps events to handlers
+    #[arg(long)]
+    pub config: PathBuf,
+}
+
+/// Why a request was turned down
+#[derive(Debug, PartialEq, Eq)]
+pub enum Rejection {
+    /// The signature is missing or does not match the body
+    Signature,
+    /// The body is not an event payload
+    Payload(String),
+}
+
+/// The result of running one handler on one event
+#[derive(Debug, Clone, Serialize)]
+pub struct HandlerResult {
+    pub handler: String,
+    pub success: bool,
+    pub message: String,
+}
+
+/// Verifies, parses and dispatches the events posted to the server
+pub struct Receiver {
+    config: HandlersConfig,
+    key: Option<hmac::Key>,
+    client: Client,
+}
+
+/// Receive webhook events and run the configured handlers until interrupted
+pub async fn handle(serve_args: &WebhookServeArgs, client: &Client) -> CommandResult {
+    let config = HandlersConfig::load(&serve_args.config)?;
+    let secret = match &serve_args.secret_env {
+        Some(name) => Some(secret_from_env(name)?),
+        None => {
+            warn!("Accepting unsigned requests");
+            None
+        }
+    };
+    let receiver = Receiver::new(config, secret.as_deref(), client.clone());
+
+    let address = SocketAddr::new(serve_args.bind, serve_args.port);
+    let listener = TcpListener::bind(address).await.map_err(|e| {
+        CommandError::UnexpectedError(format!("Unable to listen on {}: {}", address, e))
+    })?;
+    output::message(format!("Listening on {}", address));
+
+    serve(listener, Arc::new(receiver)).await
+}
+
+/// Answer requests on `listener` until an error occurs
+pub async fn serve(listener: TcpListener, receiver: Arc<Receiver>) -> CommandResult {
+    loop {
+        let (stream, peer) = listener
+            .accept()
+            .await
+            .map_err(|e| CommandError::UnexpectedError(e.to_string()))?;
+        let receiver = receiver.clone();
+
+        tokio::spawn(async move {
+            let service = service_fn(|request| respond(receiver.clone(), request));
+            if let Err(e) = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await
+            {
+                warn!("Error serving {}: {}", peer, e);
+            }
+        });
+    }
+}
+
+async fn respond(
+    receiver: Arc<Receiver>,
+    request: Request<Incoming>,
+) -> Result<Response<Full<Bytes>>, hyper::Error> {
+    if request.method() != Method::POST {
+        return Ok(reply(
+            StatusCode::METHOD_NOT_ALLOWED,
+            json!({ "error": "POST only" }),
+        ));
+    }
+
+    let header = |name| {
+        request
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    };
+    let event_key = header("X-Event-Key");
+    let signature = header("X-Hub-Signature");
+    let body = match Limited::new(request.into_body(), MAX_BODY_SIZE).collect().await {
+        Ok(body) => body.to_bytes(),
+        Err(e) => match e.downcast::<hyper::Error>() {
+            Ok(e) => return Err(*e),
+            Err(_) => {
+                warn!("Rejected a request larger than {} bytes", MAX_BODY_SIZE);
+                return Ok(reply(
+                    StatusCode::PAYLOAD_TOO_LARGE,
+                    json!({ "error": "body too large" }),
+                ));
+            }
+        },
+    };
+
+    let event = match receiver.receive(signature.as_deref(), &body) {
+        Ok(event) => event,
+        Err(Rejection::Signature) => {
+            warn!("Rejected a request with a missing or wrong signature");
+            return Ok(reply(
+                StatusCode::UNAUTHORIZED,
+                json!({ "error": "invalid signature" }),
+            ));
+        }
+        // sent by "Test connection" in the webhook settings
+        Err(Rejection::Payload(_)) if event_key.as_deref() == Some("diagnostics:ping") => {
+            return Ok(reply(
+                StatusCode::OK,
+                json!({ "event": "diagnostics:ping" }),
+            ));
+        }
+        Err(Rejection::Payload(e)) => {
+            return Ok(reply(StatusCode::BAD_REQUEST, json!({ "error": e })));
+        }
+    };
+
+    info!("Received {}", event.event_key);
+    let reply_body = json!({ "event": event.event_key });
+    tokio::spawn(async move {
+        for result in receiver.dispatch(&event).await {
+            match result.success {
+                true => info!("{}: {}", result.handler, result.message),
+                false => error!("{}: {}", result.handler, result.message),
+            }
+        }
+    });
+
+    Ok(reply(StatusCode::ACCEPTED, reply_body))
+}
+
+fn reply(status: StatusCode, body: Value) -> Response<Full<Bytes>> {
+    let mut response = Response::new(Full::new(Bytes::from(body.to_string())));
+    *response.status_mut() = status;
+    response
+}
+
+impl Receiver {
+    /// A receiver that only accepts requests signed with `secret`, or any request without one
+    pub fn new(config: HandlersConfig, secret: Option<&str>, client: Client) -> Receiver {
+        Receiver {
+            config,
+            key: secret.map(|secret| hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes())),
+            client,
+        }
+    }
+
+    /// Check the signature of a request and parse its body
+    pub fn receive(&self, signature: Option<&str>, body: &[u8]) -> Result<Event, Rejection> {
+        if let Some(key) = &self.key {
+            let signature = signature
+                .and_then(|signature| signature.strip_prefix("sha256="))
+                .and_then(decode_hex)
+                .ok_or(Rejection::Signature)?;
+            hmac::verify(key, body, &signature).map_err(|_| Rejection::Signature)?;
+        }
+
+        serde_json::from_slice(body).map_err(|e| Rejection::Payload(e.to_string()))
+    }
+
+    /// Run every handler that matches the event, one after the other
+    pub async fn dispatch(&self, event: &Event) -> Vec<HandlerResult> {
+        let mut results = Vec::new();
+
+        for env in event.envs() {
+            for handler in self.config.handlers.iter().filter(|h| h.matches(&env)) {
+                let outcome = self.run(handler, &env).await;
+                results.push(HandlerResult {
+                    handler: handler.name().to_string(),
+                    success: outcome.is_ok(),
+                    message: outcome.unwrap_or_else(|e| e),
+                });
+            }
+        }
+
+        results
+    }
+
+    async fn run(&self, handler: &Handler, env: &Env) -> Result<String, String> {
+        match &handler.action {
+            Action::Run(command) => run_command(command, env).await,
+            Action::Comment(text) => self.comment(&expand(text, env), env).await,
+            Action::BuildStatus(build_status) => self.build_status(build_status, env).await,
+        }
+    }
+
+    async fn comment(&self, text: &str, env: &Env) -> Result<String, String> {
+        let (Some(project), Some(repository), Some(id)) = (
+            env.get("BITBUCKET_PROJECT"),
+            env.get("BITBUCKET_REPOSITORY"),
+            env.get("BITBUCKET_PR_ID"),
+        ) else {
+            return Err("comment needs a pull request event".to_string());
+        };
+
+        let uri = format!(
+            "api/latest/projects/{}/repos/{}/pull-requests/{}/comments",
+            project, repository, id
+        );
+        let _: Option<Value> = rest::post(&self.client, &uri, &json!({ "text": text }))
+            .await
+            .map_err(|e| format!("{:?}", e))?;
+        Ok(format!("commented on pull request {}", id))
+    }
+
+    async fn build_status(
+        &self,
+        build_status: &BuildStatusAction,
+        env: &Env,
+    ) -> Result<String, String> {
+        let (Some(project), Some(repository), Some(commit)) = (
+            env.get("BITBUCKET_PROJECT"),
+            env.get("BITBUCKET_REPOSITORY"),
+            env.get("BITBUCKET_COMMIT"),
+        ) else {
+            return Err("buildStatus needs an event with a commit".to_string());
+        };
+        let state = expand(&build_status.state, env);
+
+        let payload = BuildStatusPostPayload {
+            key: expand(&build_status.key, env),
+            state: state.clone().into(),
+            url: expand(&build_status.url, env),
+            name: build_status.name.as_ref().map(|name| expand(name, env)),
+            description: build_status.description.as_ref().map(|d| expand(d, env)),
+            build_number: None,
+            date_added: None,
+            duration: None,
+            parent: None,
+            reference: env.get("BITBUCKET_REF").cloned(),
+            test_results: None,
+        };
+        let uri = format!(
+            "api/latest/projects/{}/repos/{}/commits/{}/builds",
+            project, repository, commit
+        );
+
+        rest::send(
+            &self.client,
+            rest::request(&self.client, reqwest::Method::POST, &uri).json(&payload),
+        )
+        .await
+        .map_err(|e| format!("{:?}", e))?;
+        Ok(format!("stored build status {} on {}", state, commit))
+    }
+}
+
+/// Run a shell command with the event's variables, and report its output
+async fn run_command(command: &str, env: &Env) -> Result<String, String> {
+    let output = tokio::process::Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .envs(env)
+        .output()
+        .await
+        .map_err(|e| format!("unable to run `{}`: {}", command, e))?;
+
+    let mut message = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !stderr.trim().is_empty() {
+        message = format!("{}\n{}", message, stderr.trim()).trim().to_string();
+    }
+
+    match output.status.success() {
+        true => Ok(message),
+        false => Err(format!(
+            "`{}` failed with {}: {}",
+            command, output.status, message
+        )),
+    }
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
@@ -0,0 +1,63 @@
+use crate::bitbucket::ScopeArgs;
+use crate::cmd::webhook::{get, uri};
+use crate::cmd::CommandResult;
+use crate::output;
+use crate::rest;
+use bitbucket_server_rs::client::{ApiError, Client};
+use clap_derive::Args;
+use serde_json::{json, Value};
+
+/// The columns of the table output
+const COLUMNS: &[&str] = &["request.url", "response.status", "response.body"];
+
+#[derive(Debug, Args)]
+pub struct WebhookTestArgs {
+    /// The id of the webhook to test, as shown by `list`
+    #[arg(required_unless_present = "url")]
+    pub id: Option<u64>,
+    /// The URL to send the test request to. Defaults to the webhook's URL.
+    #[arg(long)]
+    pub url: Option<String>,
+    /// Do not verify the TLS certificate of the URL
+    #[arg(long)]
+    pub insecure: bool,
+}
+
+/// Send a test request, and print the request and the response the URL sent back
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-repository/#api-api-latest-projects-projectkey-repos-repositoryslug-webhooks-test-post)
+pub async fn handle(
+    scope: &ScopeArgs,
+    test_args: &WebhookTestArgs,
+    client: &Client,
+) -> CommandResult {
+    let url = match (&test_args.url, test_args.id) {
+        (Some(url), _) => url.clone(),
+        (None, Some(id)) => get::fetch(scope, id, false, client).await?.webhook.url,
+        (None, None) => unreachable!("clap requires an id or a URL"),
+    };
+
+    let mut params = vec![
+        ("url", url),
+        ("sslVerificationRequired", (!test_args.insecure).to_string()),
+    ];
+    if let Some(id) = test_args.id {
+        // the test request is signed with this webhook's secret
+        params.push(("webhookId", id.to_string()));
+    }
+
+    let request = rest::request(
+        client,
+        reqwest::Method::POST,
+        &format!("{}/test", uri(scope)),
+    )
+    .query(&params)
+    .json(&json!({}));
+    let response = rest::send(client, request).await?;
+    let result: Value = rest::read_json(response)
+        .await?
+        .ok_or(ApiError::ResponseError)?;
+
+    output::print(&result, COLUMNS)?;
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use crate::bitbucket::ScopeArgs;
+use crate::cmd::webhook::{self, secret_from_env};
+use crate::cmd::CommandResult;
+use crate::output;
+use bitbucket_server_rs::client::Client;
+use clap_derive::Args;
+
+#[derive(Debug, Args)]
+pub struct WebhookUpdateArgs {
+    /// The id of the webhook, as shown by `list`
+    pub id: u64,
+    /// The new name
+    #[arg(long)]
+    pub name: Option<String>,
+    /// The new URL
+    #[arg(long)]
+    pub url: Option<String>,
+    /// Replace the events with these. May be repeated.
+    #[arg(long, conflicts_with_all = ["add_event", "remove_event"])]
+    pub event: Vec<String>,
+    /// Subscribe to another event. May be repeated.
+    #[arg(long)]
+    pub add_event: Vec<String>,
+    /// Unsubscribe from an event. May be repeated.
+    #[arg(long)]
+    pub remove_event: Vec<String>,
+    /// The environment variable holding a new secret to sign requests with
+    #[arg(long)]
+    pub secret_env: Option<String>,
+    /// Activate (`true`) or deactivate (`false`) the webhook
+    #[arg(long)]
+    pub active: Option<bool>,
+}
+
+/// Change the given fields of a webhook
+///
+/// [Bitbucket Docs](https://developer.atlassian.com/server/bitbucket/rest/v811/api-group-repository/#api-api-latest-projects-projectkey-repos-repositoryslug-webhooks-webhookid-put)
+pub async fn handle(
+    scope: &ScopeArgs,
+    update_args: &WebhookUpdateArgs,
+    client: &Client,
+) -> CommandResult {
+    let secret = match &update_args.secret_env {
+        Some(name) => Some(secret_from_env(name)?),
+        None => None,
+    };
+    let change = |spec: &mut webhook::Webhook| {
+        if let Some(name) = &update_args.name {
+            spec.name = name.clone();
+        }
+        if let Some(url) = &update_args.url {
+            spec.url = url.clone();
+        }
+        if !update_args.event.is_empty() {
+            spec.events = update_args.event.clone();
+        }
+        spec.events.extend(update_args.add_event.iter().cloned());
+        spec.events
+            .retain(|event| !update_args.remove_event.contains(event));
+        spec.events.sort();
+        spec.events.dedup();
+        if let Some(active) = update_args.active {
+            spec.active = active;
+        }
+    };
+
+    let updated = webhook::update(scope, update_args.id, change, secret.as_deref(), client).await?;
+
+    output::print(&updated, super::COLUMNS)?;
+    Ok(())
+}
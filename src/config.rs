@@ -1,10 +1,290 @@
+use crate::cmd::{CommandError, ServerArgs};
+use crate::credentials::{self, SecretStore, StoreKind};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use bitbucket_server_rs::client::{self, Client};
+use clap::ValueEnum;
+use log::warn;
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
+use serde::Deserialize;
+use std::env;
+use std::fmt;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// How requests are authenticated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum AuthScheme {
+    /// Send the API token as a bearer token
+    Bearer,
+    /// Send the username and the password, or an HTTP access token, with HTTP Basic auth
+    Basic,
+}
+
+/// The credentials to authenticate with. They are left out of the `Debug` output, so they do
+/// not end up in logs.
+#[derive(Clone, PartialEq, Eq)]
+pub enum Auth {
+    Bearer(String),
+    Basic { username: String, password: String },
+}
+
+impl fmt::Debug for Auth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Auth::Bearer(_) => write!(f, "Bearer(***)"),
+            Auth::Basic { username, .. } => write!(f, "Basic({}:***)", username),
+        }
+    }
+}
+
+impl Auth {
+    /// The credentials of the scheme chosen with `--auth`, with `secret` as the token or password
+    pub fn from_args(args: &ServerArgs, secret: String) -> Result<Self, CommandError> {
+        Ok(match args.auth {
+            AuthScheme::Bearer => Auth::Bearer(secret),
+            AuthScheme::Basic => {
+                let username = env::var("BITBUCKET_USERNAME")
+                    .ok()
+                    .or(args.username.clone())
+                    .filter(|username| !username.is_empty())
+                    .ok_or_else(|| {
+                        CommandError::ArgumentError(vec![
+                            "--auth basic needs --username or BITBUCKET_USERNAME".to_string(),
+                        ])
+                    })?;
+                Auth::Basic {
+                    username,
+                    password: secret,
+                }
+            }
+        })
+    }
+
+    /// The `Authorization` header, marked as sensitive so that it is never printed
+    pub fn header(&self) -> Result<HeaderValue, CommandError> {
+        let value = match self {
+            Auth::Bearer(token) => format!("Bearer {}", token),
+            Auth::Basic { username, password } => {
+                format!(
+                    "Basic {}",
+                    STANDARD.encode(format!("{}:{}", username, password))
+                )
+            }
+        };
+
+        let mut header = HeaderValue::from_str(&value).map_err(|_| {
+            CommandError::ArgumentError(vec![
+                "The credentials contain characters that cannot be sent in a header".to_string(),
+            ])
+        })?;
+        header.set_sensitive(true);
+        Ok(header)
+    }
+}
+
+#[derive(Debug)]
 pub struct Config {
     pub server: String,
-    pub api_token: String,
+    pub auth: Auth,
 }
 
 impl Config {
-    pub fn new(server: String, api_token: String) -> Self {
-        Config { server, api_token }
+    pub fn new(server: String, auth: Auth) -> Self {
+        Config { server, auth }
     }
+
+    /// Resolve the server and credentials from the arguments, the environment and the
+    /// configuration file
+    pub fn from_args(args: &ServerArgs) -> Result<Self, CommandError> {
+        Config::resolve(args, &Settings::load()?)
+    }
+
+    /// Resolve the server and credentials. The BITBUCKET_SERVER, BITBUCKET_USERNAME and
+    /// BITBUCKET_API_TOKEN environment variables take precedence over `--server`, `--username`
+    /// and `--api-token`, and a token read from `--api-token-file` or `--api-token-stdin` over
+    /// both. Without any of these, the token is taken from the `token_command` of the settings,
+    /// or else from the secret store `auth login` saved it in.
+    pub fn resolve(args: &ServerArgs, settings: &Settings) -> Result<Self, CommandError> {
+        let server = env::var("BITBUCKET_SERVER")
+            .ok()
+            .or(args.server.clone())
+            .unwrap_or_default();
+
+        let secret = match secret(args)? {
+            Some(secret) => secret,
+            None => match &settings.token_command {
+                Some(command) => run_token_command(command, &server)?,
+                None => stored_secret(settings, &server).unwrap_or_default(),
+            },
+        };
+
+        Ok(Config::new(server, Auth::from_args(args, secret)?))
+    }
+
+    /// Build the client. Bearer tokens are added to each request by [`crate::rest::send`]; other
+    /// credentials are set as a default header of the HTTP client.
+    pub fn client(&self) -> Result<Client, CommandError> {
+        match &self.auth {
+            Auth::Bearer(token) => Ok(client::new(&self.server, token)),
+            Auth::Basic { .. } => {
+                let mut headers = HeaderMap::new();
+                headers.insert(AUTHORIZATION, self.auth.header()?);
+                let http_client = reqwest::Client::builder()
+                    .default_headers(headers)
+                    .build()
+                    .map_err(|e| CommandError::UnexpectedError(e.to_string()))?;
+
+                let mut client = client::new(&self.server, "");
+                client.with_http_client(http_client);
+                Ok(client)
+            }
+        }
+    }
+}
+
+/// The settings of the configuration file, `config.yaml` in the [`config_dir`], or the file the
+/// BITBUCKET_CLI_CONFIG environment variable points at
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Settings {
+    /// A shell command that prints the token, for when none is given otherwise. It gets the
+    /// server in the BITBUCKET_SERVER environment variable.
+    #[serde(default)]
+    pub token_command: Option<String>,
+    /// Where `auth login` keeps tokens: `secret-service` or `file`
+    #[serde(default)]
+    pub secret_store: Option<StoreKind>,
+}
+
+impl Settings {
+    /// Read the configuration file. Without one, every setting has its default.
+    pub fn load() -> Result<Self, CommandError> {
+        let path = match env::var_os("BITBUCKET_CLI_CONFIG") {
+            Some(path) => PathBuf::from(path),
+            None => match config_dir() {
+                Some(dir) => dir.join("config.yaml"),
+                None => return Ok(Settings::default()),
+            },
+        };
+
+        match path.exists() {
+            true => Settings::load_from(&path),
+            false => Ok(Settings::default()),
+        }
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self, CommandError> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            CommandError::ArgumentError(vec![format!("Unable to read {}: {}", path.display(), e)])
+        })?;
+        serde_yaml::from_str(&content).map_err(|e| {
+            CommandError::ArgumentError(vec![format!("Invalid {}: {}", path.display(), e)])
+        })
+    }
+
+    /// The secret store `auth login` keeps tokens in
+    pub fn store(&self) -> Result<Box<dyn SecretStore>, CommandError> {
+        credentials::open(self.secret_store)
+    }
+}
+
+/// The directory of the configuration file and the encrypted tokens:
+/// `$XDG_CONFIG_HOME/bitbucket-server-cli`, or `~/.config/bitbucket-server-cli`
+pub fn config_dir() -> Option<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .map(|dir| dir.join("bitbucket-server-cli"))
+}
+
+/// The token or password given with `--api-token-file`, `--api-token-stdin`, BITBUCKET_API_TOKEN
+/// or `--api-token`, in that order
+pub fn secret(args: &ServerArgs) -> Result<Option<String>, CommandError> {
+    Ok(match (&args.api_token_file, args.api_token_stdin) {
+        (Some(file), _) => Some(read_secret_file(file)?),
+        (None, true) => Some(read_secret_stdin()?),
+        (None, false) => env::var("BITBUCKET_API_TOKEN")
+            .ok()
+            .or(args.api_token.clone()),
+    })
+}
+
+/// Run the `token_command` and take the first line it prints. Only its exit status and errors
+/// are reported, so that a token is never shown.
+fn run_token_command(command: &str, server: &str) -> Result<String, CommandError> {
+    let output = std::process::Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .env("BITBUCKET_SERVER", server)
+        .stdin(std::process::Stdio::null())
+        .output()
+        .map_err(|e| {
+            CommandError::UnexpectedError(format!("Unable to run token_command: {}", e))
+        })?;
+
+    match output.status.success() {
+        true => Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_string()),
+        false => Err(CommandError::UnexpectedError(format!(
+            "token_command failed with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))),
+    }
+}
+
+/// The token `auth login` saved for a server. A store that cannot be read is only logged, so
+/// that commands which need no token still run.
+fn stored_secret(settings: &Settings, server: &str) -> Option<String> {
+    if server.is_empty() {
+        return None;
+    }
+    match settings
+        .store()
+        .and_then(|store| store.get(server_key(server)))
+    {
+        Ok(secret) => secret,
+        Err(e) => {
+            warn!("Unable to read the stored token: {}", e);
+            None
+        }
+    }
+}
+
+/// The key tokens are stored by, so that a trailing `/` does not matter
+pub fn server_key(server: &str) -> &str {
+    server.trim_end_matches('/')
+}
+
+/// Read a token or password from a file, without its trailing line break
+fn read_secret_file(file: &Path) -> Result<String, CommandError> {
+    let content = std::fs::read_to_string(file).map_err(|e| {
+        CommandError::ArgumentError(vec![format!("Unable to read {}: {}", file.display(), e)])
+    })?;
+    Ok(content.trim_end_matches(['\r', '\n']).to_string())
+}
+
+/// Read a token or password from the first line of stdin. It is read once and remembered, as
+/// both the client and `auth login` ask for it.
+fn read_secret_stdin() -> Result<String, CommandError> {
+    static SECRET: OnceLock<String> = OnceLock::new();
+    if let Some(secret) = SECRET.get() {
+        return Ok(secret.clone());
+    }
+
+    let mut line = String::new();
+    std::io::stdin()
+        .lock()
+        .read_line(&mut line)
+        .map_err(|e| CommandError::ArgumentError(vec![format!("Unable to read stdin: {}", e)]))?;
+    Ok(SECRET
+        .get_or_init(|| line.trim_end_matches(['\r', '\n']).to_string())
+        .clone())
 }
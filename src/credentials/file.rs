@@ -0,0 +1,199 @@
+//! Tokens in a file encrypted with AES-256-GCM, for machines without a Secret Service.
+//!
+//! The key is derived with PBKDF2 from the BITBUCKET_CREDENTIALS_PASSPHRASE environment variable
+//! when it is set. Otherwise it is derived from a random key file next to the tokens that only
+//! you can read, which keeps the tokens out of backups and dotfile repositories that leave the
+//! key out, but not away from someone who can read both files.
+
+use crate::cmd::CommandError;
+use crate::config;
+use crate::credentials::SecretStore;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::num::NonZeroU32;
+use std::path::{Path, PathBuf};
+
+/// The start of the file, which also authenticates as additional data
+const MAGIC: &[u8] = b"BBSCLI1";
+const SALT_LEN: usize = 16;
+const PBKDF2_ITERATIONS: u32 = 100_000;
+
+/// What the encryption key is derived from
+enum KeySource {
+    Passphrase(Vec<u8>),
+    /// Created on the first write
+    KeyFile(PathBuf),
+}
+
+pub struct FileStore {
+    path: PathBuf,
+    key_source: KeySource,
+}
+
+impl FileStore {
+    /// A store in `path`, encrypted with a key derived from `passphrase`
+    pub fn new(path: PathBuf, passphrase: &str) -> Self {
+        FileStore {
+            path,
+            key_source: KeySource::Passphrase(passphrase.as_bytes().to_vec()),
+        }
+    }
+
+    /// The `credentials` file in the configuration directory
+    pub fn open_default() -> Result<Self, CommandError> {
+        let dir = config::config_dir().ok_or_else(|| {
+            CommandError::UnexpectedError(
+                "Unable to find the configuration directory, set HOME or XDG_CONFIG_HOME"
+                    .to_string(),
+            )
+        })?;
+        let path = dir.join("credentials");
+
+        Ok(match std::env::var("BITBUCKET_CREDENTIALS_PASSPHRASE") {
+            Ok(passphrase) if !passphrase.is_empty() => FileStore::new(path, &passphrase),
+            _ => FileStore {
+                path,
+                key_source: KeySource::KeyFile(dir.join("credentials.key")),
+            },
+        })
+    }
+
+    fn read(&self) -> Result<BTreeMap<String, String>, CommandError> {
+        let content = match fs::read(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+            Err(e) => return Err(io_error(&self.path, e)),
+        };
+
+        let invalid = || {
+            CommandError::UnexpectedError(format!(
+                "Unable to decrypt {}, the passphrase or key file may have changed",
+                self.path.display()
+            ))
+        };
+        if content.len() < MAGIC.len() + SALT_LEN + NONCE_LEN || !content.starts_with(MAGIC) {
+            return Err(invalid());
+        }
+        let (salt, rest) = content[MAGIC.len()..].split_at(SALT_LEN);
+        let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+
+        let key = self.key(salt, false)?.ok_or_else(invalid)?;
+        let nonce = Nonce::try_assume_unique_for_key(nonce).map_err(|_| invalid())?;
+        let mut buffer = ciphertext.to_vec();
+        let plaintext = key
+            .open_in_place(nonce, Aad::from(MAGIC), &mut buffer)
+            .map_err(|_| invalid())?;
+
+        serde_json::from_slice(plaintext).map_err(|_| invalid())
+    }
+
+    fn write(&self, secrets: &BTreeMap<String, String>) -> Result<(), CommandError> {
+        let random = SystemRandom::new();
+        let mut salt = [0u8; SALT_LEN];
+        let mut nonce = [0u8; NONCE_LEN];
+        random.fill(&mut salt).map_err(|_| random_error())?;
+        random.fill(&mut nonce).map_err(|_| random_error())?;
+
+        let key = self.key(&salt, true)?.ok_or_else(random_error)?;
+        let mut buffer = serde_json::to_vec(secrets)
+            .map_err(|e| CommandError::UnexpectedError(e.to_string()))?;
+        key.seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::from(MAGIC),
+            &mut buffer,
+        )
+        .map_err(|_| CommandError::UnexpectedError("Unable to encrypt the tokens".to_string()))?;
+
+        let mut content = MAGIC.to_vec();
+        content.extend_from_slice(&salt);
+        content.extend_from_slice(&nonce);
+        content.extend_from_slice(&buffer);
+        write_private(&self.path, &content)
+    }
+
+    /// The encryption key for `salt`. Without a key file, there is none, unless `create` makes one.
+    fn key(&self, salt: &[u8], create: bool) -> Result<Option<LessSafeKey>, CommandError> {
+        let secret = match &self.key_source {
+            KeySource::Passphrase(passphrase) => passphrase.clone(),
+            KeySource::KeyFile(path) => match fs::read(path) {
+                Ok(secret) => secret,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound && create => {
+                    let mut secret = vec![0u8; 32];
+                    SystemRandom::new()
+                        .fill(&mut secret)
+                        .map_err(|_| random_error())?;
+                    write_private(path, &secret)?;
+                    secret
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+                Err(e) => return Err(io_error(path, e)),
+            },
+        };
+
+        let mut key = [0u8; 32];
+        pbkdf2::derive(
+            pbkdf2::PBKDF2_HMAC_SHA256,
+            NonZeroU32::new(PBKDF2_ITERATIONS).unwrap(),
+            salt,
+            &secret,
+            &mut key,
+        );
+        let key = UnboundKey::new(&AES_256_GCM, &key)
+            .map_err(|_| CommandError::UnexpectedError("Invalid key".to_string()))?;
+        Ok(Some(LessSafeKey::new(key)))
+    }
+}
+
+impl SecretStore for FileStore {
+    fn name(&self) -> &'static str {
+        "file"
+    }
+
+    fn get(&self, server: &str) -> Result<Option<String>, CommandError> {
+        Ok(self.read()?.remove(server))
+    }
+
+    fn set(&self, server: &str, secret: &str) -> Result<(), CommandError> {
+        let mut secrets = self.read()?;
+        secrets.insert(server.to_string(), secret.to_string());
+        self.write(&secrets)
+    }
+
+    fn delete(&self, server: &str) -> Result<bool, CommandError> {
+        let mut secrets = self.read()?;
+        if secrets.remove(server).is_none() {
+            return Ok(false);
+        }
+        self.write(&secrets)?;
+        Ok(true)
+    }
+}
+
+/// Replace a file with content only its owner can read
+fn write_private(path: &Path, content: &[u8]) -> Result<(), CommandError> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| io_error(dir, e))?;
+    }
+
+    let temp = path.with_extension("tmp");
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+    let mut file = options.open(&temp).map_err(|e| io_error(&temp, e))?;
+    file.write_all(content).map_err(|e| io_error(&temp, e))?;
+    fs::rename(&temp, path).map_err(|e| io_error(path, e))
+}
+
+fn io_error(path: &Path, error: std::io::Error) -> CommandError {
+    CommandError::UnexpectedError(format!("Unable to access {}: {}", path.display(), error))
+}
+
+fn random_error() -> CommandError {
+    CommandError::UnexpectedError("Unable to generate a random key".to_string())
+}
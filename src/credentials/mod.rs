@@ -0,0 +1,47 @@
+//! Where `auth login` keeps tokens, one per server.
+//!
+//! The Secret Service of the desktop session is used through `secret-tool` when it is installed.
+//! Elsewhere, e.g. on servers and in containers, tokens go to a file encrypted with AES-256-GCM.
+
+pub mod file;
+pub mod secret_service;
+
+use crate::cmd::CommandError;
+use serde::Deserialize;
+
+pub use file::FileStore;
+pub use secret_service::SecretService;
+
+/// A place to keep the token of each server, by the server's `/rest` URL
+pub trait SecretStore {
+    /// The name of the store, as `auth status` shows it
+    fn name(&self) -> &'static str;
+
+    fn get(&self, server: &str) -> Result<Option<String>, CommandError>;
+
+    fn set(&self, server: &str, secret: &str) -> Result<(), CommandError>;
+
+    /// Remove the token of a server, returning whether there was one
+    fn delete(&self, server: &str) -> Result<bool, CommandError>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StoreKind {
+    SecretService,
+    File,
+}
+
+/// Open a store. Without a choice, the Secret Service is used when `secret-tool` is installed,
+/// and the encrypted file otherwise.
+pub fn open(kind: Option<StoreKind>) -> Result<Box<dyn SecretStore>, CommandError> {
+    let kind = kind.unwrap_or(match SecretService::available() {
+        true => StoreKind::SecretService,
+        false => StoreKind::File,
+    });
+
+    Ok(match kind {
+        StoreKind::SecretService => Box::new(SecretService),
+        StoreKind::File => Box::new(FileStore::open_default()?),
+    })
+}
@@ -0,0 +1,98 @@
+//! The Secret Service of the desktop session, e.g. GNOME Keyring or KWallet, through the
+//! `secret-tool` command of libsecret.
+
+use crate::cmd::CommandError;
+use crate::credentials::SecretStore;
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+/// The `service` attribute of the stored tokens
+const SERVICE: &str = "bitbucket-server-cli";
+
+pub struct SecretService;
+
+impl SecretService {
+    /// Whether `secret-tool` is on the `PATH`
+    pub fn available() -> bool {
+        std::env::var_os("PATH")
+            .map(|path| std::env::split_paths(&path).any(|dir| dir.join("secret-tool").is_file()))
+            .unwrap_or(false)
+    }
+}
+
+impl SecretStore for SecretService {
+    fn name(&self) -> &'static str {
+        "secret-service"
+    }
+
+    fn get(&self, server: &str) -> Result<Option<String>, CommandError> {
+        let output = secret_tool(&["lookup", "service", SERVICE, "server", server], None)?;
+
+        // `lookup` fails without a message when there is no such secret
+        match (output.status.success(), output.stderr.is_empty()) {
+            (true, _) => Ok(Some(
+                String::from_utf8_lossy(&output.stdout)
+                    .trim_end_matches(['\r', '\n'])
+                    .to_string(),
+            )),
+            (false, true) => Ok(None),
+            (false, false) => Err(failure("lookup", &output)),
+        }
+    }
+
+    fn set(&self, server: &str, secret: &str) -> Result<(), CommandError> {
+        let label = format!("--label=Bitbucket Server CLI token for {}", server);
+        let output = secret_tool(
+            &["store", &label, "service", SERVICE, "server", server],
+            Some(secret),
+        )?;
+
+        match output.status.success() {
+            true => Ok(()),
+            false => Err(failure("store", &output)),
+        }
+    }
+
+    fn delete(&self, server: &str) -> Result<bool, CommandError> {
+        if self.get(server)?.is_none() {
+            return Ok(false);
+        }
+
+        let output = secret_tool(&["clear", "service", SERVICE, "server", server], None)?;
+        match output.status.success() {
+            true => Ok(true),
+            false => Err(failure("clear", &output)),
+        }
+    }
+}
+
+/// Run `secret-tool`, writing the secret to its stdin rather than passing it as an argument
+fn secret_tool(args: &[&str], secret: Option<&str>) -> Result<Output, CommandError> {
+    let mut child = Command::new("secret-tool")
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| CommandError::UnexpectedError(format!("Unable to run secret-tool: {}", e)))?;
+
+    if let (Some(secret), Some(mut stdin)) = (secret, child.stdin.take()) {
+        stdin
+            .write_all(secret.as_bytes())
+            .map_err(|e| CommandError::UnexpectedError(e.to_string()))?;
+    }
+
+    child
+        .wait_with_output()
+        .map_err(|e| CommandError::UnexpectedError(e.to_string()))
+}
+
+fn failure(action: &str, output: &Output) -> CommandError {
+    CommandError::UnexpectedError(format!(
+        "secret-tool {} failed with {}: {}. Set `secret_store: file` in the configuration file \
+         when there is no Secret Service.",
+        action,
+        output.status,
+        String::from_utf8_lossy(&output.stderr).trim()
+    ))
+}
@@ -1,3 +1,6 @@
 pub mod bitbucket;
 pub mod config;
-pub mod cmd;
\ No newline at end of file
+pub mod credentials;
+pub mod cmd;
+pub mod output;
+pub mod rest;
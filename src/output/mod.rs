@@ -0,0 +1,288 @@
+//! Rendering of command results, in the format chosen with the global `--output` option.
+//!
+//! Commands hand their results to [`print`] along with the columns a table of them shows, and
+//! status lines to [`message`], instead of printing them themselves. A `--query` selects part of
+//! a result first, and a `--template` renders it instead of the output format.
+
+pub mod query;
+
+use crate::cmd::CommandError;
+use clap::ValueEnum;
+use query::Query;
+use serde::Serialize;
+use serde_json::Value;
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// JSON on a single line
+    Json,
+    /// Indented JSON
+    JsonPretty,
+    Yaml,
+    /// Aligned columns, for reading
+    Table,
+    /// Comma separated values with a header, for spreadsheets
+    Csv,
+    /// One JSON object per line for each item of a list
+    Ndjson,
+}
+
+/// How to print results
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// The format, or none for the command's default
+    pub format: Option<OutputFormat>,
+    /// A JMESPath expression selecting what to print
+    pub query: Option<String>,
+    /// A minijinja template to render results with, or `@` and the path of one
+    pub template: Option<String>,
+}
+
+struct Settings {
+    format: Option<OutputFormat>,
+    query: Option<Query>,
+    template: Option<String>,
+}
+
+static SETTINGS: OnceLock<Settings> = OnceLock::new();
+
+/// Choose how everything is printed from now on. Commands pick their own default format when
+/// none is given.
+pub fn init(options: &Options) -> Result<(), CommandError> {
+    let query = match &options.query {
+        Some(query) => Some(Query::parse(query).map_err(|e| {
+            CommandError::ArgumentError(vec![format!("Invalid query {}: {}", query, e)])
+        })?),
+        None => None,
+    };
+    let template = match options
+        .template
+        .as_deref()
+        .map(|t| (t, t.strip_prefix('@')))
+    {
+        Some((_, Some(path))) => Some(std::fs::read_to_string(path).map_err(|e| {
+            CommandError::ArgumentError(vec![format!("Unable to read {}: {}", path, e)])
+        })?),
+        Some((template, None)) => Some(template.to_string()),
+        None => None,
+    };
+    if let Some(template) = &template {
+        // report syntax errors before anything is done
+        minijinja::Environment::new()
+            .template_from_str(template)
+            .map_err(|e| CommandError::ArgumentError(vec![format!("Invalid template: {}", e)]))?;
+    }
+
+    // the first choice wins, as tests may run several commands in one process
+    let _ = SETTINGS.set(Settings {
+        format: options.format,
+        query,
+        template,
+    });
+    Ok(())
+}
+
+/// Print a result in the chosen format, or as JSON when none was chosen. A table or CSV shows
+/// the given columns, which are paths like `path.toString` into each item.
+pub fn print<T: Serialize>(value: &T, columns: &[&str]) -> Result<(), CommandError> {
+    print_or(value, columns, OutputFormat::Json)
+}
+
+/// Print a result in the chosen format, or in `default` when none was chosen
+pub fn print_or<T: Serialize>(
+    value: &T,
+    columns: &[&str],
+    default: OutputFormat,
+) -> Result<(), CommandError> {
+    let settings = SETTINGS.get();
+    let mut value =
+        serde_json::to_value(value).map_err(|e| CommandError::UnexpectedError(e.to_string()))?;
+    if let Some(query) = settings.and_then(|s| s.query.as_ref()) {
+        value = query
+            .search(&value)
+            .map_err(|e| CommandError::ArgumentError(vec![format!("Query failed: {}", e)]))?;
+    }
+
+    let rendered = match settings.and_then(|s| s.template.as_deref()) {
+        Some(template) => render_template(&value, template),
+        None => {
+            let format = settings.and_then(|s| s.format).unwrap_or(default);
+            // the columns are fields of the command's result, not of what a query selects
+            let columns = match settings.is_some_and(|s| s.query.is_some()) {
+                true => &[],
+                false => columns,
+            };
+            render(&value, format, columns)
+        }
+    }
+    .map_err(CommandError::UnexpectedError)?;
+    if !rendered.is_empty() {
+        println!("{}", rendered);
+    }
+    Ok(())
+}
+
+/// Print a status line, such as the outcome of a delete, which has no data to format
+pub fn message(text: impl std::fmt::Display) {
+    println!("{}", text);
+}
+
+/// Render a value, without a trailing newline
+pub fn render(value: &Value, format: OutputFormat, columns: &[&str]) -> Result<String, String> {
+    match format {
+        OutputFormat::Json => Ok(value.to_string()),
+        OutputFormat::JsonPretty => serde_json::to_string_pretty(value).map_err(|e| e.to_string()),
+        OutputFormat::Yaml => serde_yaml::to_string(value)
+            .map(|yaml| yaml.trim_end().to_string())
+            .map_err(|e| e.to_string()),
+        OutputFormat::Ndjson => Ok(rows(value)
+            .iter()
+            .map(|row| row.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")),
+        OutputFormat::Table => Ok(table(value, columns)),
+        OutputFormat::Csv => Ok(csv(value, columns)),
+    }
+}
+
+/// Render a value with a minijinja template. The value is `data`, and the fields of an object
+/// are variables too.
+pub fn render_template(value: &Value, template: &str) -> Result<String, String> {
+    let mut context = match value {
+        Value::Object(object) => object.clone(),
+        _ => serde_json::Map::new(),
+    };
+    context.insert("data".to_string(), value.clone());
+
+    let environment = minijinja::Environment::new();
+    let rendered = environment
+        .render_str(template, context)
+        .map_err(|e| e.to_string())?;
+    Ok(rendered.trim_end_matches('\n').to_string())
+}
+
+/// The items of a list. Paged responses list their items in `values`, and anything else is a
+/// single item.
+fn rows(value: &Value) -> Vec<&Value> {
+    match value {
+        Value::Array(items) => items.iter().collect(),
+        Value::Object(object) => match object.get("values") {
+            Some(Value::Array(items)) => items.iter().collect(),
+            _ => vec![value],
+        },
+        Value::Null => Vec::new(),
+        _ => vec![value],
+    }
+}
+
+/// The given columns, or the fields of the items when there are none
+fn columns(rows: &[&Value], columns: &[&str]) -> Vec<String> {
+    if !columns.is_empty() {
+        return columns.iter().map(|column| column.to_string()).collect();
+    }
+
+    let mut fields: Vec<String> = Vec::new();
+    for row in rows {
+        match row {
+            Value::Object(object) => {
+                for key in object.keys() {
+                    if !fields.contains(key) {
+                        fields.push(key.clone());
+                    }
+                }
+            }
+            _ => return vec![String::new()],
+        }
+    }
+    fields
+}
+
+/// The text of a cell: a field of `row` at a dotted path, or the row itself for an empty path
+fn cell(row: &Value, path: &str) -> String {
+    let value =
+        path.split('.')
+            .filter(|key| !key.is_empty())
+            .try_fold(row, |value, key| match value {
+                Value::Array(items) => items.get(key.parse::<usize>().ok()?),
+                _ => value.get(key),
+            });
+
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(text)) => text.clone(),
+        Some(Value::Array(items)) if items.iter().all(|item| !item.is_object()) => items
+            .iter()
+            .map(|item| cell(item, ""))
+            .collect::<Vec<_>>()
+            .join(","),
+        Some(value) => value.to_string(),
+    }
+}
+
+fn table(value: &Value, columns: &[&str]) -> String {
+    let rows = rows(value);
+    let columns = self::columns(&rows, columns);
+    let header: Vec<String> = columns.iter().map(|column| column.to_uppercase()).collect();
+    let lines: Vec<Vec<String>> = std::iter::once(header)
+        .chain(rows.iter().map(|row| {
+            columns
+                .iter()
+                .map(|column| cell(row, column).replace(['\n', '\r'], " "))
+                .collect()
+        }))
+        .collect();
+
+    let widths: Vec<usize> = (0..columns.len())
+        .map(|i| {
+            lines
+                .iter()
+                .map(|line| line[i].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    lines
+        .iter()
+        .map(|line| {
+            line.iter()
+                .zip(&widths)
+                .map(|(text, width)| format!("{:width$}", text, width = width))
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn csv(value: &Value, columns: &[&str]) -> String {
+    let rows = rows(value);
+    let columns = self::columns(&rows, columns);
+    let header: Vec<&str> = columns.iter().map(String::as_str).collect();
+
+    std::iter::once(csv_line(&header))
+        .chain(rows.iter().map(|row| {
+            let cells: Vec<String> = columns.iter().map(|column| cell(row, column)).collect();
+            csv_line(&cells.iter().map(String::as_str).collect::<Vec<_>>())
+        }))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// A CSV record, quoting the fields that need it
+pub fn csv_line(fields: &[&str]) -> String {
+    fields
+        .iter()
+        .map(|field| {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
@@ -0,0 +1,92 @@
+//! JMESPath expressions, to select fields of a result with `--query`.
+
+use jmespath::Expression;
+use serde_json::Value;
+
+/// A parsed expression
+#[derive(Debug, Clone)]
+pub struct Query(Expression<'static>);
+
+impl Query {
+    /// Parse an expression such as `values[?state=='FAILED'].key`
+    pub fn parse(expression: &str) -> Result<Query, String> {
+        jmespath::compile(&legacy_literals(expression))
+            .map(Query)
+            .map_err(|e| e.to_string())
+    }
+
+    /// Evaluate the expression against a value
+    pub fn search(&self, value: &Value) -> Result<Value, String> {
+        let result = self.0.search(value).map_err(|e| e.to_string())?;
+        let result = serde_json::to_value(&*result).map_err(|e| e.to_string())?;
+        Ok(integers(result))
+    }
+}
+
+/// Quote the backtick literals that are not valid JSON, such as `` `FAILED` ``, which JMESPath
+/// used to read as strings. Raw strings and quoted identifiers are left alone.
+fn legacy_literals(expression: &str) -> String {
+    let mut quoted = String::with_capacity(expression.len());
+    let mut chars = expression.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' | '"' => {
+                quoted.push(c);
+                while let Some(next) = chars.next() {
+                    quoted.push(next);
+                    match next {
+                        '\\' => quoted.extend(chars.next()),
+                        _ if next == c => break,
+                        _ => {}
+                    }
+                }
+            }
+            '`' => {
+                let mut literal = String::new();
+                let mut terminated = false;
+                while let Some(next) = chars.next() {
+                    match next {
+                        '\\' if chars.as_str().starts_with('`') => literal.extend(chars.next()),
+                        '`' => {
+                            terminated = true;
+                            break;
+                        }
+                        _ => literal.push(next),
+                    }
+                }
+                if terminated && serde_json::from_str::<Value>(&literal).is_err() {
+                    literal = Value::from(literal.trim_start()).to_string();
+                }
+                quoted.push('`');
+                quoted.push_str(&literal.replace('`', "\\`"));
+                if terminated {
+                    quoted.push('`');
+                }
+            }
+            _ => quoted.push(c),
+        }
+    }
+
+    quoted
+}
+
+/// JMESPath numbers are all floats, so turn the whole ones back into integers, as the API sent them
+fn integers(value: Value) -> Value {
+    match value {
+        Value::Number(number) => match number.as_f64() {
+            Some(float) if float.fract() == 0.0 && float.abs() < i64::MAX as f64 => {
+                Value::from(float as i64)
+            }
+            _ => Value::Number(number),
+        },
+        Value::Array(items) => Value::Array(items.into_iter().map(integers).collect()),
+        Value::Object(fields) => Value::Object(
+            fields
+                .into_iter()
+                .map(|(key, value)| (key, integers(value)))
+                .collect(),
+        ),
+        value => value,
+    }
+}
@@ -0,0 +1,171 @@
+//! Thin REST helpers for Bitbucket endpoints not (yet) covered by `bitbucket-server-rs`.
+//!
+//! These reuse the library's [`Client`] and [`ApiError`] so that commands built on top of them
+//! report errors through the same `handle_error` path as the library-backed commands.
+
+use bitbucket_server_rs::client::{ApiError, ApiResponse, Client};
+use log::debug;
+use reqwest::header::{HeaderValue, AUTHORIZATION};
+use reqwest::{Method, RequestBuilder, Response, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+/// A single page of a paged Bitbucket collection
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Page<T> {
+    pub values: Vec<T>,
+    #[serde(default)]
+    pub is_last_page: bool,
+    pub next_page_start: Option<u32>,
+}
+
+/// Percent-encode a repository path for use in a URI, keeping the `/` separators
+pub fn encode_path(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len());
+    for byte in path.trim_matches('/').bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Build a request to `uri`, relative to the client's `/rest` base path
+pub fn request(client: &Client, method: Method, uri: &str) -> RequestBuilder {
+    client
+        .http_client
+        .request(method, format!("{}/{}", client.base_path, uri))
+}
+
+/// Authenticate and send a request, mapping unsuccessful responses to [`ApiError`]. Clients
+/// without an API token authenticate with the default headers of their HTTP client, if at all.
+pub async fn send(client: &Client, mut request: RequestBuilder) -> Result<Response, ApiError> {
+    if !client.api_token.is_empty() {
+        let mut header = HeaderValue::from_str(&format!("Bearer {}", client.api_token))
+            .map_err(|_| ApiError::RequestError)?;
+        header.set_sensitive(true);
+        request = request.header(AUTHORIZATION, header);
+    }
+
+    let response = request.send().await.map_err(|e| {
+        debug!("Error sending request: {:?}", e);
+        ApiError::RequestError
+    })?;
+
+    match response.status() {
+        status if status.is_success() => Ok(response),
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Err(ApiError::Unauthorized),
+        status if status.is_client_error() => Err(ApiError::HttpClientError(
+            status.as_u16(),
+            response.text().await.unwrap_or_default(),
+        )),
+        status if status.is_server_error() => Err(ApiError::HttpServerError(
+            status.as_u16(),
+            response.text().await.unwrap_or_default(),
+        )),
+        status => Err(ApiError::UnexpectedResponse(
+            status.as_u16(),
+            response.text().await.unwrap_or_default(),
+        )),
+    }
+}
+
+/// Read a JSON response body. An empty body is `Ok(None)`, same as the library.
+pub async fn read_json<T: DeserializeOwned>(response: Response) -> ApiResponse<T> {
+    let body = response.text().await.map_err(|e| {
+        debug!("Error reading response: {:?}", e);
+        ApiError::ResponseError
+    })?;
+
+    if body.is_empty() {
+        return Ok(None);
+    }
+
+    serde_json::from_str(&body)
+        .map(Some)
+        .map_err(|e| ApiError::DeserializationError(e.to_string()))
+}
+
+/// GET a single resource
+pub async fn get<T: DeserializeOwned>(
+    client: &Client,
+    uri: &str,
+    params: &[(&str, String)],
+) -> ApiResponse<T> {
+    let response = send(client, request(client, Method::GET, uri).query(params)).await?;
+    read_json(response).await
+}
+
+/// GET every page of a paged collection
+pub async fn get_paged<T: DeserializeOwned>(
+    client: &Client,
+    uri: &str,
+    params: &[(&str, String)],
+) -> Result<Vec<T>, ApiError> {
+    let mut values = Vec::new();
+    let mut start = 0;
+
+    loop {
+        let mut query = params.to_vec();
+        query.push(("start", start.to_string()));
+
+        let page: Page<T> = get(client, uri, &query)
+            .await?
+            .ok_or(ApiError::ResponseError)?;
+        values.extend(page.values);
+
+        match page.next_page_start {
+            Some(next) if !page.is_last_page => start = next,
+            _ => break,
+        }
+    }
+
+    Ok(values)
+}
+
+/// Send a JSON body with the given method
+pub async fn send_json<B: Serialize + ?Sized, T: DeserializeOwned>(
+    client: &Client,
+    method: Method,
+    uri: &str,
+    body: &B,
+) -> ApiResponse<T> {
+    let response = send(client, request(client, method, uri).json(body)).await?;
+    read_json(response).await
+}
+
+/// POST a JSON body
+pub async fn post<B: Serialize + ?Sized, T: DeserializeOwned>(
+    client: &Client,
+    uri: &str,
+    body: &B,
+) -> ApiResponse<T> {
+    send_json(client, Method::POST, uri, body).await
+}
+
+/// PUT a JSON body
+pub async fn put<B: Serialize + ?Sized, T: DeserializeOwned>(
+    client: &Client,
+    uri: &str,
+    body: &B,
+) -> ApiResponse<T> {
+    send_json(client, Method::PUT, uri, body).await
+}
+
+/// DELETE a resource, optionally with a JSON body
+pub async fn delete<B: Serialize + ?Sized>(
+    client: &Client,
+    uri: &str,
+    body: Option<&B>,
+) -> Result<(), ApiError> {
+    let mut request = request(client, Method::DELETE, uri);
+    if let Some(body) = body {
+        request = request.json(body);
+    }
+
+    send(client, request).await.map(|_| ())
+}
@@ -0,0 +1,116 @@
+use bitbucket_server_cli::bitbucket::ScopeArgs;
+use bitbucket_server_cli::cmd::access_token::create::{handle, AccessTokenCreateArgs};
+use bitbucket_server_cli::cmd::access_token::{TokenPermission, TokenScope};
+use bitbucket_server_cli::cmd::Command;
+use bitbucket_server_rs::client;
+use clap::{CommandFactory, FromArgMatches};
+use mockito::{Matcher, Server};
+use serde_json::json;
+
+const CREATED: &str = r#"{
+    "id": "123456789012",
+    "name": "ci",
+    "permissions": ["PROJECT_READ", "REPO_WRITE"],
+    "createdDate": 1738198923000,
+    "expiryDate": 1745974923000,
+    "token": "BBDC-secret"
+}"#;
+
+#[tokio::test]
+async fn test_create_project_token() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("PUT", "/rest/access-tokens/latest/projects/PROJ")
+        .match_body(Matcher::Json(json!({
+            "name": "ci",
+            "permissions": ["PROJECT_READ", "REPO_WRITE"],
+            "expiryDays": 90
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(CREATED)
+        .create_async()
+        .await;
+
+    let scope = TokenScope::Project(ScopeArgs {
+        project_key: "PROJ".to_string(),
+        repository_slug: None,
+    });
+    let create_args = AccessTokenCreateArgs {
+        name: "ci".to_string(),
+        permission: vec![TokenPermission::ProjectRead, TokenPermission::RepoWrite],
+        expiry_days: Some(90),
+        env: None,
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&scope, &create_args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_create_without_expiry() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("PUT", "/rest/access-tokens/latest/users/jdoe")
+        .match_body(Matcher::Json(json!({
+            "name": "ci",
+            "permissions": ["REPO_READ"]
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(CREATED)
+        .create_async()
+        .await;
+
+    let create_args = AccessTokenCreateArgs {
+        name: "ci".to_string(),
+        permission: vec![TokenPermission::RepoRead],
+        expiry_days: None,
+        env: Some("BITBUCKET_API_TOKEN".to_string()),
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&TokenScope::User("jdoe".to_string()), &create_args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[test]
+fn test_create_arguments() {
+    let matches = Command::command()
+        .try_get_matches_from(vec![
+            "bitbucket-server-cli",
+            "access-token",
+            "create",
+            "--name",
+            "ci",
+            "--permission",
+            "repo-read",
+            "--permission",
+            "project-admin",
+            "--env",
+        ])
+        .unwrap();
+    let (_, matches) = matches.subcommand().unwrap();
+    let (_, matches) = matches.subcommand().unwrap();
+    let create_args = AccessTokenCreateArgs::from_arg_matches(matches).unwrap();
+
+    assert_eq!(
+        create_args.permission,
+        vec![TokenPermission::RepoRead, TokenPermission::ProjectAdmin]
+    );
+    assert_eq!(create_args.env, Some("BITBUCKET_API_TOKEN".to_string()));
+
+    // at least one permission is needed
+    assert!(Command::command()
+        .try_get_matches_from(vec![
+            "bitbucket-server-cli",
+            "access-token",
+            "create",
+            "--name",
+            "ci"
+        ])
+        .is_err());
+}
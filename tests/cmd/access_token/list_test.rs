@@ -0,0 +1,100 @@
+use bitbucket_server_cli::bitbucket::ScopeArgs;
+use bitbucket_server_cli::cmd::access_token::list::handle;
+use bitbucket_server_cli::cmd::access_token::TokenScope;
+use bitbucket_server_cli::cmd::Command;
+use bitbucket_server_rs::client;
+use clap::CommandFactory;
+use mockito::Server;
+
+#[tokio::test]
+async fn test_list_user_tokens() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("GET", "/rest/access-tokens/latest/users/jdoe")
+        .match_query(mockito::Matcher::UrlEncoded("start".into(), "0".into()))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{
+                "values": [
+                    {
+                        "id": "123456789012",
+                        "name": "ci",
+                        "permissions": ["REPO_READ", "PROJECT_READ"],
+                        "createdDate": 1738198923000,
+                        "expiryDate": 1745974923000
+                    }
+                ],
+                "isLastPage": true
+            }"#,
+        )
+        .create_async()
+        .await;
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&TokenScope::User("jdoe".to_string()), &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_list_repository_tokens() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("GET", "/rest/access-tokens/latest/projects/PROJ/repos/repo")
+        .match_query(mockito::Matcher::Any)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"values": [], "isLastPage": true}"#)
+        .create_async()
+        .await;
+
+    let scope = TokenScope::Project(ScopeArgs {
+        project_key: "PROJ".to_string(),
+        repository_slug: Some("repo".to_string()),
+    });
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&scope, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[test]
+fn test_scope_arguments() {
+    for args in [
+        vec!["bitbucket-server-cli", "access-token", "list"],
+        vec![
+            "bitbucket-server-cli",
+            "access-token",
+            "--user",
+            "jdoe",
+            "list",
+        ],
+        vec![
+            "bitbucket-server-cli",
+            "access-token",
+            "-k",
+            "PROJ",
+            "-r",
+            "repo",
+            "list",
+        ],
+    ] {
+        assert!(Command::command().try_get_matches_from(args).is_ok());
+    }
+
+    for args in [
+        vec!["bitbucket-server-cli", "access-token", "-r", "repo", "list"],
+        vec![
+            "bitbucket-server-cli",
+            "access-token",
+            "-u",
+            "jdoe",
+            "-k",
+            "PROJ",
+            "list",
+        ],
+    ] {
+        assert!(Command::command().try_get_matches_from(args).is_err());
+    }
+}
@@ -0,0 +1,4 @@
+mod create_test;
+mod list_test;
+mod revoke_test;
+mod rotate_test;
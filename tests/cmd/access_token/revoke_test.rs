@@ -0,0 +1,50 @@
+use bitbucket_server_cli::cmd::access_token::revoke::{handle, AccessTokenRevokeArgs};
+use bitbucket_server_cli::cmd::access_token::TokenScope;
+use bitbucket_server_cli::cmd::CommandError;
+use bitbucket_server_rs::client;
+use bitbucket_server_rs::client::ApiError;
+use mockito::Server;
+
+#[tokio::test]
+async fn test_revoke_token() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock(
+            "DELETE",
+            "/rest/access-tokens/latest/users/jdoe/123456789012",
+        )
+        .with_status(204)
+        .create_async()
+        .await;
+
+    let revoke_args = AccessTokenRevokeArgs {
+        id: "123456789012".to_string(),
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&TokenScope::User("jdoe".to_string()), &revoke_args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_revoke_unknown_token() {
+    let mut server = Server::new_async().await;
+    let _mock = server
+        .mock("DELETE", "/rest/access-tokens/latest/users/jdoe/404")
+        .with_status(404)
+        .with_body(r#"{"errors": [{"message": "No access token exists with ID 404"}]}"#)
+        .create_async()
+        .await;
+
+    let revoke_args = AccessTokenRevokeArgs {
+        id: "404".to_string(),
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&TokenScope::User("jdoe".to_string()), &revoke_args, &client).await;
+    assert!(matches!(
+        result,
+        Err(CommandError::ApiError(ApiError::HttpClientError(404, _)))
+    ));
+}
@@ -0,0 +1,159 @@
+use bitbucket_server_cli::bitbucket::ScopeArgs;
+use bitbucket_server_cli::cmd::access_token::rotate::{handle, AccessTokenRotateArgs};
+use bitbucket_server_cli::cmd::access_token::TokenScope;
+use bitbucket_server_cli::cmd::Command;
+use bitbucket_server_rs::client;
+use clap::{CommandFactory, FromArgMatches};
+use mockito::{Matcher, Server};
+use serde_json::json;
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_rotate_token() {
+    let mut server = Server::new_async().await;
+    let get = server
+        .mock(
+            "GET",
+            "/rest/access-tokens/latest/projects/PROJ/repos/repo/111",
+        )
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{
+                "id": "111",
+                "name": "deploy",
+                "permissions": ["REPO_WRITE"],
+                "createdDate": 1738198923000,
+                "expiryDate": 1745974923000
+            }"#,
+        )
+        .create_async()
+        .await;
+    // the old token lived for 90 days
+    let create = server
+        .mock("PUT", "/rest/access-tokens/latest/projects/PROJ/repos/repo")
+        .match_body(Matcher::Json(json!({
+            "name": "deploy",
+            "permissions": ["REPO_WRITE"],
+            "expiryDays": 90
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"id": "222", "name": "deploy", "permissions": ["REPO_WRITE"], "token": "BBDC-new"}"#,
+        )
+        .create_async()
+        .await;
+    let revoke = server
+        .mock(
+            "DELETE",
+            "/rest/access-tokens/latest/projects/PROJ/repos/repo/111",
+        )
+        .with_status(204)
+        .create_async()
+        .await;
+
+    let scope = TokenScope::Project(ScopeArgs {
+        project_key: "PROJ".to_string(),
+        repository_slug: Some("repo".to_string()),
+    });
+    let rotate_args = AccessTokenRotateArgs {
+        id: "111".to_string(),
+        name: None,
+        expiry_days: None,
+        grace_period: Duration::ZERO,
+        env: Some("DEPLOY_TOKEN".to_string()),
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&scope, &rotate_args, &client).await;
+    assert!(result.is_ok());
+    get.assert_async().await;
+    create.assert_async().await;
+    revoke.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_rotate_keeps_old_token_when_create_fails() {
+    let mut server = Server::new_async().await;
+    let _get = server
+        .mock("GET", "/rest/access-tokens/latest/users/jdoe/111")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"id": "111", "name": "ci", "permissions": ["REPO_READ"]}"#)
+        .create_async()
+        .await;
+    let _create = server
+        .mock("PUT", "/rest/access-tokens/latest/users/jdoe")
+        .with_status(400)
+        .with_body(r#"{"errors": [{"message": "Expiry is too long"}]}"#)
+        .create_async()
+        .await;
+    let revoke = server
+        .mock("DELETE", "/rest/access-tokens/latest/users/jdoe/111")
+        .expect(0)
+        .create_async()
+        .await;
+
+    let rotate_args = AccessTokenRotateArgs {
+        id: "111".to_string(),
+        name: None,
+        expiry_days: Some(400),
+        grace_period: Duration::ZERO,
+        env: None,
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&TokenScope::User("jdoe".to_string()), &rotate_args, &client).await;
+    assert!(result.is_err());
+    revoke.assert_async().await;
+}
+
+#[test]
+fn test_grace_period() {
+    let parse = |args: Vec<&str>| {
+        let matches = Command::command().try_get_matches_from(args)?;
+        let (_, matches) = matches.subcommand().unwrap();
+        let (_, matches) = matches.subcommand().unwrap();
+        AccessTokenRotateArgs::from_arg_matches(matches)
+    };
+
+    let rotate_args = parse(vec![
+        "bitbucket-server-cli",
+        "access-token",
+        "rotate",
+        "111",
+    ])
+    .unwrap();
+    assert_eq!(rotate_args.grace_period, Duration::from_secs(300));
+
+    let rotate_args = parse(vec![
+        "bitbucket-server-cli",
+        "access-token",
+        "rotate",
+        "111",
+        "--grace-period",
+        "1h",
+    ])
+    .unwrap();
+    assert_eq!(rotate_args.grace_period, Duration::from_secs(3600));
+
+    assert!(parse(vec![
+        "bitbucket-server-cli",
+        "access-token",
+        "rotate",
+        "111",
+        "--grace-period",
+        "1 day",
+    ])
+    .is_err());
+    assert!(parse(vec![
+        "bitbucket-server-cli",
+        "access-token",
+        "rotate",
+        "111",
+        "--grace-period",
+        "18446744073709551615h",
+    ])
+    .is_err());
+}
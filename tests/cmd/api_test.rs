@@ -0,0 +1,192 @@
+use bitbucket_server_cli::cmd::api::{handle, ApiArgs};
+use bitbucket_server_cli::cmd::{Command, CommandError};
+use bitbucket_server_rs::client;
+use bitbucket_server_rs::client::ApiError;
+use clap::{CommandFactory, FromArgMatches};
+use mockito::{Matcher, Server};
+use serde_json::json;
+
+/// Parse the arguments of `api` as the CLI would
+fn api_args(args: &[&str]) -> ApiArgs {
+    let matches = Command::command()
+        .try_get_matches_from(["bitbucket-server-cli", "api"].iter().chain(args))
+        .expect("Failed to parse arguments");
+    let (_, matches) = matches.subcommand().unwrap();
+    ApiArgs::from_arg_matches(matches).unwrap()
+}
+
+#[tokio::test]
+async fn test_api_get_with_fields_as_query() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("GET", "/rest/api/latest/projects")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("name".into(), "Platform".into()),
+            Matcher::UrlEncoded("limit".into(), "5".into()),
+        ]))
+        .match_header("x-atlassian-token", "no-check")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"values": [], "isLastPage": true}"#)
+        .create_async()
+        .await;
+
+    let args = api_args(&[
+        "get",
+        "/rest/api/latest/projects",
+        "-F",
+        "name=Platform",
+        "-F",
+        "limit=5",
+        "-H",
+        "X-Atlassian-Token: no-check",
+        "--include",
+    ]);
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_api_post_with_typed_fields() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/rest/api/latest/projects")
+        .match_header("content-type", "application/json")
+        .match_body(Matcher::Json(json!({
+            "key": "PLAT",
+            "public": false,
+            "avatar": null,
+            "priority": 3
+        })))
+        .with_status(201)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"id": 1, "key": "PLAT"}"#)
+        .create_async()
+        .await;
+
+    let args = api_args(&[
+        "POST",
+        "api/latest/projects",
+        "-F",
+        "key=PLAT",
+        "-F",
+        "public=false",
+        "-F",
+        "avatar=null",
+        "-F",
+        "priority=3",
+    ]);
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_api_input_file() {
+    let file = std::env::temp_dir().join("bitbucket-server-cli-api-input.json");
+    std::fs::write(&file, r#"{"name": "renamed"}"#).unwrap();
+
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("PUT", "/rest/api/latest/projects/PROJ/repos/repo")
+        .match_query(Matcher::UrlEncoded("dryRun".into(), "true".into()))
+        .match_body(Matcher::Json(json!({"name": "renamed"})))
+        .with_status(204)
+        .create_async()
+        .await;
+
+    let args = api_args(&[
+        "PUT",
+        "api/latest/projects/PROJ/repos/repo",
+        "--input",
+        file.to_str().unwrap(),
+        "-F",
+        "dryRun=true",
+    ]);
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_api_paginate() {
+    let mut server = Server::new_async().await;
+    let first = server
+        .mock("GET", "/rest/api/latest/repos")
+        .match_query(Matcher::Missing)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"values": [{"slug": "a"}], "isLastPage": false, "nextPageStart": 1}"#)
+        .create_async()
+        .await;
+    let second = server
+        .mock("GET", "/rest/api/latest/repos")
+        .match_query(Matcher::UrlEncoded("start".into(), "1".into()))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"values": [{"slug": "b"}], "isLastPage": true}"#)
+        .create_async()
+        .await;
+
+    let args = api_args(&["GET", "api/latest/repos", "--paginate"]);
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&args, &client).await;
+    assert!(result.is_ok());
+    first.assert_async().await;
+    second.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_api_error_status() {
+    let mut server = Server::new_async().await;
+    let _mock = server
+        .mock("DELETE", "/rest/api/latest/projects/NOPE")
+        .with_status(404)
+        .with_body(r#"{"errors": [{"message": "Project NOPE does not exist."}]}"#)
+        .create_async()
+        .await;
+
+    let args = api_args(&["DELETE", "api/latest/projects/NOPE"]);
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&args, &client).await;
+    assert!(matches!(
+        result,
+        Err(CommandError::ApiError(ApiError::HttpClientError(404, _)))
+    ));
+}
+
+#[tokio::test]
+async fn test_api_rejects_other_hosts() {
+    let client = client::new("https://bitbucket.example.com/rest", "token");
+
+    let args = api_args(&[
+        "GET",
+        "https://elsewhere.example.com/rest/api/latest/projects",
+    ]);
+    let result = handle(&args, &client).await;
+    assert!(matches!(result, Err(CommandError::ArgumentError(_))));
+
+    let args = api_args(&["POST", "api/latest/repos", "--paginate"]);
+    let result = handle(&args, &client).await;
+    assert!(matches!(result, Err(CommandError::ArgumentError(_))));
+}
+
+#[test]
+fn test_api_invalid_arguments() {
+    for args in [
+        vec!["bitbucket-server-cli", "api", "GET"],
+        vec!["bitbucket-server-cli", "api", "GET", "x", "-F", "novalue"],
+        vec!["bitbucket-server-cli", "api", "GET", "x", "-H", "NoColon"],
+    ] {
+        assert!(Command::command().try_get_matches_from(args).is_err());
+    }
+}
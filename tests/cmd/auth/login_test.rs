@@ -0,0 +1,136 @@
+use bitbucket_server_cli::cmd::auth::login::handle;
+use bitbucket_server_cli::cmd::Command;
+use bitbucket_server_cli::credentials::{FileStore, SecretStore};
+use bitbucket_server_rs::client;
+use clap::Parser;
+use mockito::Server;
+use std::io::Write;
+use std::path::PathBuf;
+
+fn temp_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir()
+        .join("bitbucket-server-cli-auth")
+        .join(name);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+#[tokio::test]
+async fn test_login_saves_valid_token() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("GET", "/rest/api/latest/application-properties")
+        .match_header("authorization", "Bearer new-token")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_header("X-AUSERNAME", "jdoe")
+        .with_header("X-AUSERID", "42")
+        .with_body(r#"{"version": "8.19.1", "displayName": "Bitbucket"}"#)
+        .create_async()
+        .await;
+
+    let token_file = temp_path("login-token");
+    std::fs::write(&token_file, "new-token\n").unwrap();
+    let args = Command::parse_from([
+        "bitbucket-server-cli",
+        "--api-token-file",
+        token_file.to_str().unwrap(),
+        "auth",
+        "login",
+    ]);
+
+    let store = FileStore::new(temp_path("login-store"), "passphrase");
+    let base_path = format!("{}/rest", server.url());
+    let client = client::new(&base_path, "");
+    let result = handle(&args.server_args, &client, &store).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+
+    assert_eq!(
+        store.get(&base_path).unwrap(),
+        Some("new-token".to_string())
+    );
+}
+
+#[tokio::test]
+async fn test_login_rejects_invalid_token() {
+    let mut server = Server::new_async().await;
+    let _mock = server
+        .mock("GET", "/rest/api/latest/application-properties")
+        .with_status(401)
+        .create_async()
+        .await;
+
+    let token_file = temp_path("login-invalid-token");
+    std::fs::write(&token_file, "wrong-token").unwrap();
+    let args = Command::parse_from([
+        "bitbucket-server-cli",
+        "--api-token-file",
+        token_file.to_str().unwrap(),
+        "auth",
+        "login",
+    ]);
+
+    let store = FileStore::new(temp_path("login-invalid-store"), "passphrase");
+    let base_path = format!("{}/rest", server.url());
+    let client = client::new(&base_path, "");
+    let result = handle(&args.server_args, &client, &store).await;
+    assert!(result.is_err());
+
+    assert_eq!(store.get(&base_path).unwrap(), None);
+}
+
+#[tokio::test]
+async fn test_login_reads_stdin_token_once() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("GET", "/rest/api/latest/application-properties")
+        .match_header("authorization", "Bearer piped-token")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_header("X-AUSERNAME", "jdoe")
+        .with_body(r#"{"version": "8.19.1"}"#)
+        .create_async()
+        .await;
+
+    let config_home = temp_path("login-stdin-config");
+    let _ = std::fs::remove_dir_all(&config_home);
+    let settings = temp_path("login-stdin-settings.yaml");
+    std::fs::write(&settings, "secret_store: file\n").unwrap();
+
+    // through the binary, where the client is built from the same stdin first
+    let base_path = format!("{}/rest", server.url());
+    let mut login = std::process::Command::new(env!("CARGO_BIN_EXE_bitbucket-server-cli"))
+        .args(["--server", &base_path, "--api-token-stdin", "auth", "login"])
+        .env("XDG_CONFIG_HOME", &config_home)
+        .env("BITBUCKET_CLI_CONFIG", &settings)
+        .env("BITBUCKET_CREDENTIALS_PASSPHRASE", "passphrase")
+        .env_remove("BITBUCKET_SERVER")
+        .env_remove("BITBUCKET_API_TOKEN")
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::null())
+        .spawn()
+        .unwrap();
+    login
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"piped-token\n")
+        .unwrap();
+    let status = tokio::task::spawn_blocking(move || login.wait())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(status.success());
+    mock.assert_async().await;
+
+    let store = FileStore::new(
+        config_home.join("bitbucket-server-cli").join("credentials"),
+        "passphrase",
+    );
+    assert_eq!(
+        store.get(&base_path).unwrap(),
+        Some("piped-token".to_string())
+    );
+}
@@ -0,0 +1,40 @@
+use bitbucket_server_cli::cmd::auth::logout::handle;
+use bitbucket_server_cli::cmd::CommandError;
+use bitbucket_server_cli::credentials::{FileStore, SecretStore};
+use bitbucket_server_rs::client;
+
+#[tokio::test]
+async fn test_logout_removes_token() {
+    let path = std::env::temp_dir()
+        .join("bitbucket-server-cli-auth")
+        .join("logout-store");
+    let _ = std::fs::remove_file(&path);
+    let store = FileStore::new(path, "passphrase");
+    store
+        .set("https://bitbucket.example.com/rest", "token")
+        .unwrap();
+
+    // a trailing slash still refers to the same server
+    let client = client::new("https://bitbucket.example.com/rest/", "token");
+    let result = handle(&client, &store).await;
+    assert!(result.is_ok());
+    assert_eq!(
+        store.get("https://bitbucket.example.com/rest").unwrap(),
+        None
+    );
+
+    let result = handle(&client, &store).await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_logout_needs_server() {
+    let path = std::env::temp_dir()
+        .join("bitbucket-server-cli-auth")
+        .join("logout-no-server-store");
+    let store = FileStore::new(path, "passphrase");
+
+    let client = client::new("", "");
+    let result = handle(&client, &store).await;
+    assert!(matches!(result, Err(CommandError::ArgumentError(_))));
+}
@@ -0,0 +1,3 @@
+mod login_test;
+mod logout_test;
+mod status_test;
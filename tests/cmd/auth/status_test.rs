@@ -0,0 +1,52 @@
+use bitbucket_server_cli::cmd::auth::status::handle;
+use bitbucket_server_cli::cmd::CommandError;
+use bitbucket_server_cli::credentials::FileStore;
+use bitbucket_server_rs::client;
+use bitbucket_server_rs::client::ApiError;
+use mockito::Server;
+
+fn store(name: &str) -> FileStore {
+    let path = std::env::temp_dir()
+        .join("bitbucket-server-cli-auth")
+        .join(name);
+    let _ = std::fs::remove_file(&path);
+    FileStore::new(path, "passphrase")
+}
+
+#[tokio::test]
+async fn test_status_shows_user() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("GET", "/rest/api/latest/application-properties")
+        .match_header("authorization", "Bearer token")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_header("X-AUSERNAME", "jdoe")
+        .with_body(r#"{"version": "8.19.1", "buildNumber": "8019001", "displayName": "Bitbucket"}"#)
+        .create_async()
+        .await;
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&client, &store("status-store")).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_status_anonymous() {
+    let mut server = Server::new_async().await;
+    let _mock = server
+        .mock("GET", "/rest/api/latest/application-properties")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"version": "8.19.1"}"#)
+        .create_async()
+        .await;
+
+    let client = client::new(&format!("{}/rest", server.url()), "");
+    let result = handle(&client, &store("status-anonymous-store")).await;
+    assert!(matches!(
+        result,
+        Err(CommandError::ApiError(ApiError::Unauthorized))
+    ));
+}
@@ -0,0 +1,61 @@
+use bitbucket_server_cli::bitbucket::RepositoryArgs;
+use bitbucket_server_cli::cmd::branch::create::{handle, BranchCreateArgs};
+use bitbucket_server_rs::client;
+use mockito::{Matcher, Server};
+
+#[tokio::test]
+async fn test_create_branch_success() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/rest/api/latest/projects/TEST/repos/repo/branches")
+        .match_body(Matcher::PartialJson(serde_json::json!({
+            "name": "release/1.2",
+            "startPoint": "abc123",
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"id": "refs/heads/release/1.2", "displayId": "release/1.2", "type": "BRANCH", "latestCommit": "abc123", "isDefault": false}"#)
+        .create_async()
+        .await;
+
+    let repository_args = RepositoryArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: "repo".to_string(),
+    };
+    let create_args = BranchCreateArgs {
+        name: "release/1.2".to_string(),
+        from: "abc123".to_string(),
+        message: None,
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&repository_args, &create_args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_create_branch_conflict() {
+    let mut server = Server::new_async().await;
+    let _m = server
+        .mock("POST", "/rest/api/latest/projects/TEST/repos/repo/branches")
+        .with_status(409)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"errors":[{"message":"Branch already exists"}]}"#)
+        .create_async()
+        .await;
+
+    let repository_args = RepositoryArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: "repo".to_string(),
+    };
+    let create_args = BranchCreateArgs {
+        name: "main".to_string(),
+        from: "abc123".to_string(),
+        message: None,
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&repository_args, &create_args, &client).await;
+    assert!(result.is_err());
+}
@@ -0,0 +1,72 @@
+use bitbucket_server_cli::bitbucket::RepositoryArgs;
+use bitbucket_server_cli::cmd::branch::default::{get, set, BranchDefaultSetArgs};
+use bitbucket_server_cli::cmd::Command;
+use bitbucket_server_rs::client;
+use clap::{CommandFactory, Parser};
+use mockito::{Matcher, Server};
+
+fn repository_args() -> RepositoryArgs {
+    RepositoryArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: "repo".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_get_default_branch() {
+    let mut server = Server::new_async().await;
+    let _m = server
+        .mock("GET", "/rest/api/latest/projects/TEST/repos/repo/default-branch")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"id": "refs/heads/main", "displayId": "main", "type": "BRANCH"}"#)
+        .create_async()
+        .await;
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = get(&repository_args(), &client).await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_set_default_branch() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("PUT", "/rest/api/latest/projects/TEST/repos/repo/default-branch")
+        .match_body(Matcher::Json(serde_json::json!({"id": "refs/heads/develop"})))
+        .with_status(204)
+        .create_async()
+        .await;
+
+    let set_args = BranchDefaultSetArgs {
+        branch: "develop".to_string(),
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = set(&repository_args(), &set_args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[test]
+fn test_branch_default_set_command_line_args() {
+    let args = vec![
+        "bitbucket-server-cli",
+        "branch",
+        "-k", "TEST",
+        "-r", "repo",
+        "default",
+        "set",
+        "develop",
+    ];
+
+    Command::parse_from(args.clone());
+
+    let matches = Command::command().try_get_matches_from(args).expect("Failed to parse arguments");
+    let set_matches = matches
+        .subcommand_matches("branch")
+        .and_then(|m| m.subcommand_matches("default"))
+        .and_then(|m| m.subcommand_matches("set"))
+        .expect("No default set subcommand");
+    assert_eq!(set_matches.get_one::<String>("branch").map(|s| s.as_str()), Some("develop"));
+}
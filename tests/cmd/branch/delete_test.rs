@@ -0,0 +1,68 @@
+use bitbucket_server_cli::bitbucket::RepositoryArgs;
+use bitbucket_server_cli::cmd::branch::delete::{handle, BranchDeleteArgs};
+use bitbucket_server_cli::cmd::CommandError;
+use bitbucket_server_rs::client;
+use mockito::{Matcher, Server, ServerGuard};
+
+async fn mock_default_branch(server: &mut ServerGuard) -> mockito::Mock {
+    server
+        .mock("GET", "/rest/api/latest/projects/TEST/repos/repo/default-branch")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"id": "refs/heads/main", "displayId": "main", "type": "BRANCH"}"#)
+        .create_async()
+        .await
+}
+
+fn repository_args() -> RepositoryArgs {
+    RepositoryArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: "repo".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_delete_branch_dry_run() {
+    let mut server = Server::new_async().await;
+    let _default = mock_default_branch(&mut server).await;
+    let mock = server
+        .mock("DELETE", "/rest/branch-utils/latest/projects/TEST/repos/repo/branches")
+        .match_body(Matcher::Json(serde_json::json!({
+            "name": "refs/heads/feature/old",
+            "dryRun": true,
+        })))
+        .with_status(204)
+        .create_async()
+        .await;
+
+    let delete_args = BranchDeleteArgs {
+        name: "feature/old".to_string(),
+        dry_run: true,
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&repository_args(), &delete_args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_delete_default_branch_is_refused() {
+    let mut server = Server::new_async().await;
+    let _default = mock_default_branch(&mut server).await;
+    let mock = server
+        .mock("DELETE", "/rest/branch-utils/latest/projects/TEST/repos/repo/branches")
+        .expect(0)
+        .create_async()
+        .await;
+
+    let delete_args = BranchDeleteArgs {
+        name: "refs/heads/main".to_string(),
+        dry_run: false,
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&repository_args(), &delete_args, &client).await;
+    assert!(matches!(result, Err(CommandError::ArgumentError(_))));
+    mock.assert_async().await;
+}
@@ -0,0 +1,68 @@
+use bitbucket_server_cli::bitbucket::RepositoryArgs;
+use bitbucket_server_cli::cmd::branch::info::{handle, BranchInfoArgs};
+use bitbucket_server_cli::cmd::CommandError;
+use bitbucket_server_rs::client::{self, ApiError};
+use mockito::{Matcher, Server};
+
+fn repository_args() -> RepositoryArgs {
+    RepositoryArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: "repo".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_branch_info_exact_match() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("GET", "/rest/api/latest/projects/TEST/repos/repo/branches")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("filterText".into(), "main".into()),
+            Matcher::UrlEncoded("details".into(), "true".into()),
+        ]))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{
+            "values": [
+                {"id": "refs/heads/main-old", "displayId": "main-old", "type": "BRANCH", "latestCommit": "def456"},
+                {"id": "refs/heads/main", "displayId": "main", "type": "BRANCH", "latestCommit": "abc123", "isDefault": true,
+                 "metadata": {"com.atlassian.bitbucket.server.bitbucket-branch:ahead-behind-metadata-provider": {"ahead": 0, "behind": 0}}}
+            ],
+            "isLastPage": true
+        }"#)
+        .create_async()
+        .await;
+
+    let info_args = BranchInfoArgs {
+        branch: "main".to_string(),
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&repository_args(), &info_args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_branch_info_not_found() {
+    let mut server = Server::new_async().await;
+    let _m = server
+        .mock("GET", "/rest/api/latest/projects/TEST/repos/repo/branches")
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"values": [{"id": "refs/heads/main-old", "displayId": "main-old", "type": "BRANCH"}], "isLastPage": true}"#)
+        .create_async()
+        .await;
+
+    let info_args = BranchInfoArgs {
+        branch: "main".to_string(),
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&repository_args(), &info_args, &client).await;
+    assert!(matches!(
+        result,
+        Err(CommandError::ApiError(ApiError::HttpClientError(404, _)))
+    ));
+}
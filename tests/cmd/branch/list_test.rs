@@ -0,0 +1,110 @@
+use bitbucket_server_cli::bitbucket::{RefOrder, RepositoryArgs};
+use bitbucket_server_cli::cmd::branch::list::{handle, BranchListArgs};
+use bitbucket_server_cli::cmd::Command;
+use bitbucket_server_rs::client;
+use clap::{CommandFactory, Parser};
+use mockito::{Matcher, Server};
+
+fn repository_args() -> RepositoryArgs {
+    RepositoryArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: "repo".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_list_branches_follows_pages() {
+    let mut server = Server::new_async().await;
+    let first = server
+        .mock("GET", "/rest/api/latest/projects/TEST/repos/repo/branches")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("start".into(), "0".into()),
+            Matcher::UrlEncoded("details".into(), "false".into()),
+        ]))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{
+            "values": [{"id": "refs/heads/main", "displayId": "main", "type": "BRANCH", "latestCommit": "abc123", "isDefault": true}],
+            "isLastPage": false,
+            "nextPageStart": 1
+        }"#)
+        .create_async()
+        .await;
+    let second = server
+        .mock("GET", "/rest/api/latest/projects/TEST/repos/repo/branches")
+        .match_query(Matcher::UrlEncoded("start".into(), "1".into()))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{
+            "values": [{"id": "refs/heads/feature", "displayId": "feature", "type": "BRANCH", "latestCommit": "def456", "isDefault": false}],
+            "isLastPage": true
+        }"#)
+        .create_async()
+        .await;
+
+    let list_args = BranchListArgs {
+        filter: None,
+        details: false,
+        order_by: None,
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&repository_args(), &list_args, &client).await;
+    assert!(result.is_ok());
+    first.assert_async().await;
+    second.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_list_branches_with_options() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("GET", "/rest/api/latest/projects/TEST/repos/repo/branches")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("filterText".into(), "release".into()),
+            Matcher::UrlEncoded("details".into(), "true".into()),
+            Matcher::UrlEncoded("orderBy".into(), "MODIFICATION".into()),
+        ]))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"values": [], "isLastPage": true}"#)
+        .create_async()
+        .await;
+
+    let list_args = BranchListArgs {
+        filter: Some("release".to_string()),
+        details: true,
+        order_by: Some(RefOrder::Modification),
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&repository_args(), &list_args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[test]
+fn test_branch_list_command_line_args() {
+    let args = vec![
+        "bitbucket-server-cli",
+        "branch",
+        "--project-key", "TEST",
+        "--repository-slug", "repo",
+        "list",
+        "--filter", "release",
+        "--details",
+        "--order-by", "modification",
+    ];
+
+    Command::parse_from(args.clone());
+
+    let matches = Command::command().try_get_matches_from(args).expect("Failed to parse arguments");
+    let branch_matches = matches.subcommand_matches("branch").expect("No branch subcommand");
+    assert_eq!(branch_matches.get_one::<String>("project_key").map(|s| s.as_str()), Some("TEST"));
+    assert_eq!(branch_matches.get_one::<String>("repository_slug").map(|s| s.as_str()), Some("repo"));
+
+    let list_matches = branch_matches.subcommand_matches("list").expect("No list subcommand");
+    assert_eq!(list_matches.get_one::<String>("filter").map(|s| s.as_str()), Some("release"));
+    assert_eq!(list_matches.get_one::<bool>("details").copied(), Some(true));
+    assert_eq!(list_matches.get_one::<RefOrder>("order_by").copied(), Some(RefOrder::Modification));
+}
@@ -0,0 +1,6 @@
+mod create_test;
+mod default_test;
+mod delete_test;
+mod info_test;
+mod list_test;
+mod prune_test;
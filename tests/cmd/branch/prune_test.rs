@@ -0,0 +1,220 @@
+use bitbucket_server_cli::cmd::branch::prune::{handle, BranchPruneArgs};
+use bitbucket_server_cli::cmd::{Command, CommandError};
+use bitbucket_server_rs::client;
+use chrono::{TimeDelta, Utc};
+use clap::{CommandFactory, Parser};
+use mockito::{Matcher, Mock, Server, ServerGuard};
+
+fn branch(name: &str, days_old: i64, ahead: u64) -> String {
+    let timestamp = (Utc::now() - TimeDelta::days(days_old)).timestamp_millis();
+    format!(
+        r#"{{"id": "refs/heads/{name}", "displayId": "{name}", "type": "BRANCH", "latestCommit": "{name}-sha",
+            "isDefault": {default},
+            "metadata": {{
+                "com.atlassian.bitbucket.server.bitbucket-branch:latest-commit-metadata": {{"committerTimestamp": {timestamp}}},
+                "com.atlassian.bitbucket.server.bitbucket-branch:ahead-behind-metadata-provider": {{"ahead": {ahead}, "behind": 3}}
+            }}}}"#,
+        default = name == "main",
+    )
+}
+
+async fn mock_repository(server: &mut ServerGuard, slug: &str, branches: Vec<String>) -> Vec<Mock> {
+    let branches = server
+        .mock("GET", format!("/rest/api/latest/projects/TEST/repos/{}/branches", slug).as_str())
+        .match_query(Matcher::UrlEncoded("details".into(), "true".into()))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(format!(r#"{{"values": [{}], "isLastPage": true}}"#, branches.join(",")))
+        .create_async()
+        .await;
+    let pull_requests = server
+        .mock("GET", format!("/rest/api/latest/projects/TEST/repos/{}/pull-requests", slug).as_str())
+        .match_query(Matcher::UrlEncoded("state".into(), "OPEN".into()))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(format!(
+            r#"{{"values": [{{
+                "fromRef": {{"id": "refs/heads/has-pr", "repository": {{"slug": "{slug}"}}}},
+                "toRef": {{"id": "refs/heads/main", "repository": {{"slug": "{slug}"}}}}
+            }}], "isLastPage": true}}"#
+        ))
+        .create_async()
+        .await;
+
+    vec![branches, pull_requests]
+}
+
+fn prune_args(execute: bool, merged: bool) -> BranchPruneArgs {
+    BranchPruneArgs {
+        older_than: TimeDelta::days(90),
+        merged,
+        exclude: vec!["release/*".to_string()],
+        execute,
+        parallelism: 2,
+    }
+}
+
+#[tokio::test]
+async fn test_prune_report_does_not_delete() {
+    let mut server = Server::new_async().await;
+    let _repo = mock_repository(
+        &mut server,
+        "repo",
+        vec![branch("main", 400, 0), branch("stale", 120, 2), branch("fresh", 10, 1)],
+    )
+    .await;
+    let delete = server
+        .mock("DELETE", "/rest/branch-utils/latest/projects/TEST/repos/repo/branches")
+        .expect(0)
+        .create_async()
+        .await;
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle("TEST", Some("repo"), &prune_args(false, false), &client).await;
+    assert!(result.is_ok());
+    delete.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_prune_execute_skips_protected_branches() {
+    let mut server = Server::new_async().await;
+    let _repo = mock_repository(
+        &mut server,
+        "repo",
+        vec![
+            branch("main", 400, 0),
+            branch("stale", 120, 0),
+            branch("stale-unmerged", 120, 4),
+            branch("has-pr", 200, 0),
+            branch("release/1.0", 300, 0),
+            branch("fresh", 10, 0),
+        ],
+    )
+    .await;
+    let delete_stale = server
+        .mock("DELETE", "/rest/branch-utils/latest/projects/TEST/repos/repo/branches")
+        .match_body(Matcher::Json(serde_json::json!({"name": "refs/heads/stale", "dryRun": false})))
+        .expect(1)
+        .create_async()
+        .await;
+    let delete_others = server
+        .mock("DELETE", "/rest/branch-utils/latest/projects/TEST/repos/repo/branches")
+        .match_body(Matcher::Any)
+        .expect(0)
+        .create_async()
+        .await;
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle("TEST", Some("repo"), &prune_args(true, true), &client).await;
+    assert!(result.is_ok());
+    delete_stale.assert_async().await;
+    delete_others.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_prune_execute_continues_after_failed_delete() {
+    let mut server = Server::new_async().await;
+    let _repo = mock_repository(
+        &mut server,
+        "repo",
+        vec![branch("locked", 120, 0), branch("stale", 120, 0)],
+    )
+    .await;
+    let delete_locked = server
+        .mock("DELETE", "/rest/branch-utils/latest/projects/TEST/repos/repo/branches")
+        .match_body(Matcher::Json(serde_json::json!({"name": "refs/heads/locked", "dryRun": false})))
+        .with_status(403)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"errors": [{"message": "Branch permissions prevent deletion"}]}"#)
+        .expect(1)
+        .create_async()
+        .await;
+    let delete_stale = server
+        .mock("DELETE", "/rest/branch-utils/latest/projects/TEST/repos/repo/branches")
+        .match_body(Matcher::Json(serde_json::json!({"name": "refs/heads/stale", "dryRun": false})))
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle("TEST", Some("repo"), &prune_args(true, false), &client).await;
+    assert!(result.is_err());
+    delete_locked.assert_async().await;
+    delete_stale.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_prune_whole_project() {
+    let mut server = Server::new_async().await;
+    let repos = server
+        .mock("GET", "/rest/api/latest/projects/TEST/repos")
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"values": [
+            {"id": 1, "slug": "one", "name": "one", "scmId": "git", "project": {"key": "TEST"}},
+            {"id": 2, "slug": "two", "name": "two", "scmId": "git", "project": {"key": "TEST"}}
+        ], "isLastPage": true}"#)
+        .create_async()
+        .await;
+    let one = mock_repository(&mut server, "one", vec![branch("stale", 120, 0)]).await;
+    let two = mock_repository(&mut server, "two", vec![branch("stale", 120, 0)]).await;
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle("TEST", None, &prune_args(false, false), &client).await;
+    assert!(result.is_ok());
+    repos.assert_async().await;
+    for mock in one.iter().chain(two.iter()) {
+        mock.assert_async().await;
+    }
+}
+
+#[tokio::test]
+async fn test_prune_rejects_age_before_earliest_date() {
+    let server = Server::new_async().await;
+    let prune_args = BranchPruneArgs {
+        older_than: TimeDelta::weeks(100_000_000),
+        ..prune_args(false, false)
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle("TEST", Some("repo"), &prune_args, &client).await;
+    assert!(matches!(result, Err(CommandError::ArgumentError(_))));
+}
+
+#[test]
+fn test_branch_prune_command_line_args() {
+    let args = vec![
+        "bitbucket-server-cli",
+        "branch",
+        "--project-key", "TEST",
+        "prune",
+        "--older-than", "12w",
+        "--merged",
+        "--exclude", "release/*",
+        "--exclude", "hotfix/*",
+        "--execute",
+    ];
+
+    Command::parse_from(args.clone());
+
+    let matches = Command::command().try_get_matches_from(args).expect("Failed to parse arguments");
+    let prune_matches = matches
+        .subcommand_matches("branch")
+        .and_then(|m| m.subcommand_matches("prune"))
+        .expect("No prune subcommand");
+    assert_eq!(prune_matches.get_one::<TimeDelta>("older_than").copied(), Some(TimeDelta::weeks(12)));
+    assert_eq!(prune_matches.get_one::<bool>("execute").copied(), Some(true));
+    assert_eq!(prune_matches.get_one::<usize>("parallelism").copied(), Some(4));
+    let excludes: Vec<_> = prune_matches.get_many::<String>("exclude").unwrap().collect();
+    assert_eq!(excludes, vec!["release/*", "hotfix/*"]);
+}
+
+#[test]
+fn test_branch_prune_rejects_invalid_age() {
+    for age in ["90", "0d", "-1d"] {
+        let args = vec!["bitbucket-server-cli", "branch", "-k", "TEST", "prune", "--older-than", age];
+
+        assert!(Command::try_parse_from(args).is_err(), "{} was accepted", age);
+    }
+}
@@ -0,0 +1,103 @@
+use bitbucket_server_cli::bitbucket::ScopeArgs;
+use bitbucket_server_cli::cmd::branch_restrictions::create::{
+    handle, BranchRestrictionsCreateArgs, MatcherArgs,
+};
+use bitbucket_server_cli::cmd::branch_restrictions::RestrictionType;
+use bitbucket_server_cli::cmd::Command;
+use bitbucket_server_rs::client;
+use clap::CommandFactory;
+use mockito::{Matcher, Server};
+
+#[tokio::test]
+async fn test_create_project_pattern_restriction() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/rest/branch-permissions/latest/projects/TEST/restrictions")
+        .match_body(Matcher::Json(serde_json::json!({
+            "type": "read-only",
+            "matcher": {"id": "release/*", "type": {"id": "PATTERN"}},
+            "users": [],
+            "groups": ["release-managers"],
+            "accessKeyIds": [3]
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"id": 9, "scope": {"type": "PROJECT"}, "type": "read-only",
+                "matcher": {"id": "release/*", "type": {"id": "PATTERN"}},
+                "groups": ["release-managers"], "accessKeys": [{"key": {"id": 3}}]}"#,
+        )
+        .create_async()
+        .await;
+
+    let scope = ScopeArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: None,
+    };
+    let create_args = BranchRestrictionsCreateArgs {
+        restriction_type: RestrictionType::ReadOnly,
+        matcher: MatcherArgs {
+            branch: None,
+            pattern: Some("release/*".to_string()),
+            model_category: None,
+            model_branch: None,
+        },
+        user: vec![],
+        group: vec!["release-managers".to_string()],
+        access_key: vec![3],
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&scope, &create_args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[test]
+fn test_create_branch_matcher_uses_full_ref_name() {
+    let matcher = MatcherArgs {
+        branch: Some("main".to_string()),
+        pattern: None,
+        model_category: None,
+        model_branch: None,
+    };
+
+    assert_eq!(matcher.matcher().id, "refs/heads/main");
+}
+
+#[test]
+fn test_create_requires_exactly_one_matcher() {
+    let both = Command::command().try_get_matches_from(vec![
+        "bitbucket-server-cli",
+        "branch-restrictions",
+        "-k",
+        "TEST",
+        "create",
+        "--type",
+        "no-deletes",
+        "--branch",
+        "main",
+        "--pattern",
+        "release/*",
+    ]);
+    assert!(both.is_err());
+
+    let model_category = Command::command().try_get_matches_from(vec![
+        "bitbucket-server-cli",
+        "branch-restrictions",
+        "-k",
+        "TEST",
+        "-r",
+        "repo",
+        "create",
+        "--type",
+        "pull-request-only",
+        "--model-category",
+        "RELEASE",
+        "--user",
+        "jdoe",
+        "--user",
+        "asmith",
+    ]);
+    assert!(model_category.is_ok());
+}
@@ -0,0 +1,26 @@
+use bitbucket_server_cli::bitbucket::ScopeArgs;
+use bitbucket_server_cli::cmd::branch_restrictions::delete::{
+    handle, BranchRestrictionsDeleteArgs,
+};
+use bitbucket_server_rs::client;
+use mockito::Server;
+
+#[tokio::test]
+async fn test_delete_branch_restriction() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("DELETE", "/rest/branch-permissions/latest/projects/TEST/repos/repo/restrictions/9")
+        .with_status(204)
+        .create_async()
+        .await;
+
+    let scope = ScopeArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: Some("repo".to_string()),
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&scope, &BranchRestrictionsDeleteArgs { id: 9 }, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
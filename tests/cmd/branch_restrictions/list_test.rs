@@ -0,0 +1,61 @@
+use bitbucket_server_cli::bitbucket::ScopeArgs;
+use bitbucket_server_cli::cmd::branch_restrictions::list::handle;
+use bitbucket_server_rs::client;
+use mockito::{Matcher, Server};
+
+#[tokio::test]
+async fn test_list_repository_branch_restrictions() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("GET", "/rest/branch-permissions/latest/projects/TEST/repos/repo/restrictions")
+        .match_query(Matcher::UrlEncoded("start".into(), "0".into()))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"values": [{
+                "id": 1,
+                "scope": {"type": "REPOSITORY", "resourceId": 1},
+                "type": "read-only",
+                "matcher": {"id": "refs/heads/main", "displayId": "main", "type": {"id": "BRANCH", "name": "Branch"}, "active": true},
+                "users": [{"name": "release-bot", "displayName": "Release Bot"}],
+                "groups": ["release-managers"],
+                "accessKeys": [{"key": {"id": 3, "label": "deploy"}}]
+            }], "isLastPage": true}"#,
+        )
+        .create_async()
+        .await;
+
+    let scope = ScopeArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: Some("repo".to_string()),
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&scope, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_list_project_branch_restrictions_with_unknown_matcher() {
+    let mut server = Server::new_async().await;
+    let _mock = server
+        .mock("GET", "/rest/branch-permissions/latest/projects/TEST/restrictions")
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"values": [{"id": 1, "type": "no-deletes", "matcher": {"id": "x", "type": {"id": "SOMETHING_NEW"}}}], "isLastPage": true}"#,
+        )
+        .create_async()
+        .await;
+
+    let scope = ScopeArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: None,
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&scope, &client).await;
+    assert!(result.is_err());
+}
@@ -0,0 +1,3 @@
+mod create_test;
+mod delete_test;
+mod list_test;
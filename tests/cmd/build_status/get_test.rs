@@ -137,8 +137,6 @@ async fn test_build_status_get_command_line_args() {
     let get_matches = build_status_matches.subcommand_matches("get").expect("No get subcommand");
     assert_eq!(get_matches.get_one::<String>("key").map(|s| s.as_str()), Some("build-1"));
     
-    // Final assertion
-    assert!(true, "Command line arguments were parsed correctly");
 }
 
 #[tokio::test]
@@ -178,6 +176,4 @@ async fn test_build_status_get_command_line_args_without_key() {
     let get_matches = build_status_matches.subcommand_matches("get").expect("No get subcommand");
     assert_eq!(get_matches.get_one::<String>("key"), None);
     
-    // Final assertion
-    assert!(true, "Command line arguments were parsed correctly");
 }
@@ -174,8 +174,6 @@ async fn test_build_status_store_command_line_args() {
     assert_eq!(store_matches.get_one::<String>("build_number").map(|s| s.as_str()), Some("42"));
     assert_eq!(store_matches.get_one::<u64>("duration").copied(), Some(60000));
     
-    // Final assertion
-    assert!(true, "Command line arguments were parsed correctly");
 }
 
 #[tokio::test]
@@ -229,6 +227,4 @@ async fn test_build_status_store_command_line_args_with_test_results() {
         .unwrap_or_default();
     assert_eq!(test_results, vec![100, 5, 10]);
     
-    // Final assertion
-    assert!(true, "Command line arguments were parsed correctly");
 }
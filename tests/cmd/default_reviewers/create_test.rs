@@ -0,0 +1,126 @@
+use bitbucket_server_cli::bitbucket::{parse_ref_matcher, ScopeArgs};
+use bitbucket_server_cli::cmd::default_reviewers::create::{handle, DefaultReviewersCreateArgs};
+use bitbucket_server_cli::cmd::Command;
+use bitbucket_server_rs::client;
+use clap::CommandFactory;
+use mockito::{Matcher, Server};
+
+#[tokio::test]
+async fn test_create_resolves_reviewers_to_users() {
+    let mut server = Server::new_async().await;
+    let users_mock = server
+        .mock("GET", "/rest/api/latest/users")
+        .match_query(Matcher::UrlEncoded("filter".into(), "jdoe".into()))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"values": [{"name": "jdoe2", "id": 8}, {"name": "jdoe", "id": 7}],
+                "isLastPage": true, "start": 0, "size": 2, "limit": 25}"#,
+        )
+        .create_async()
+        .await;
+    let create_mock = server
+        .mock(
+            "POST",
+            "/rest/default-reviewers/latest/projects/TEST/repos/repo/condition",
+        )
+        .match_body(Matcher::Json(serde_json::json!({
+            "sourceMatcher": {"id": "ANY_REF_MATCHER_ID", "type": {"id": "ANY_REF"}},
+            "targetMatcher": {"id": "refs/heads/main", "type": {"id": "BRANCH"}},
+            "reviewers": [{"name": "jdoe", "id": 7}],
+            "requiredApprovals": 1
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"id": 3, "scope": {"type": "REPOSITORY"},
+                "sourceRefMatcher": {"id": "ANY_REF_MATCHER_ID", "type": {"id": "ANY_REF"}},
+                "targetRefMatcher": {"id": "refs/heads/main", "type": {"id": "BRANCH"}},
+                "reviewers": [{"name": "jdoe", "id": 7}], "requiredApprovals": 1}"#,
+        )
+        .create_async()
+        .await;
+
+    let scope = ScopeArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: Some("repo".to_string()),
+    };
+    let create_args = DefaultReviewersCreateArgs {
+        source: parse_ref_matcher("any").unwrap(),
+        target: parse_ref_matcher("branch:main").unwrap(),
+        reviewer: vec!["jdoe".to_string()],
+        required_approvals: 1,
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&scope, &create_args, &client).await;
+    assert!(result.is_ok());
+    users_mock.assert_async().await;
+    create_mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_create_unknown_reviewer() {
+    let mut server = Server::new_async().await;
+    let users_mock = server
+        .mock("GET", "/rest/api/latest/users")
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"values": [], "isLastPage": true, "start": 0, "size": 0, "limit": 25}"#)
+        .create_async()
+        .await;
+    let create_mock = server
+        .mock("POST", Matcher::Any)
+        .expect(0)
+        .create_async()
+        .await;
+
+    let scope = ScopeArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: None,
+    };
+    let create_args = DefaultReviewersCreateArgs {
+        source: parse_ref_matcher("any").unwrap(),
+        target: parse_ref_matcher("any").unwrap(),
+        reviewer: vec!["nobody".to_string()],
+        required_approvals: 0,
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&scope, &create_args, &client).await;
+    assert!(result.is_err());
+    users_mock.assert_async().await;
+    create_mock.assert_async().await;
+}
+
+#[test]
+fn test_parse_ref_matcher() {
+    assert_eq!(
+        parse_ref_matcher("branch:main").unwrap().id,
+        "refs/heads/main"
+    );
+    assert_eq!(
+        parse_ref_matcher("model-category:FEATURE")
+            .unwrap()
+            .to_string(),
+        "model-category FEATURE"
+    );
+    assert!(parse_ref_matcher("main").is_err());
+    assert!(parse_ref_matcher("branch:").is_err());
+    assert!(parse_ref_matcher("tag:v1").is_err());
+}
+
+#[test]
+fn test_create_requires_a_reviewer() {
+    let result = Command::command().try_get_matches_from(vec![
+        "bitbucket-server-cli",
+        "default-reviewers",
+        "-k",
+        "TEST",
+        "create",
+        "--target",
+        "branch:main",
+    ]);
+    assert!(result.is_err());
+}
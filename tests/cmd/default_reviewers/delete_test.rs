@@ -0,0 +1,28 @@
+use bitbucket_server_cli::bitbucket::ScopeArgs;
+use bitbucket_server_cli::cmd::default_reviewers::delete::{handle, DefaultReviewersDeleteArgs};
+use bitbucket_server_rs::client;
+use mockito::Server;
+
+#[tokio::test]
+async fn test_delete_condition() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock(
+            "DELETE",
+            "/rest/default-reviewers/latest/projects/TEST/condition/6",
+        )
+        .with_status(204)
+        .create_async()
+        .await;
+
+    let scope = ScopeArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: None,
+    };
+    let delete_args = DefaultReviewersDeleteArgs { id: 6 };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&scope, &delete_args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
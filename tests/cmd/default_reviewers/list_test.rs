@@ -0,0 +1,62 @@
+use bitbucket_server_cli::bitbucket::ScopeArgs;
+use bitbucket_server_cli::cmd::default_reviewers::list::handle;
+use bitbucket_server_rs::client;
+use mockito::Server;
+
+const CONDITIONS: &str = r#"[
+    {"id": 1, "scope": {"type": "PROJECT"},
+     "sourceRefMatcher": {"id": "ANY_REF_MATCHER_ID", "type": {"id": "ANY_REF"}},
+     "targetRefMatcher": {"id": "refs/heads/main", "type": {"id": "BRANCH"}},
+     "reviewers": [{"name": "zoe"}, {"name": "adam"}], "requiredApprovals": 1},
+    {"id": 2, "scope": {"type": "REPOSITORY"},
+     "sourceRefMatcher": {"id": "feature/*", "type": {"id": "PATTERN"}},
+     "targetRefMatcher": {"id": "ANY_REF_MATCHER_ID", "type": {"id": "ANY_REF"}},
+     "reviewers": [{"name": "jdoe"}], "requiredApprovals": 0}
+]"#;
+
+#[tokio::test]
+async fn test_list_repository_conditions() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock(
+            "GET",
+            "/rest/default-reviewers/latest/projects/TEST/repos/repo/conditions",
+        )
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(CONDITIONS)
+        .create_async()
+        .await;
+
+    let scope = ScopeArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: Some("repo".to_string()),
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&scope, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_list_project_conditions() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("GET", "/rest/default-reviewers/latest/projects/TEST/conditions")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(CONDITIONS)
+        .create_async()
+        .await;
+
+    let scope = ScopeArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: None,
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&scope, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
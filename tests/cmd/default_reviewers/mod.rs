@@ -0,0 +1,4 @@
+mod create_test;
+mod delete_test;
+mod list_test;
+mod update_test;
@@ -0,0 +1,111 @@
+use bitbucket_server_cli::bitbucket::ScopeArgs;
+use bitbucket_server_cli::cmd::default_reviewers::update::{handle, DefaultReviewersUpdateArgs};
+use bitbucket_server_rs::client;
+use mockito::{Matcher, Server};
+
+const CONDITION: &str = r#"[
+    {"id": 4, "scope": {"type": "REPOSITORY"},
+     "sourceRefMatcher": {"id": "ANY_REF_MATCHER_ID", "type": {"id": "ANY_REF"}},
+     "targetRefMatcher": {"id": "refs/heads/main", "type": {"id": "BRANCH"}},
+     "reviewers": [{"name": "jdoe"}, {"name": "old-lead"}], "requiredApprovals": 1}
+]"#;
+
+fn update_args(id: u64) -> DefaultReviewersUpdateArgs {
+    DefaultReviewersUpdateArgs {
+        id,
+        source: None,
+        target: None,
+        reviewer: vec![],
+        add_reviewer: vec!["new-lead".to_string()],
+        remove_reviewer: vec!["old-lead".to_string()],
+        required_approvals: Some(2),
+    }
+}
+
+fn user_mock(server: &mut mockito::ServerGuard, name: &str) -> mockito::Mock {
+    server
+        .mock("GET", "/rest/api/latest/users")
+        .match_query(Matcher::UrlEncoded("filter".into(), name.into()))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(format!(
+            r#"{{"values": [{{"name": "{}"}}], "isLastPage": true, "start": 0, "size": 1, "limit": 25}}"#,
+            name
+        ))
+}
+
+#[tokio::test]
+async fn test_update_keeps_fields_not_given() {
+    let mut server = Server::new_async().await;
+    let list_mock = server
+        .mock(
+            "GET",
+            "/rest/default-reviewers/latest/projects/TEST/repos/repo/conditions",
+        )
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(CONDITION)
+        .create_async()
+        .await;
+    let jdoe_mock = user_mock(&mut server, "jdoe").create_async().await;
+    let lead_mock = user_mock(&mut server, "new-lead").create_async().await;
+    let update_mock = server
+        .mock(
+            "PUT",
+            "/rest/default-reviewers/latest/projects/TEST/repos/repo/condition/4",
+        )
+        .match_body(Matcher::Json(serde_json::json!({
+            "sourceMatcher": {"id": "ANY_REF_MATCHER_ID", "type": {"id": "ANY_REF"}},
+            "targetMatcher": {"id": "refs/heads/main", "type": {"id": "BRANCH"}},
+            "reviewers": [{"name": "jdoe"}, {"name": "new-lead"}],
+            "requiredApprovals": 2
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"id": 4, "scope": {"type": "REPOSITORY"},
+                "sourceRefMatcher": {"id": "ANY_REF_MATCHER_ID", "type": {"id": "ANY_REF"}},
+                "targetRefMatcher": {"id": "refs/heads/main", "type": {"id": "BRANCH"}},
+                "reviewers": [{"name": "jdoe"}, {"name": "new-lead"}], "requiredApprovals": 2}"#,
+        )
+        .create_async()
+        .await;
+
+    let scope = ScopeArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: Some("repo".to_string()),
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&scope, &update_args(4), &client).await;
+    assert!(result.is_ok());
+    list_mock.assert_async().await;
+    jdoe_mock.assert_async().await;
+    lead_mock.assert_async().await;
+    update_mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_update_unknown_condition() {
+    let mut server = Server::new_async().await;
+    let list_mock = server
+        .mock(
+            "GET",
+            "/rest/default-reviewers/latest/projects/TEST/repos/repo/conditions",
+        )
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(CONDITION)
+        .create_async()
+        .await;
+
+    let scope = ScopeArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: Some("repo".to_string()),
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&scope, &update_args(5), &client).await;
+    assert!(result.is_err());
+    list_mock.assert_async().await;
+}
@@ -0,0 +1,45 @@
+use bitbucket_server_cli::bitbucket::RepositoryArgs;
+use bitbucket_server_cli::cmd::file::blame::{handle, FileBlameArgs};
+use bitbucket_server_rs::client;
+use mockito::{Matcher, Server};
+
+#[tokio::test]
+async fn test_blame_file() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("GET", "/rest/api/latest/projects/TEST/repos/repo/browse/CODEOWNERS")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("blame".into(), "true".into()),
+            Matcher::UrlEncoded("start".into(), "0".into()),
+        ]))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{
+            "lines": [{"text": "* @team"}],
+            "blame": [{
+                "author": {"name": "jdoe"},
+                "authorTimestamp": 1738198923000,
+                "commitHash": "abc123",
+                "displayCommitHash": "abc123",
+                "lineNumber": 1,
+                "spannedLines": 1
+            }],
+            "isLastPage": true
+        }"#)
+        .create_async()
+        .await;
+
+    let repository_args = RepositoryArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: "repo".to_string(),
+    };
+    let blame_args = FileBlameArgs {
+        path: "CODEOWNERS".to_string(),
+        at: None,
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&repository_args, &blame_args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
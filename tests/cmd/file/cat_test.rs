@@ -0,0 +1,121 @@
+use bitbucket_server_cli::bitbucket::RepositoryArgs;
+use bitbucket_server_cli::cmd::file::cat::{handle, FileCatArgs};
+use bitbucket_server_cli::cmd::Command;
+use bitbucket_server_rs::client;
+use clap::{CommandFactory, Parser};
+use mockito::{Matcher, Server};
+
+fn repository_args() -> RepositoryArgs {
+    RepositoryArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: "repo".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_cat_file_raw() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("GET", "/rest/api/latest/projects/TEST/repos/repo/raw/docs/my%20file.txt")
+        .match_query(Matcher::UrlEncoded("at".into(), "refs/heads/main".into()))
+        .with_status(200)
+        .with_body("1.2.0\n")
+        .create_async()
+        .await;
+
+    let cat_args = FileCatArgs {
+        path: "docs/my file.txt".to_string(),
+        at: Some("refs/heads/main".to_string()),
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&repository_args(), &cat_args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_cat_file_falls_back_to_browse_pages() {
+    let mut server = Server::new_async().await;
+    let _raw = server
+        .mock("GET", "/rest/api/latest/projects/TEST/repos/repo/raw/version.txt")
+        .with_status(404)
+        .create_async()
+        .await;
+    let first = server
+        .mock("GET", "/rest/api/latest/projects/TEST/repos/repo/browse/version.txt")
+        .match_query(Matcher::UrlEncoded("start".into(), "0".into()))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"lines": [{"text": "1.2.0"}], "start": 0, "isLastPage": false, "nextPageStart": 1}"#)
+        .create_async()
+        .await;
+    let second = server
+        .mock("GET", "/rest/api/latest/projects/TEST/repos/repo/browse/version.txt")
+        .match_query(Matcher::UrlEncoded("start".into(), "1".into()))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"lines": [{"text": ""}], "start": 1, "isLastPage": true}"#)
+        .create_async()
+        .await;
+
+    let cat_args = FileCatArgs {
+        path: "version.txt".to_string(),
+        at: None,
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&repository_args(), &cat_args, &client).await;
+    assert!(result.is_ok());
+    first.assert_async().await;
+    second.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_cat_missing_file() {
+    let mut server = Server::new_async().await;
+    let _raw = server
+        .mock("GET", "/rest/api/latest/projects/TEST/repos/repo/raw/missing.txt")
+        .with_status(404)
+        .create_async()
+        .await;
+    let _browse = server
+        .mock("GET", "/rest/api/latest/projects/TEST/repos/repo/browse/missing.txt")
+        .match_query(Matcher::Any)
+        .with_status(404)
+        .with_body(r#"{"errors":[{"message":"The path \"missing.txt\" does not exist"}]}"#)
+        .create_async()
+        .await;
+
+    let cat_args = FileCatArgs {
+        path: "missing.txt".to_string(),
+        at: None,
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&repository_args(), &cat_args, &client).await;
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_file_cat_command_line_args() {
+    let args = vec![
+        "bitbucket-server-cli",
+        "file",
+        "-k", "TEST",
+        "-r", "repo",
+        "cat",
+        "CODEOWNERS",
+        "--at", "release/1.2",
+    ];
+
+    Command::parse_from(args.clone());
+
+    let matches = Command::command().try_get_matches_from(args).expect("Failed to parse arguments");
+    let cat_matches = matches
+        .subcommand_matches("file")
+        .and_then(|m| m.subcommand_matches("cat"))
+        .expect("No file cat subcommand");
+    assert_eq!(cat_matches.get_one::<String>("path").map(|s| s.as_str()), Some("CODEOWNERS"));
+    assert_eq!(cat_matches.get_one::<String>("at").map(|s| s.as_str()), Some("release/1.2"));
+}
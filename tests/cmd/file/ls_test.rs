@@ -0,0 +1,71 @@
+use bitbucket_server_cli::bitbucket::RepositoryArgs;
+use bitbucket_server_cli::cmd::file::ls::{handle, FileLsArgs};
+use bitbucket_server_rs::client;
+use mockito::{Matcher, Server};
+
+fn repository_args() -> RepositoryArgs {
+    RepositoryArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: "repo".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_ls_directory() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("GET", "/rest/api/latest/projects/TEST/repos/repo/browse/src")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("at".into(), "main".into()),
+            Matcher::UrlEncoded("start".into(), "0".into()),
+        ]))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{
+            "path": {"toString": "src"},
+            "children": {
+                "values": [
+                    {"path": {"toString": "cmd"}, "type": "DIRECTORY"},
+                    {"path": {"toString": "main.rs"}, "type": "FILE", "size": 120}
+                ],
+                "isLastPage": true
+            }
+        }"#)
+        .create_async()
+        .await;
+
+    let ls_args = FileLsArgs {
+        path: "src".to_string(),
+        at: Some("main".to_string()),
+        recursive: false,
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&repository_args(), &ls_args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_ls_recursive_from_root() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("GET", "/rest/api/latest/projects/TEST/repos/repo/files")
+        .match_query(Matcher::UrlEncoded("start".into(), "0".into()))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"values": ["README.md", "src/main.rs"], "isLastPage": true}"#)
+        .create_async()
+        .await;
+
+    let ls_args = FileLsArgs {
+        path: "".to_string(),
+        at: None,
+        recursive: true,
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&repository_args(), &ls_args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
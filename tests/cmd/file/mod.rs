@@ -0,0 +1,4 @@
+mod blame_test;
+mod cat_test;
+mod ls_test;
+mod put_test;
@@ -0,0 +1,89 @@
+use bitbucket_server_cli::bitbucket::RepositoryArgs;
+use bitbucket_server_cli::cmd::file::put::{put, FilePutArgs};
+use bitbucket_server_cli::cmd::{Command, CommandError};
+use bitbucket_server_rs::client;
+use clap::{CommandFactory, Parser};
+use mockito::{Matcher, Server};
+
+fn repository_args() -> RepositoryArgs {
+    RepositoryArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: "repo".to_string(),
+    }
+}
+
+fn put_args() -> FilePutArgs {
+    FilePutArgs {
+        path: "VERSION".to_string(),
+        branch: "main".to_string(),
+        message: "Bump version to 1.3.0".to_string(),
+        source_commit: Some("abc123".to_string()),
+    }
+}
+
+#[tokio::test]
+async fn test_put_file_success() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("PUT", "/rest/api/latest/projects/TEST/repos/repo/browse/VERSION")
+        .match_header("content-type", Matcher::Regex("^multipart/form-data".to_string()))
+        .match_body(Matcher::AllOf(vec![
+            Matcher::Regex(r#"name="content"; filename="VERSION"\r\n(.*\r\n)*\r\n1\.3\.0\n"#.to_string()),
+            Matcher::Regex(r#"name="branch"\r\n\r\nmain\r\n"#.to_string()),
+            Matcher::Regex(r#"name="message"\r\n\r\nBump version to 1\.3\.0\r\n"#.to_string()),
+            Matcher::Regex(r#"name="sourceCommitId"\r\n\r\nabc123\r\n"#.to_string()),
+        ]))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"id": "def456", "displayId": "def456", "message": "Bump version to 1.3.0"}"#)
+        .create_async()
+        .await;
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = put(&repository_args(), &put_args(), b"1.3.0\n".to_vec(), &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_put_file_stale_source_commit() {
+    let mut server = Server::new_async().await;
+    let _m = server
+        .mock("PUT", "/rest/api/latest/projects/TEST/repos/repo/browse/VERSION")
+        .with_status(409)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"errors":[{"message":"The file was modified since the source commit"}]}"#)
+        .create_async()
+        .await;
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = put(&repository_args(), &put_args(), b"1.3.0\n".to_vec(), &client).await;
+    assert!(matches!(result, Err(CommandError::ArgumentError(_))));
+}
+
+#[test]
+fn test_file_put_command_line_args() {
+    let args = vec![
+        "bitbucket-server-cli",
+        "file",
+        "-k", "TEST",
+        "-r", "repo",
+        "put",
+        "VERSION",
+        "--branch", "main",
+        "--message", "Bump version",
+        "--source-commit", "abc123",
+    ];
+
+    Command::parse_from(args.clone());
+
+    let matches = Command::command().try_get_matches_from(args).expect("Failed to parse arguments");
+    let put_matches = matches
+        .subcommand_matches("file")
+        .and_then(|m| m.subcommand_matches("put"))
+        .expect("No file put subcommand");
+    assert_eq!(put_matches.get_one::<String>("path").map(|s| s.as_str()), Some("VERSION"));
+    assert_eq!(put_matches.get_one::<String>("branch").map(|s| s.as_str()), Some("main"));
+    assert_eq!(put_matches.get_one::<String>("message").map(|s| s.as_str()), Some("Bump version"));
+    assert_eq!(put_matches.get_one::<String>("source_commit").map(|s| s.as_str()), Some("abc123"));
+}
@@ -0,0 +1,109 @@
+use bitbucket_server_cli::bitbucket::CommitArgs;
+use bitbucket_server_cli::cmd::insights::annotations::{
+    add, delete, list, InsightsAnnotationsAddArgs, InsightsAnnotationsDeleteArgs,
+    InsightsAnnotationsListArgs,
+};
+use bitbucket_server_cli::cmd::insights::{AnnotationType, Severity};
+use bitbucket_server_rs::client;
+use mockito::{Matcher, Server};
+use serde_json::json;
+
+fn commit_args() -> CommitArgs {
+    CommitArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: "repo".to_string(),
+        commit_id: "abc123".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_add_annotation() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock(
+            "POST",
+            "/rest/insights/latest/projects/TEST/repos/repo/commits/abc123/reports/lint/annotations",
+        )
+        .match_body(Matcher::Json(json!({
+            "annotations": [{
+                "path": "src/main.rs",
+                "line": 12,
+                "message": "unused variable",
+                "severity": "LOW",
+                "type": "CODE_SMELL",
+                "externalId": "unused-12"
+            }]
+        })))
+        .with_status(204)
+        .create_async()
+        .await;
+
+    let add_args = InsightsAnnotationsAddArgs {
+        key: "lint".to_string(),
+        path: "src/main.rs".to_string(),
+        line: Some(12),
+        severity: Severity::Low,
+        annotation_type: Some(AnnotationType::CodeSmell),
+        message: "unused variable".to_string(),
+        link: None,
+        external_id: Some("unused-12".to_string()),
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = add(&commit_args(), &add_args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_list_annotations_of_every_report() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock(
+            "GET",
+            "/rest/insights/latest/projects/TEST/repos/repo/commits/abc123/annotations",
+        )
+        .match_query(Matcher::UrlEncoded("path".into(), "src/main.rs".into()))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"annotations": [{"reportKey": "lint", "path": "src/main.rs", "line": 12,
+                "message": "unused variable", "severity": "LOW"}], "totalCount": 1}"#,
+        )
+        .create_async()
+        .await;
+
+    let list_args = InsightsAnnotationsListArgs {
+        key: None,
+        path: Some("src/main.rs".to_string()),
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = list(&commit_args(), &list_args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_delete_annotations_by_external_id() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock(
+            "DELETE",
+            "/rest/insights/latest/projects/TEST/repos/repo/commits/abc123/reports/lint/annotations",
+        )
+        .match_query(Matcher::UrlEncoded("externalId".into(), "unused-12".into()))
+        .with_status(204)
+        .create_async()
+        .await;
+
+    let delete_args = InsightsAnnotationsDeleteArgs {
+        key: "lint".to_string(),
+        external_id: vec!["unused-12".to_string()],
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = delete(&commit_args(), &delete_args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
@@ -0,0 +1,273 @@
+use bitbucket_server_cli::bitbucket::CommitArgs;
+use bitbucket_server_cli::cmd::insights::coverage::{
+    cobertura, handle, lcov, InsightsCoverageArgs,
+};
+use bitbucket_server_cli::cmd::Command;
+use bitbucket_server_rs::client;
+use clap::CommandFactory;
+use mockito::{Matcher, Mock, Server, ServerGuard};
+use serde_json::json;
+use std::path::PathBuf;
+
+const LCOV: &str = "TN:
+SF:/work/app/src/lib.rs
+DA:1,4
+DA:2,0
+DA:3,1
+DA:4,0
+end_of_record
+SF:/work/app/src/main.rs
+DA:1,1
+end_of_record
+SF:/work/app/src/lib.rs
+DA:2,3
+end_of_record
+";
+
+const REPORT_URI: &str =
+    "/rest/insights/latest/projects/TEST/repos/repo/commits/abc123/reports/coverage";
+
+fn commit_args() -> CommitArgs {
+    CommitArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: "repo".to_string(),
+        commit_id: "abc123".to_string(),
+    }
+}
+
+fn write_report(name: &str, content: &str) -> PathBuf {
+    let file = std::env::temp_dir().join(format!("bitbucket-server-cli-{}", name));
+    std::fs::write(&file, content).expect("Failed to write coverage report");
+    file
+}
+
+fn coverage_args(lcov: PathBuf) -> InsightsCoverageArgs {
+    InsightsCoverageArgs {
+        lcov: Some(lcov),
+        cobertura: None,
+        key: "coverage".to_string(),
+        title: "Coverage".to_string(),
+        pull_request: None,
+        min_diff_coverage: None,
+        annotate_uncovered: false,
+        repo_root: Some(PathBuf::from("/work/app")),
+        link: None,
+        logo_url: None,
+    }
+}
+
+#[test]
+fn test_parse_lcov() {
+    let coverage = lcov::parse(LCOV).unwrap();
+
+    // the second record of lib.rs adds to the first
+    assert_eq!(coverage.files["/work/app/src/lib.rs"][&2], 3);
+    assert_eq!(coverage.total(), (4, 5));
+    assert!(lcov::parse("DA:1,1").is_err());
+    assert!(lcov::parse("SF:a.rs\nDA:x,1").is_err());
+}
+
+#[test]
+fn test_parse_cobertura() {
+    let content = r#"<?xml version="1.0" ?>
+<coverage line-rate="0.5">
+  <sources><source>/work/app</source></sources>
+  <packages><package name="app"><classes>
+    <class name="calc.py" filename="app/calc.py">
+      <methods><method name="add"><lines><line number="2" hits="1"/></lines></method></methods>
+      <lines>
+        <line number="1" hits="1"/>
+        <line number="2" hits="1"/>
+        <line number="5" hits="0"/>
+      </lines>
+    </class>
+  </classes></package></packages>
+</coverage>"#;
+
+    let coverage = cobertura::parse(content).unwrap();
+
+    assert_eq!(coverage.files["/work/app/app/calc.py"].len(), 3);
+    assert_eq!(coverage.total(), (2, 3));
+}
+
+#[tokio::test]
+async fn test_coverage_without_pull_request() {
+    let mut server = Server::new_async().await;
+    let report_mock = server
+        .mock("PUT", REPORT_URI)
+        .match_body(Matcher::Json(json!({
+            "title": "Coverage",
+            "details": "4 of 5 lines covered (80%).",
+            "data": [{"title": "Coverage", "type": "PERCENTAGE", "value": 80.0}]
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"key": "coverage"}"#)
+        .create_async()
+        .await;
+
+    let file = write_report("coverage-total.info", LCOV);
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&commit_args(), &coverage_args(file), &client).await;
+    assert!(result.is_ok());
+    report_mock.assert_async().await;
+}
+
+/// A pull request that changes `src/lib.rs`, adding the given lines
+async fn mock_pull_request(server: &mut ServerGuard, lines: &[u32]) -> Vec<Mock> {
+    let pull_request_uri = "/rest/api/latest/projects/TEST/repos/repo/pull-requests/7";
+    let changes_mock = server
+        .mock("GET", format!("{}/changes", pull_request_uri).as_str())
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({
+                "fromHash": "a",
+                "toHash": "b",
+                "values": [{
+                    "contentId": "c",
+                    "path": {"components": ["src", "lib.rs"], "name": "lib.rs", "toString": "src/lib.rs"},
+                    "executable": false,
+                    "percentUnchanged": 90,
+                    "type": "MODIFY",
+                    "nodeType": "FILE",
+                    "srcExecutable": false,
+                    "links": {}
+                }]
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+    let added: Vec<_> = lines
+        .iter()
+        .map(|line| json!({"source": 3, "destination": line}))
+        .collect();
+    let diff_mock = server
+        .mock(
+            "GET",
+            format!("{}/diff/src/lib.rs", pull_request_uri).as_str(),
+        )
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({"diffs": [{"hunks": [{"segments": [
+                {"type": "ADDED", "lines": added}
+            ]}]}]})
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    vec![changes_mock, diff_mock]
+}
+
+#[tokio::test]
+async fn test_coverage_fails_below_min_diff_coverage() {
+    let mut server = Server::new_async().await;
+    // line 9 is not instrumented, e.g. a comment
+    let pull_request_mocks = mock_pull_request(&mut server, &[3, 4, 9]).await;
+    let report_mock = server
+        .mock("PUT", REPORT_URI)
+        .match_body(Matcher::PartialJson(json!({
+            "result": "FAIL",
+            "details": "4 of 5 lines covered (80%). 1 of 2 changed lines covered (50%).",
+            "data": [
+                {"title": "Coverage", "type": "PERCENTAGE", "value": 80.0},
+                {"title": "Diff coverage", "type": "PERCENTAGE", "value": 50.0},
+                {"title": "Changed lines covered", "type": "TEXT", "value": "1/2"}
+            ]
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"key": "coverage"}"#)
+        .create_async()
+        .await;
+    let annotations_mock = server
+        .mock("POST", format!("{}/annotations", REPORT_URI).as_str())
+        .match_body(Matcher::PartialJson(json!({
+            "annotations": [
+                {"path": "src/lib.rs", "line": 4, "severity": "LOW",
+                 "externalId": "uncovered:src/lib.rs:4"}
+            ]
+        })))
+        .with_status(204)
+        .create_async()
+        .await;
+
+    let file = write_report("coverage-diff.info", LCOV);
+    let mut args = coverage_args(file);
+    args.pull_request = Some("7".to_string());
+    args.min_diff_coverage = Some(80.0);
+    args.annotate_uncovered = true;
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&commit_args(), &args, &client).await;
+    assert!(result.is_ok());
+    for mock in pull_request_mocks {
+        mock.assert_async().await;
+    }
+    report_mock.assert_async().await;
+    annotations_mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_coverage_compares_unrounded_diff_coverage() {
+    let mut server = Server::new_async().await;
+    let _pull_request = mock_pull_request(&mut server, &[1, 3, 4]).await;
+    // 2 of 3 is shown as 66.7%, but is still below it
+    let report_mock = server
+        .mock("PUT", REPORT_URI)
+        .match_body(Matcher::PartialJson(json!({
+            "result": "FAIL",
+            "details": "4 of 5 lines covered (80%). 2 of 3 changed lines covered (66.7%)."
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"key": "coverage"}"#)
+        .create_async()
+        .await;
+
+    let file = write_report("coverage-rounding.info", LCOV);
+    let mut args = coverage_args(file);
+    args.pull_request = Some("7".to_string());
+    args.min_diff_coverage = Some(66.7);
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&commit_args(), &args, &client).await;
+    assert!(result.is_ok());
+    report_mock.assert_async().await;
+}
+
+#[test]
+fn test_coverage_requires_a_report() {
+    fn args(extra: &[&'static str]) -> Vec<&'static str> {
+        let mut args = vec![
+            "bitbucket-server-cli",
+            "insights",
+            "-c",
+            "abc123",
+            "-r",
+            "repo",
+            "-k",
+            "TEST",
+            "coverage",
+        ];
+        args.extend_from_slice(extra);
+        args
+    }
+
+    assert!(Command::command().try_get_matches_from(args(&[])).is_err());
+    assert!(Command::command()
+        .try_get_matches_from(args(&["--lcov", "a.info", "--cobertura", "b.xml"]))
+        .is_err());
+    assert!(Command::command()
+        .try_get_matches_from(args(&["--lcov", "a.info", "--min-diff-coverage", "80"]))
+        .is_err());
+    assert!(Command::command()
+        .try_get_matches_from(args(&["--cobertura", "b.xml"]))
+        .is_ok());
+}
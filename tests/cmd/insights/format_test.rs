@@ -0,0 +1,109 @@
+use bitbucket_server_cli::cmd::insights::format::{checkstyle, clippy, eslint, junit};
+use bitbucket_server_cli::cmd::insights::{AnnotationType, Severity};
+
+#[test]
+fn test_parse_checkstyle() {
+    let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+<checkstyle version="8.0">
+  <file name="src/Main.java">
+    <error line="10" column="5" severity="error" message="Missing a Javadoc comment."
+           source="com.puppycrawl.tools.checkstyle.checks.javadoc.MissingJavadocMethodCheck"/>
+    <error line="12" severity="warning" message="Line is longer than 100 characters."/>
+    <error line="14" severity="ignore" message="Ignored"/>
+  </file>
+  <file name="src/Empty.java"/>
+</checkstyle>"#;
+
+    let findings = checkstyle::parse(content).unwrap();
+
+    assert_eq!(findings.annotations.len(), 2);
+    let javadoc = &findings.annotations[0];
+    assert_eq!(javadoc.path, "src/Main.java");
+    assert_eq!(javadoc.line, Some(10));
+    assert_eq!(javadoc.severity, Severity::High);
+    assert_eq!(javadoc.message, "Missing a Javadoc comment.");
+    assert_eq!(
+        javadoc.external_id.as_deref(),
+        Some("com.puppycrawl.tools.checkstyle.checks.javadoc.MissingJavadocMethodCheck")
+    );
+    assert_eq!(findings.annotations[1].severity, Severity::Medium);
+    assert!(checkstyle::parse("<checkstyle>").is_err());
+}
+
+#[test]
+fn test_parse_clippy() {
+    let message = r#"{"reason":"compiler-message","message":{"message":"this `if` has identical blocks","level":"warning","code":{"code":"clippy::if_same_then_else"},"spans":[{"file_name":"src/lib.rs","line_start":7,"is_primary":true}]}}"#;
+    let content = [
+        r#"{"reason":"compiler-artifact","target":{}}"#,
+        message,
+        // the same lint reported for the test target
+        message,
+        r#"{"reason":"compiler-message","message":{"message":"mismatched types","level":"error","code":{"code":"E0308"},"spans":[{"file_name":"src/main.rs","line_start":3,"is_primary":true}]}}"#,
+        r#"{"reason":"compiler-message","message":{"message":"2 warnings emitted","level":"warning","code":null,"spans":[]}}"#,
+        "    Finished `dev` profile",
+    ]
+    .join("\n");
+
+    let findings = clippy::parse(&content).unwrap();
+
+    assert_eq!(findings.annotations.len(), 2);
+    let lint = &findings.annotations[0];
+    assert_eq!(lint.path, "src/lib.rs");
+    assert_eq!(lint.line, Some(7));
+    assert_eq!(lint.severity, Severity::Medium);
+    assert_eq!(lint.annotation_type, Some(AnnotationType::CodeSmell));
+    assert_eq!(
+        lint.link.as_deref(),
+        Some("https://rust-lang.github.io/rust-clippy/master/index.html#if_same_then_else")
+    );
+    let error = &findings.annotations[1];
+    assert_eq!(error.severity, Severity::High);
+    assert_eq!(error.annotation_type, Some(AnnotationType::Bug));
+    assert_eq!(error.link, None);
+}
+
+#[test]
+fn test_parse_eslint() {
+    let content = r#"[
+  {"filePath": "/work/app/src/index.js", "messages": [
+    {"ruleId": "no-unused-vars", "severity": 2, "message": "'x' is defined but never used.", "line": 3},
+    {"ruleId": "react/prop-types", "severity": 1, "message": "'name' is missing in props validation", "line": 8}
+  ]},
+  {"filePath": "/work/app/src/clean.js", "messages": []}
+]"#;
+
+    let findings = eslint::parse(content).unwrap();
+
+    assert_eq!(findings.annotations.len(), 2);
+    let unused = &findings.annotations[0];
+    assert_eq!(unused.path, "/work/app/src/index.js");
+    assert_eq!(unused.severity, Severity::High);
+    assert_eq!(
+        unused.link.as_deref(),
+        Some("https://eslint.org/docs/latest/rules/no-unused-vars")
+    );
+    let props = &findings.annotations[1];
+    assert_eq!(props.severity, Severity::Medium);
+    assert_eq!(props.link, None);
+    assert_eq!(props.external_id.as_deref(), Some("react/prop-types"));
+}
+
+#[test]
+fn test_parse_junit() {
+    let content = r#"<testsuite name="unit">
+  <testcase classname="calc" name="adds"/>
+  <testcase classname="calc" name="divides">
+    <error>ZeroDivisionError
+  at calc.py:4</error>
+  </testcase>
+</testsuite>"#;
+
+    let findings = junit::parse(content).unwrap();
+
+    assert_eq!(findings.annotations.len(), 1);
+    let failure = &findings.annotations[0];
+    assert_eq!(failure.path, "");
+    assert_eq!(failure.line, None);
+    assert_eq!(failure.message, "calc.divides: ZeroDivisionError");
+    assert_eq!(failure.external_id.as_deref(), Some("calc.divides"));
+}
@@ -0,0 +1,337 @@
+use bitbucket_server_cli::bitbucket::CommitArgs;
+use bitbucket_server_cli::cmd::insights::format::{sarif, Format};
+use bitbucket_server_cli::cmd::insights::import::{handle, relative_path, InsightsImportArgs};
+use bitbucket_server_cli::cmd::insights::{AnnotationType, Severity};
+use bitbucket_server_rs::client;
+use mockito::{Matcher, Server};
+use serde_json::json;
+use std::path::{Path, PathBuf};
+
+const SARIF: &str = r#"{
+  "version": "2.1.0",
+  "runs": [{
+    "tool": {"driver": {"name": "semgrep", "rules": [
+      {"id": "sql-injection", "helpUri": "https://semgrep.dev/r/sql-injection",
+       "defaultConfiguration": {"level": "error"}, "properties": {"tags": ["security"]}},
+      {"id": "unused-import"}
+    ]}},
+    "originalUriBaseIds": {"SRCROOT": {"uri": "file:///work/app/"}},
+    "results": [
+      {"ruleId": "sql-injection", "ruleIndex": 0, "message": {"text": "Query built from user input"},
+       "locations": [{"physicalLocation": {"artifactLocation": {"uri": "src/db.py", "uriBaseId": "SRCROOT"},
+                                           "region": {"startLine": 42}}}]},
+      {"ruleId": "unused-import", "level": "note", "message": {"text": "os is never used"},
+       "locations": [{"physicalLocation": {"artifactLocation": {"uri": "file:///work/app/src/main.py"},
+                                           "region": {"startLine": 1}}}]},
+      {"ruleId": "unused-import", "level": "note", "message": {"text": "sys is never used"},
+       "locations": [{"physicalLocation": {"artifactLocation": {"uri": "file:///work/app/src/main.py"},
+                                           "region": {"startLine": 1}}}]},
+      {"ruleId": "unused-import", "message": {"text": "No location"}}
+    ]
+  }]
+}"#;
+
+fn commit_args() -> CommitArgs {
+    CommitArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: "repo".to_string(),
+        commit_id: "abc123".to_string(),
+    }
+}
+
+fn write_sarif(name: &str, content: &str) -> PathBuf {
+    let file = std::env::temp_dir().join(format!("bitbucket-server-cli-{}.sarif", name));
+    std::fs::write(&file, content).expect("Failed to write SARIF");
+    file
+}
+
+fn import_args(file: PathBuf, fail_on: Severity) -> InsightsImportArgs {
+    InsightsImportArgs {
+        file,
+        format: Format::Sarif,
+        key: "security-scan".to_string(),
+        title: None,
+        fail_on,
+        repo_root: Some(PathBuf::from("/work/app")),
+        changed_only: false,
+        pull_request: None,
+        link: None,
+        logo_url: None,
+    }
+}
+
+const REPORT_URI: &str =
+    "/rest/insights/latest/projects/TEST/repos/repo/commits/abc123/reports/security-scan";
+
+#[test]
+fn test_parse_sarif() {
+    let findings = sarif::parse(SARIF).unwrap();
+
+    assert_eq!(findings.tool.as_deref(), Some("semgrep"));
+    assert_eq!(findings.annotations.len(), 3);
+    let injection = &findings.annotations[0];
+    assert_eq!(injection.path, "file:///work/app/src/db.py");
+    assert_eq!(injection.line, Some(42));
+    assert_eq!(injection.severity, Severity::High);
+    assert_eq!(
+        injection.annotation_type,
+        Some(AnnotationType::Vulnerability)
+    );
+    assert_eq!(
+        injection.message,
+        "sql-injection: Query built from user input"
+    );
+    assert_eq!(
+        injection.link.as_deref(),
+        Some("https://semgrep.dev/r/sql-injection")
+    );
+    assert_eq!(findings.annotations[1].severity, Severity::Low);
+}
+
+#[test]
+fn test_relative_path() {
+    let cwd = Path::new("/work/app/web");
+    let root = Path::new("/work/app");
+
+    assert_eq!(
+        relative_path("file:///work/app/src/a%20b.rs", cwd, root),
+        "src/a b.rs"
+    );
+    assert_eq!(
+        relative_path("./src/index.js", cwd, root),
+        "web/src/index.js"
+    );
+    assert_eq!(relative_path("../lib/x.rs", cwd, root), "lib/x.rs");
+    assert_eq!(
+        relative_path("/elsewhere/x.rs", cwd, root),
+        "/elsewhere/x.rs"
+    );
+}
+
+#[tokio::test]
+async fn test_import_fails_on_high_severity() {
+    let mut server = Server::new_async().await;
+    let report_mock = server
+        .mock("PUT", REPORT_URI)
+        .match_body(Matcher::PartialJson(json!({
+            "title": "semgrep",
+            "result": "FAIL",
+            "reporter": "semgrep",
+            "data": [
+                {"title": "High", "type": "NUMBER", "value": 1},
+                {"title": "Medium", "type": "NUMBER", "value": 0},
+                {"title": "Low", "type": "NUMBER", "value": 2}
+            ]
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"key": "security-scan", "title": "semgrep", "result": "FAIL"}"#)
+        .create_async()
+        .await;
+    let annotations_mock = server
+        .mock("POST", format!("{}/annotations", REPORT_URI).as_str())
+        .match_body(Matcher::PartialJson(json!({
+            "annotations": [
+                {"path": "src/db.py", "line": 42, "severity": "HIGH",
+                 "externalId": "sql-injection:src/db.py:42"},
+                {"path": "src/main.py", "line": 1, "severity": "LOW",
+                 "externalId": "unused-import:src/main.py:1"},
+                {"path": "src/main.py", "line": 1, "severity": "LOW",
+                 "externalId": "unused-import:src/main.py:1#2"}
+            ]
+        })))
+        .with_status(204)
+        .create_async()
+        .await;
+
+    let file = write_sarif("import-fail", SARIF);
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&commit_args(), &import_args(file, Severity::High), &client).await;
+    assert!(result.is_ok());
+    report_mock.assert_async().await;
+    annotations_mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_import_batches_and_caps_annotations() {
+    let results: Vec<String> = (1..=1005)
+        .map(|line| {
+            format!(
+                r#"{{"ruleId": "r", "level": "warning", "message": {{"text": "m"}},
+                    "locations": [{{"physicalLocation": {{"artifactLocation": {{"uri": "src/a.rs"}},
+                    "region": {{"startLine": {}}}}}}}]}}"#,
+                line
+            )
+        })
+        .collect();
+    let sarif = format!(
+        r#"{{"version": "2.1.0", "runs": [{{"tool": {{"driver": {{"name": "lint"}}}}, "results": [{}]}}]}}"#,
+        results.join(",")
+    );
+
+    let mut server = Server::new_async().await;
+    let report_mock = server
+        .mock("PUT", REPORT_URI)
+        .match_body(Matcher::PartialJson(json!({
+            "result": "PASS",
+            "details": "1005 findings: 0 high, 1005 medium and 0 low severity. Only the 1000 most severe of 1005 findings are annotated."
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"key": "security-scan"}"#)
+        .create_async()
+        .await;
+    let annotations_mock = server
+        .mock("POST", format!("{}/annotations", REPORT_URI).as_str())
+        .with_status(204)
+        .expect(10)
+        .create_async()
+        .await;
+
+    let file = write_sarif("import-batches", &sarif);
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&commit_args(), &import_args(file, Severity::High), &client).await;
+    assert!(result.is_ok());
+    report_mock.assert_async().await;
+    annotations_mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_import_invalid_sarif() {
+    let file = write_sarif("import-invalid", "not json");
+
+    let client = client::new("http://localhost/rest", "token");
+    let result = handle(&commit_args(), &import_args(file, Severity::Low), &client).await;
+    assert!(result.is_err());
+}
+
+fn change(path: &str, change_type: &str) -> serde_json::Value {
+    json!({
+        "contentId": "c",
+        "path": {"components": path.split('/').collect::<Vec<_>>(), "name": path, "toString": path},
+        "executable": false,
+        "percentUnchanged": 90,
+        "type": change_type,
+        "nodeType": "FILE",
+        "srcExecutable": false,
+        "links": {}
+    })
+}
+
+#[tokio::test]
+async fn test_import_changed_only() {
+    let mut server = Server::new_async().await;
+    let pull_request_uri = "/rest/api/latest/projects/TEST/repos/repo/pull-requests/7";
+    let changes_mock = server
+        .mock("GET", format!("{}/changes", pull_request_uri).as_str())
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({
+                "fromHash": "a",
+                "toHash": "b",
+                "values": [change("src/db.py", "MODIFY"), change("src/old.py", "DELETE")]
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+    let diff_mock = server
+        .mock(
+            "GET",
+            format!("{}/diff/src/db.py", pull_request_uri).as_str(),
+        )
+        .match_query(Matcher::UrlEncoded("contextLines".into(), "0".into()))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({"diffs": [{"hunks": [{"segments": [
+                {"type": "REMOVED", "lines": [{"source": 41, "destination": 41}]},
+                {"type": "ADDED", "lines": [{"source": 42, "destination": 42}]}
+            ]}]}]})
+            .to_string(),
+        )
+        .create_async()
+        .await;
+    let report_mock = server
+        .mock("PUT", REPORT_URI)
+        .match_body(Matcher::PartialJson(json!({"result": "FAIL"})))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"key": "security-scan"}"#)
+        .create_async()
+        .await;
+    let annotations_mock = server
+        .mock("POST", format!("{}/annotations", REPORT_URI).as_str())
+        .match_body(Matcher::Json(json!({
+            "annotations": [
+                {"path": "src/db.py", "line": 42, "severity": "HIGH", "type": "VULNERABILITY",
+                 "message": "sql-injection: Query built from user input",
+                 "link": "https://semgrep.dev/r/sql-injection",
+                 "externalId": "sql-injection:src/db.py:42"}
+            ]
+        })))
+        .with_status(204)
+        .create_async()
+        .await;
+
+    let file = write_sarif("import-changed-only", SARIF);
+    let mut args = import_args(file, Severity::High);
+    args.changed_only = true;
+    args.pull_request = Some("7".to_string());
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&commit_args(), &args, &client).await;
+    assert!(result.is_ok());
+    changes_mock.assert_async().await;
+    diff_mock.assert_async().await;
+    report_mock.assert_async().await;
+    annotations_mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_import_junit() {
+    let junit = r#"<testsuites>
+  <testsuite name="api">
+    <testcase classname="api.UserTest" name="creates" file="tests/user_test.py" line="12"/>
+    <testcase classname="api.UserTest" name="deletes" file="tests/user_test.py" line="30">
+      <failure message="expected 204, got 500">trace</failure>
+    </testcase>
+  </testsuite>
+</testsuites>"#;
+    let mut server = Server::new_async().await;
+    let report_mock = server
+        .mock("PUT", REPORT_URI)
+        .match_body(Matcher::PartialJson(
+            json!({"title": "junit", "result": "FAIL"}),
+        ))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"key": "security-scan"}"#)
+        .create_async()
+        .await;
+    let annotations_mock = server
+        .mock("POST", format!("{}/annotations", REPORT_URI).as_str())
+        .match_body(Matcher::PartialJson(json!({
+            "annotations": [
+                {"path": "tests/user_test.py", "line": 30, "severity": "HIGH", "type": "BUG",
+                 "message": "api.UserTest.deletes: expected 204, got 500"}
+            ]
+        })))
+        .with_status(204)
+        .create_async()
+        .await;
+
+    let file = write_sarif("import-junit", junit);
+    let mut args = import_args(file, Severity::High);
+    args.format = Format::Junit;
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&commit_args(), &args, &client).await;
+    assert!(result.is_ok());
+    report_mock.assert_async().await;
+    annotations_mock.assert_async().await;
+}
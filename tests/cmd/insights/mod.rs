@@ -0,0 +1,5 @@
+mod annotations_test;
+mod coverage_test;
+mod format_test;
+mod import_test;
+mod report_test;
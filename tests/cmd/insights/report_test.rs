@@ -0,0 +1,109 @@
+use bitbucket_server_cli::bitbucket::CommitArgs;
+use bitbucket_server_cli::cmd::insights::report::{
+    delete, get, parse_data_field, put, InsightsReportKeyArgs, InsightsReportPutArgs,
+};
+use bitbucket_server_cli::cmd::insights::{DataType, ReportResult};
+use bitbucket_server_rs::client;
+use mockito::{Matcher, Server};
+use serde_json::json;
+
+fn commit_args() -> CommitArgs {
+    CommitArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: "repo".to_string(),
+        commit_id: "abc123".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_put_report() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock(
+            "PUT",
+            "/rest/insights/latest/projects/TEST/repos/repo/commits/abc123/reports/security-scan",
+        )
+        .match_body(Matcher::Json(json!({
+            "title": "Security scan",
+            "result": "FAIL",
+            "data": [
+                {"title": "Findings", "type": "NUMBER", "value": 3.0},
+                {"title": "Coverage", "type": "PERCENTAGE", "value": 81.5},
+                {"title": "Dashboard", "type": "LINK",
+                 "value": {"linktext": "https://scan.example.com", "href": "https://scan.example.com"}}
+            ],
+            "reporter": "scanner",
+            "logoUrl": "https://scan.example.com/logo.png"
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"key": "security-scan", "title": "Security scan", "result": "FAIL"}"#)
+        .create_async()
+        .await;
+
+    let put_args = InsightsReportPutArgs {
+        key: "security-scan".to_string(),
+        title: "Security scan".to_string(),
+        details: None,
+        result: Some(ReportResult::Fail),
+        data: vec![
+            parse_data_field("Findings=3").unwrap(),
+            parse_data_field("Coverage:percentage=81.5").unwrap(),
+            parse_data_field("Dashboard=https://scan.example.com").unwrap(),
+        ],
+        reporter: Some("scanner".to_string()),
+        link: None,
+        logo_url: Some("https://scan.example.com/logo.png".to_string()),
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = put(&commit_args(), &put_args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_get_and_delete_report() {
+    let mut server = Server::new_async().await;
+    let uri = "/rest/insights/latest/projects/TEST/repos/repo/commits/abc123/reports/lint";
+    let get_mock = server
+        .mock("GET", uri)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"key": "lint", "title": "Lint", "result": "PASS"}"#)
+        .create_async()
+        .await;
+    let delete_mock = server
+        .mock("DELETE", uri)
+        .with_status(204)
+        .create_async()
+        .await;
+
+    let key_args = InsightsReportKeyArgs {
+        key: "lint".to_string(),
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    assert!(get(&commit_args(), &key_args, &client).await.is_ok());
+    assert!(delete(&commit_args(), &key_args, &client).await.is_ok());
+    get_mock.assert_async().await;
+    delete_mock.assert_async().await;
+}
+
+#[test]
+fn test_parse_data_field() {
+    let field = parse_data_field("Passed=true").unwrap();
+    assert_eq!(field.data_type, DataType::Boolean);
+
+    let field = parse_data_field("Scanner=semgrep 1.2").unwrap();
+    assert_eq!(field.data_type, DataType::Text);
+    assert_eq!(field.value, json!("semgrep 1.2"));
+
+    let field = parse_data_field("Build time:duration=65000").unwrap();
+    assert_eq!(field.title, "Build time");
+    assert_eq!(field.value, json!(65000));
+
+    assert!(parse_data_field("no value").is_err());
+    assert!(parse_data_field("Coverage:percent=80").is_err());
+    assert!(parse_data_field("Findings:number=many").is_err());
+}
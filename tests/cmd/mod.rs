@@ -1,2 +1,15 @@
+mod access_token;
+mod api_test;
+mod auth;
+mod branch;
+mod project;
 mod pull_request_changes;
+mod repo;
 mod build_status;
+mod file;
+mod tag;
+mod permissions;
+mod branch_restrictions;
+mod default_reviewers;
+mod webhook;
+mod insights;
@@ -0,0 +1,99 @@
+use bitbucket_server_cli::cmd::permissions::audit::{audit, AuditRow};
+use bitbucket_server_rs::client;
+use mockito::{Matcher, Server, ServerGuard};
+
+async fn mock_get(server: &mut ServerGuard, path: &str, body: &str) {
+    server
+        .mock("GET", path)
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(body)
+        .create_async()
+        .await;
+}
+
+fn row(
+    scope: &'static str,
+    repository: &str,
+    principal_type: &'static str,
+    principal: &str,
+    via_group: &str,
+    permission: &str,
+) -> AuditRow {
+    AuditRow {
+        scope,
+        repository: repository.to_string(),
+        principal_type,
+        principal: principal.to_string(),
+        via_group: via_group.to_string(),
+        permission: permission.to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_audit_project() {
+    let mut server = Server::new_async().await;
+    let empty = r#"{"values": [], "isLastPage": true}"#;
+
+    mock_get(
+        &mut server,
+        "/rest/api/latest/projects/TEST/permissions/PROJECT_READ/all",
+        r#"{"permitted": true}"#,
+    )
+    .await;
+    mock_get(
+        &mut server,
+        "/rest/api/latest/projects/TEST/permissions/PROJECT_WRITE/all",
+        r#"{"permitted": false}"#,
+    )
+    .await;
+    mock_get(
+        &mut server,
+        "/rest/api/latest/projects/TEST/permissions/users",
+        r#"{"values": [{"user": {"name": "admin"}, "permission": "PROJECT_ADMIN"}], "isLastPage": true}"#,
+    )
+    .await;
+    mock_get(&mut server, "/rest/api/latest/projects/TEST/permissions/groups", empty).await;
+    mock_get(
+        &mut server,
+        "/rest/api/latest/projects/TEST/repos",
+        r#"{"values": [{"id": 1, "slug": "repo", "name": "repo", "scmId": "git", "public": true, "project": {"key": "TEST"}}], "isLastPage": true}"#,
+    )
+    .await;
+    mock_get(
+        &mut server,
+        "/rest/api/latest/projects/TEST/repos/repo/permissions/users",
+        empty,
+    )
+    .await;
+    mock_get(
+        &mut server,
+        "/rest/api/latest/projects/TEST/repos/repo/permissions/groups",
+        r#"{"values": [{"group": {"name": "developers"}, "permission": "REPO_WRITE"}], "isLastPage": true}"#,
+    )
+    .await;
+    let members = server
+        .mock("GET", "/rest/api/latest/admin/groups/more-members")
+        .match_query(Matcher::UrlEncoded("context".into(), "developers".into()))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"values": [{"name": "jdoe"}], "isLastPage": true}"#)
+        .create_async()
+        .await;
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let rows = audit("TEST", true, &client).await.expect("audit failed");
+
+    assert_eq!(
+        rows,
+        vec![
+            row("PROJECT", "", "DEFAULT", "all-users", "", "PROJECT_READ"),
+            row("PROJECT", "", "USER", "admin", "", "PROJECT_ADMIN"),
+            row("REPOSITORY", "repo", "PUBLIC", "anonymous", "", "REPO_READ"),
+            row("REPOSITORY", "repo", "USER", "jdoe", "developers", "REPO_WRITE"),
+            row("REPOSITORY", "repo", "GROUP", "developers", "", "REPO_WRITE"),
+        ]
+    );
+    members.assert_async().await;
+}
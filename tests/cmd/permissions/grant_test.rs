@@ -0,0 +1,98 @@
+use bitbucket_server_cli::bitbucket::ScopeArgs;
+use bitbucket_server_cli::cmd::permissions::grant::{handle, PermissionsGrantArgs};
+use bitbucket_server_cli::cmd::permissions::{Permission, PrincipalArgs};
+use bitbucket_server_cli::cmd::Command;
+use bitbucket_server_rs::client;
+use clap::CommandFactory;
+use mockito::{Matcher, Server};
+
+#[tokio::test]
+async fn test_grant_repository_permission_to_group() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("PUT", "/rest/api/latest/projects/TEST/repos/repo/permissions/groups")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("name".into(), "developers".into()),
+            Matcher::UrlEncoded("permission".into(), "REPO_WRITE".into()),
+        ]))
+        .with_status(204)
+        .create_async()
+        .await;
+
+    let scope = ScopeArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: Some("repo".to_string()),
+    };
+    let grant_args = PermissionsGrantArgs {
+        principal: PrincipalArgs {
+            user: None,
+            group: Some("developers".to_string()),
+        },
+        permission: Permission::Write,
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&scope, &grant_args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_grant_project_permission_to_user() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("PUT", "/rest/api/latest/projects/TEST/permissions/users")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("name".into(), "jdoe".into()),
+            Matcher::UrlEncoded("permission".into(), "PROJECT_ADMIN".into()),
+        ]))
+        .with_status(204)
+        .create_async()
+        .await;
+
+    let scope = ScopeArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: None,
+    };
+    let grant_args = PermissionsGrantArgs {
+        principal: PrincipalArgs {
+            user: Some("jdoe".to_string()),
+            group: None,
+        },
+        permission: Permission::Admin,
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&scope, &grant_args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[test]
+fn test_grant_requires_exactly_one_principal() {
+    let both = Command::command().try_get_matches_from(vec![
+        "bitbucket-server-cli",
+        "permissions",
+        "-k",
+        "TEST",
+        "grant",
+        "--user",
+        "jdoe",
+        "--group",
+        "developers",
+        "--permission",
+        "read",
+    ]);
+    assert!(both.is_err());
+
+    let neither = Command::command().try_get_matches_from(vec![
+        "bitbucket-server-cli",
+        "permissions",
+        "-k",
+        "TEST",
+        "grant",
+        "--permission",
+        "read",
+    ]);
+    assert!(neither.is_err());
+}
@@ -0,0 +1,60 @@
+use bitbucket_server_cli::bitbucket::ScopeArgs;
+use bitbucket_server_cli::cmd::permissions::list::handle;
+use bitbucket_server_rs::client;
+use mockito::{Matcher, Server};
+
+#[tokio::test]
+async fn test_list_repository_permissions() {
+    let mut server = Server::new_async().await;
+    let users = server
+        .mock("GET", "/rest/api/latest/projects/TEST/repos/repo/permissions/users")
+        .match_query(Matcher::UrlEncoded("start".into(), "0".into()))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"values": [{"user": {"name": "jdoe", "displayName": "Jane Doe"}, "permission": "REPO_WRITE"}], "isLastPage": true}"#,
+        )
+        .create_async()
+        .await;
+    let groups = server
+        .mock("GET", "/rest/api/latest/projects/TEST/repos/repo/permissions/groups")
+        .match_query(Matcher::UrlEncoded("start".into(), "0".into()))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"values": [{"group": {"name": "developers"}, "permission": "REPO_READ"}], "isLastPage": true}"#,
+        )
+        .create_async()
+        .await;
+
+    let scope = ScopeArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: Some("repo".to_string()),
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&scope, &client).await;
+    assert!(result.is_ok());
+    users.assert_async().await;
+    groups.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_list_project_permissions_unauthorized() {
+    let mut server = Server::new_async().await;
+    let _users = server
+        .mock("GET", "/rest/api/latest/projects/TEST/permissions/users")
+        .match_query(Matcher::Any)
+        .with_status(401)
+        .create_async()
+        .await;
+
+    let scope = ScopeArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: None,
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&scope, &client).await;
+    assert!(result.is_err());
+}
@@ -0,0 +1,5 @@
+mod audit_test;
+mod grant_test;
+mod list_test;
+mod revoke_test;
+mod set_default_test;
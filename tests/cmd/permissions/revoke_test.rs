@@ -0,0 +1,32 @@
+use bitbucket_server_cli::bitbucket::ScopeArgs;
+use bitbucket_server_cli::cmd::permissions::revoke::{handle, PermissionsRevokeArgs};
+use bitbucket_server_cli::cmd::permissions::PrincipalArgs;
+use bitbucket_server_rs::client;
+use mockito::{Matcher, Server};
+
+#[tokio::test]
+async fn test_revoke_user_permission() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("DELETE", "/rest/api/latest/projects/TEST/repos/repo/permissions/users")
+        .match_query(Matcher::UrlEncoded("name".into(), "jdoe".into()))
+        .with_status(204)
+        .create_async()
+        .await;
+
+    let scope = ScopeArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: Some("repo".to_string()),
+    };
+    let revoke_args = PermissionsRevokeArgs {
+        principal: PrincipalArgs {
+            user: Some("jdoe".to_string()),
+            group: None,
+        },
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&scope, &revoke_args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
@@ -0,0 +1,53 @@
+use bitbucket_server_cli::bitbucket::ScopeArgs;
+use bitbucket_server_cli::cmd::permissions::set_default::{
+    handle, DefaultPermission, PermissionsSetDefaultArgs,
+};
+use bitbucket_server_cli::cmd::CommandError;
+use bitbucket_server_rs::client;
+use mockito::{Matcher, Server};
+
+#[tokio::test]
+async fn test_set_default_read() {
+    let mut server = Server::new_async().await;
+    let write = server
+        .mock("POST", "/rest/api/latest/projects/TEST/permissions/PROJECT_WRITE/all")
+        .match_query(Matcher::UrlEncoded("allow".into(), "false".into()))
+        .with_status(204)
+        .create_async()
+        .await;
+    let read = server
+        .mock("POST", "/rest/api/latest/projects/TEST/permissions/PROJECT_READ/all")
+        .match_query(Matcher::UrlEncoded("allow".into(), "true".into()))
+        .with_status(204)
+        .create_async()
+        .await;
+
+    let scope = ScopeArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: None,
+    };
+    let set_default_args = PermissionsSetDefaultArgs {
+        permission: DefaultPermission::Read,
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&scope, &set_default_args, &client).await;
+    assert!(result.is_ok());
+    write.assert_async().await;
+    read.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_set_default_rejects_repository_scope() {
+    let scope = ScopeArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: Some("repo".to_string()),
+    };
+    let set_default_args = PermissionsSetDefaultArgs {
+        permission: DefaultPermission::Write,
+    };
+
+    let client = client::new("http://localhost/rest", "token");
+    let result = handle(&scope, &set_default_args, &client).await;
+    assert!(matches!(result, Err(CommandError::ArgumentError(_))));
+}
@@ -0,0 +1,64 @@
+use bitbucket_server_cli::bitbucket::ProjectArgs;
+use bitbucket_server_cli::cmd::project::avatar::{set, ProjectAvatarSetArgs};
+use bitbucket_server_cli::cmd::{Command, CommandError};
+use bitbucket_server_rs::client;
+use clap::{CommandFactory, Parser};
+use mockito::{Matcher, Server};
+use std::path::PathBuf;
+
+#[tokio::test]
+async fn test_set_project_avatar() {
+    let file = std::env::temp_dir().join("bitbucket-server-cli-avatar-test.png");
+    std::fs::write(&file, b"\x89PNG").unwrap();
+
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/rest/api/latest/projects/PAY/avatar.png")
+        .match_header("x-atlassian-token", "no-check")
+        .match_body(Matcher::Regex(r#"name="avatar"; filename="bitbucket-server-cli-avatar-test.png""#.to_string()))
+        .with_status(201)
+        .create_async()
+        .await;
+
+    let set_args = ProjectAvatarSetArgs {
+        project_args: ProjectArgs {
+            project_key: "PAY".to_string(),
+        },
+        file,
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = set(&set_args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_set_project_avatar_missing_file() {
+    let set_args = ProjectAvatarSetArgs {
+        project_args: ProjectArgs {
+            project_key: "PAY".to_string(),
+        },
+        file: PathBuf::from("/nonexistent/logo.png"),
+    };
+
+    let client = client::new("http://localhost/rest", "token");
+    let result = set(&set_args, &client).await;
+    assert!(matches!(result, Err(CommandError::ArgumentError(_))));
+}
+
+#[test]
+fn test_project_avatar_set_command_line_args() {
+    let args = vec!["bitbucket-server-cli", "project", "avatar", "set", "-k", "PAY", "logo.png"];
+
+    Command::parse_from(args.clone());
+
+    let matches = Command::command().try_get_matches_from(args).expect("Failed to parse arguments");
+    let set_matches = matches
+        .subcommand_matches("project")
+        .and_then(|m| m.subcommand_matches("avatar"))
+        .and_then(|m| m.subcommand_matches("set"))
+        .expect("No project avatar set subcommand");
+    assert_eq!(set_matches.get_one::<String>("project_key").map(|s| s.as_str()), Some("PAY"));
+    assert_eq!(set_matches.get_one::<PathBuf>("file"), Some(&PathBuf::from("logo.png")));
+}
@@ -0,0 +1,49 @@
+use bitbucket_server_cli::cmd::project::create::{handle, ProjectCreateArgs};
+use bitbucket_server_cli::cmd::Command;
+use bitbucket_server_rs::client;
+use clap::{CommandFactory, Parser};
+use mockito::{Matcher, Server};
+
+#[tokio::test]
+async fn test_create_project() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/rest/api/latest/projects")
+        .match_body(Matcher::Json(serde_json::json!({
+            "key": "PAY",
+            "name": "Payments",
+            "description": "Payments squad",
+        })))
+        .with_status(201)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"id": 1, "key": "PAY", "name": "Payments", "description": "Payments squad", "public": false, "type": "NORMAL"}"#)
+        .create_async()
+        .await;
+
+    let create_args = ProjectCreateArgs {
+        project_key: "PAY".to_string(),
+        name: "Payments".to_string(),
+        description: Some("Payments squad".to_string()),
+        public: None,
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&create_args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[test]
+fn test_project_create_command_line_args() {
+    let args = vec!["bitbucket-server-cli", "project", "create", "-k", "PAY", "--name", "Payments"];
+
+    Command::parse_from(args.clone());
+
+    let matches = Command::command().try_get_matches_from(args).expect("Failed to parse arguments");
+    let create_matches = matches
+        .subcommand_matches("project")
+        .and_then(|m| m.subcommand_matches("create"))
+        .expect("No project create subcommand");
+    assert_eq!(create_matches.get_one::<String>("project_key").map(|s| s.as_str()), Some("PAY"));
+    assert_eq!(create_matches.get_one::<String>("name").map(|s| s.as_str()), Some("Payments"));
+}
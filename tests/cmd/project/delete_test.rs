@@ -0,0 +1,48 @@
+use bitbucket_server_cli::bitbucket::ProjectArgs;
+use bitbucket_server_cli::cmd::project::delete::{handle, ProjectDeleteArgs};
+use bitbucket_server_rs::client;
+use mockito::Server;
+
+#[tokio::test]
+async fn test_delete_project_with_yes() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("DELETE", "/rest/api/latest/projects/PAY")
+        .with_status(204)
+        .create_async()
+        .await;
+
+    let delete_args = ProjectDeleteArgs {
+        project_args: ProjectArgs {
+            project_key: "PAY".to_string(),
+        },
+        yes: true,
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&delete_args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_delete_project_with_repositories() {
+    let mut server = Server::new_async().await;
+    let _m = server
+        .mock("DELETE", "/rest/api/latest/projects/PAY")
+        .with_status(409)
+        .with_body(r#"{"errors":[{"message":"The project PAY cannot be deleted because it has repositories."}]}"#)
+        .create_async()
+        .await;
+
+    let delete_args = ProjectDeleteArgs {
+        project_args: ProjectArgs {
+            project_key: "PAY".to_string(),
+        },
+        yes: true,
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&delete_args, &client).await;
+    assert!(result.is_err());
+}
@@ -0,0 +1,42 @@
+use bitbucket_server_cli::bitbucket::ProjectArgs;
+use bitbucket_server_cli::cmd::project::get::handle;
+use bitbucket_server_rs::client;
+use mockito::Server;
+
+#[tokio::test]
+async fn test_get_project_success() {
+    let mut server = Server::new_async().await;
+    let _m = server
+        .mock("GET", "/rest/api/latest/projects/PAY")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"id": 1, "key": "PAY", "name": "Payments", "description": "Payments squad", "public": false, "type": "NORMAL"}"#)
+        .create_async()
+        .await;
+
+    let project_args = ProjectArgs {
+        project_key: "PAY".to_string(),
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&project_args, &client).await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_get_project_unauthorized() {
+    let mut server = Server::new_async().await;
+    let _m = server
+        .mock("GET", "/rest/api/latest/projects/PAY")
+        .with_status(401)
+        .create_async()
+        .await;
+
+    let project_args = ProjectArgs {
+        project_key: "PAY".to_string(),
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&project_args, &client).await;
+    assert!(result.is_err());
+}
@@ -0,0 +1,49 @@
+use bitbucket_server_cli::cmd::project::list::{handle, ProjectListArgs, ProjectPermission};
+use bitbucket_server_cli::cmd::Command;
+use bitbucket_server_rs::client;
+use clap::{CommandFactory, Parser};
+use mockito::{Matcher, Server};
+
+#[tokio::test]
+async fn test_list_projects_with_filters() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("GET", "/rest/api/latest/projects")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("name".into(), "pay".into()),
+            Matcher::UrlEncoded("permission".into(), "PROJECT_ADMIN".into()),
+        ]))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"values": [{"id": 1, "key": "PAY", "name": "Payments", "public": false, "type": "NORMAL"}], "isLastPage": true}"#)
+        .create_async()
+        .await;
+
+    let list_args = ProjectListArgs {
+        name: Some("pay".to_string()),
+        permission: Some(ProjectPermission::ProjectAdmin),
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&list_args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[test]
+fn test_project_list_command_line_args() {
+    let args = vec!["bitbucket-server-cli", "project", "list", "--name", "pay", "--permission", "project-write"];
+
+    Command::parse_from(args.clone());
+
+    let matches = Command::command().try_get_matches_from(args).expect("Failed to parse arguments");
+    let list_matches = matches
+        .subcommand_matches("project")
+        .and_then(|m| m.subcommand_matches("list"))
+        .expect("No project list subcommand");
+    assert_eq!(list_matches.get_one::<String>("name").map(|s| s.as_str()), Some("pay"));
+    assert_eq!(
+        list_matches.get_one::<ProjectPermission>("permission").copied(),
+        Some(ProjectPermission::ProjectWrite)
+    );
+}
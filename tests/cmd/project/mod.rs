@@ -0,0 +1,6 @@
+mod avatar_test;
+mod create_test;
+mod delete_test;
+mod get_test;
+mod list_test;
+mod update_test;
@@ -0,0 +1,32 @@
+use bitbucket_server_cli::bitbucket::ProjectArgs;
+use bitbucket_server_cli::cmd::project::update::{handle, ProjectUpdateArgs};
+use bitbucket_server_rs::client;
+use mockito::{Matcher, Server};
+
+#[tokio::test]
+async fn test_update_project_only_sends_given_fields() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("PUT", "/rest/api/latest/projects/PAY")
+        .match_body(Matcher::Json(serde_json::json!({"key": "PAYMENTS", "public": true})))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"id": 1, "key": "PAYMENTS", "name": "Payments", "public": true, "type": "NORMAL"}"#)
+        .create_async()
+        .await;
+
+    let update_args = ProjectUpdateArgs {
+        project_args: ProjectArgs {
+            project_key: "PAY".to_string(),
+        },
+        new_key: Some("PAYMENTS".to_string()),
+        name: None,
+        description: None,
+        public: Some(true),
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&update_args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
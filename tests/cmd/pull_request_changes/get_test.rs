@@ -175,8 +175,6 @@ async fn test_pull_request_changes_command_line_args() {
     assert_eq!(pr_changes_matches.get_one::<u32>("limit").copied(), Some(100));
     assert_eq!(pr_changes_matches.get_one::<bool>("with_comments").copied(), Some(true));
     
-    // Final assertion
-    assert!(true, "Command line arguments were parsed correctly");
 }
 
 #[tokio::test]
@@ -219,6 +217,4 @@ async fn test_pull_request_changes_command_line_args_minimal() {
     assert_eq!(pr_changes_matches.get_one::<u32>("limit"), None);
     assert_eq!(pr_changes_matches.get_one::<bool>("with_comments"), None);
     
-    // Final assertion
-    assert!(true, "Command line arguments were parsed correctly");
 }
@@ -0,0 +1,334 @@
+use bitbucket_server_cli::bitbucket::{MatcherType, RefMatcher};
+use bitbucket_server_cli::cmd::permissions::Permission;
+use bitbucket_server_cli::cmd::repo::apply::{handle, RepoApplyArgs};
+use bitbucket_server_cli::cmd::repo::manifest::plan::{plan, summary, Action};
+use bitbucket_server_cli::cmd::repo::manifest::remote::{Operation, Remote};
+use bitbucket_server_cli::cmd::repo::manifest::{
+    BranchRestriction, Hook, Manifest, MergeChecks, Permissions, RestrictionType, Webhook,
+};
+use bitbucket_server_cli::cmd::CommandError;
+use bitbucket_server_rs::client;
+use mockito::{Matcher, Mock, Server, ServerGuard};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+const MANIFEST: &str = r#"
+project: TEST
+repository: repo
+defaultBranch: main
+branchRestrictions:
+  - type: no-deletes
+    matcher: { type: branch, id: main }
+webhooks:
+  - name: jenkins
+    url: https://ci.example.com/hook
+    events: [repo:refs_changed, pr:opened]
+"#;
+
+fn write_manifest(name: &str, content: &str) -> PathBuf {
+    let file = std::env::temp_dir().join(format!("bitbucket-server-cli-{}.yaml", name));
+    std::fs::write(&file, content).expect("Failed to write manifest");
+    file
+}
+
+async fn mock_get(server: &mut ServerGuard, path: &str, body: &str) -> Mock {
+    server
+        .mock("GET", path)
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(body)
+        .create_async()
+        .await
+}
+
+/// The server state: default branch `develop`, no restrictions and an outdated webhook
+async fn mock_current_state(server: &mut ServerGuard, default_branch: &str) -> Vec<Mock> {
+    vec![
+        mock_get(
+            server,
+            "/rest/api/latest/projects/TEST/repos/repo/default-branch",
+            &format!(
+                r#"{{"id": "refs/heads/{0}", "displayId": "{0}", "type": "BRANCH"}}"#,
+                default_branch
+            ),
+        )
+        .await,
+        mock_get(
+            server,
+            "/rest/branch-permissions/latest/projects/TEST/repos/repo/restrictions",
+            r#"{"values": [], "isLastPage": true}"#,
+        )
+        .await,
+        mock_get(
+            server,
+            "/rest/api/latest/projects/TEST/repos/repo/webhooks",
+            r#"{"values": [{"id": 7, "name": "jenkins", "url": "https://old.example.com/hook", "events": ["pr:opened", "repo:refs_changed"], "active": true}], "isLastPage": true}"#,
+        )
+        .await,
+    ]
+}
+
+#[tokio::test]
+async fn test_apply_detect_drift() {
+    let mut server = Server::new_async().await;
+    let _state = mock_current_state(&mut server, "develop").await;
+
+    let apply_args = RepoApplyArgs {
+        file: write_manifest("apply-drift", MANIFEST),
+        auto_approve: false,
+        detect_drift: true,
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&apply_args, &client).await;
+    assert!(matches!(result, Err(CommandError::DriftDetected(_))));
+}
+
+#[tokio::test]
+async fn test_apply_auto_approve() {
+    let mut server = Server::new_async().await;
+    let _state = mock_current_state(&mut server, "develop").await;
+    let set_default_branch = server
+        .mock("PUT", "/rest/api/latest/projects/TEST/repos/repo/default-branch")
+        .match_body(Matcher::Json(serde_json::json!({"id": "refs/heads/main"})))
+        .with_status(204)
+        .create_async()
+        .await;
+    let create_restriction = server
+        .mock("POST", "/rest/branch-permissions/latest/projects/TEST/repos/repo/restrictions")
+        .match_body(Matcher::PartialJson(serde_json::json!({
+            "type": "no-deletes",
+            "matcher": {"id": "refs/heads/main", "type": {"id": "BRANCH"}}
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"id": 1, "type": "no-deletes", "matcher": {"id": "refs/heads/main", "type": {"id": "BRANCH"}}}"#,
+        )
+        .create_async()
+        .await;
+    let _webhook = mock_get(
+        &mut server,
+        "/rest/api/latest/projects/TEST/repos/repo/webhooks/7",
+        r#"{"id": 7, "name": "jenkins", "url": "https://old.example.com/hook", "events": ["pr:opened", "repo:refs_changed"], "active": true}"#,
+    )
+    .await;
+    let update_webhook = server
+        .mock("PUT", "/rest/api/latest/projects/TEST/repos/repo/webhooks/7")
+        .match_body(Matcher::PartialJson(serde_json::json!({
+            "url": "https://ci.example.com/hook"
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"id": 7, "name": "jenkins", "url": "https://ci.example.com/hook",
+                "events": ["pr:opened", "repo:refs_changed"], "active": true}"#,
+        )
+        .create_async()
+        .await;
+
+    let apply_args = RepoApplyArgs {
+        file: write_manifest("apply-auto-approve", MANIFEST),
+        auto_approve: true,
+        detect_drift: false,
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&apply_args, &client).await;
+    assert!(result.is_ok());
+    set_default_branch.assert_async().await;
+    create_restriction.assert_async().await;
+    update_webhook.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_apply_no_changes() {
+    let mut server = Server::new_async().await;
+    let _state = mock_current_state(&mut server, "main").await;
+    let manifest = r#"
+project: TEST
+repository: repo
+defaultBranch: refs/heads/main
+"#;
+
+    let apply_args = RepoApplyArgs {
+        file: write_manifest("apply-no-changes", manifest),
+        auto_approve: false,
+        detect_drift: true,
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&apply_args, &client).await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_apply_restriction_without_scope_is_not_drift() {
+    let mut server = Server::new_async().await;
+    let _default_branch = mock_get(
+        &mut server,
+        "/rest/api/latest/projects/TEST/repos/repo/default-branch",
+        r#"{"id": "refs/heads/main", "displayId": "main", "type": "BRANCH"}"#,
+    )
+    .await;
+    // older servers leave out the scope of settings defined on the repository
+    let _restrictions = mock_get(
+        &mut server,
+        "/rest/branch-permissions/latest/projects/TEST/repos/repo/restrictions",
+        r#"{"values": [{"id": 1, "type": "no-deletes", "matcher": {"id": "refs/heads/main", "type": {"id": "BRANCH"}}}], "isLastPage": true}"#,
+    )
+    .await;
+    let _webhooks = mock_get(
+        &mut server,
+        "/rest/api/latest/projects/TEST/repos/repo/webhooks",
+        r#"{"values": [{"id": 7, "name": "jenkins", "url": "https://ci.example.com/hook", "events": ["pr:opened", "repo:refs_changed"], "active": true}], "isLastPage": true}"#,
+    )
+    .await;
+
+    let apply_args = RepoApplyArgs {
+        file: write_manifest("apply-no-scope", MANIFEST),
+        auto_approve: false,
+        detect_drift: true,
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&apply_args, &client).await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_apply_invalid_manifest() {
+    let apply_args = RepoApplyArgs {
+        file: write_manifest("apply-invalid", "project: TEST\nrepository: repo\nunknown: true\n"),
+        auto_approve: true,
+        detect_drift: false,
+    };
+
+    let client = client::new("http://localhost/rest", "token");
+    let result = handle(&apply_args, &client).await;
+    assert!(matches!(result, Err(CommandError::ArgumentError(_))));
+}
+
+#[test]
+fn test_plan_replaces_changed_restrictions_and_removes_unlisted_settings() {
+    let main = RefMatcher::new(MatcherType::Branch, "main");
+    let restriction = |users: &[&str]| BranchRestriction {
+        restriction_type: RestrictionType::ReadOnly,
+        matcher: main.clone(),
+        users: users.iter().map(|u| u.to_string()).collect(),
+        groups: vec![],
+        access_keys: vec![],
+    };
+    let desired = Manifest {
+        project: "TEST".to_string(),
+        repository: "repo".to_string(),
+        branch_restrictions: Some(vec![restriction(&["release-bot"])]),
+        webhooks: Some(vec![]),
+        permissions: Some(Permissions {
+            users: BTreeMap::from([("jdoe".to_string(), Permission::Write)]),
+            groups: BTreeMap::new(),
+        }),
+        ..Manifest::default()
+    };
+    let remote = Remote {
+        branch_restrictions: Some(vec![(3, restriction(&[]))]),
+        webhooks: Some(vec![(
+            9,
+            Webhook {
+                name: "old".to_string(),
+                url: "https://old.example.com".to_string(),
+                events: vec![],
+                active: true,
+            },
+        )]),
+        permissions: Some(Permissions {
+            users: BTreeMap::from([("jdoe".to_string(), Permission::Read)]),
+            groups: BTreeMap::from([("contractors".to_string(), Permission::Read)]),
+        }),
+        ..Remote::default()
+    };
+
+    let changes = plan(&desired, &remote);
+
+    let actions: Vec<Action> = changes.iter().map(|c| c.action).collect();
+    assert_eq!(
+        actions,
+        vec![Action::Update, Action::Delete, Action::Update, Action::Delete]
+    );
+    assert_eq!(
+        changes[0].operations,
+        vec![
+            Operation::DeleteBranchRestriction(3),
+            Operation::CreateBranchRestriction(restriction(&["release-bot"])),
+        ]
+    );
+    assert_eq!(changes[1].operations, vec![Operation::DeleteWebhook(9)]);
+    assert_eq!(
+        changes[2].to_string(),
+        "~ user permission jdoe: read -> write"
+    );
+    assert_eq!(
+        summary(&changes),
+        "Plan: 0 to add, 2 to change, 2 to destroy."
+    );
+
+    // sections left out of the manifest are not managed
+    let unmanaged = Manifest {
+        project: "TEST".to_string(),
+        repository: "repo".to_string(),
+        ..Manifest::default()
+    };
+    assert!(plan(&unmanaged, &remote).is_empty());
+}
+
+#[test]
+fn test_plan_hooks_and_merge_checks() {
+    let hook = |enabled, settings: Option<serde_json::Value>| Hook { enabled, settings };
+    let desired = Manifest {
+        project: "TEST".to_string(),
+        repository: "repo".to_string(),
+        merge_checks: Some(MergeChecks {
+            required_approvers: Some(2),
+            ..MergeChecks::default()
+        }),
+        hooks: Some(BTreeMap::from([
+            ("verify-committer".to_string(), hook(true, None)),
+            (
+                "jira-issue".to_string(),
+                hook(true, Some(serde_json::json!({"keys": 1}))),
+            ),
+        ])),
+        ..Manifest::default()
+    };
+    let remote = Remote {
+        merge_checks: Some(MergeChecks {
+            required_approvers: Some(1),
+            required_all_tasks_complete: Some(true),
+            ..MergeChecks::default()
+        }),
+        hooks: Some(BTreeMap::from([
+            (
+                "jira-issue".to_string(),
+                hook(true, Some(serde_json::json!({"keys": 2}))),
+            ),
+            ("force-push".to_string(), hook(true, None)),
+        ])),
+        ..Remote::default()
+    };
+
+    let changes: Vec<String> = plan(&desired, &remote)
+        .iter()
+        .map(|change| change.to_string())
+        .collect();
+
+    assert_eq!(
+        changes,
+        vec![
+            "~ merge checks (required approvers: [1] -> [2])",
+            "~ hook jira-issue (settings)",
+            "+ hook verify-committer",
+            "- hook force-push",
+        ]
+    );
+}
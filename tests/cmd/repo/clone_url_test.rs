@@ -0,0 +1,52 @@
+use bitbucket_server_cli::bitbucket::RepositoryArgs;
+use bitbucket_server_cli::cmd::repo::clone_url::{handle, CloneProtocol, RepoCloneUrlArgs};
+use bitbucket_server_cli::cmd::CommandError;
+use bitbucket_server_rs::client;
+use mockito::Server;
+
+fn clone_url_args(protocol: CloneProtocol) -> RepoCloneUrlArgs {
+    RepoCloneUrlArgs {
+        repository_args: RepositoryArgs {
+            project_key: "TEST".to_string(),
+            repository_slug: "repo".to_string(),
+        },
+        protocol,
+    }
+}
+
+#[tokio::test]
+async fn test_clone_url() {
+    let mut server = Server::new_async().await;
+    let _m = server
+        .mock("GET", "/rest/api/latest/projects/TEST/repos/repo")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"id": 1, "slug": "repo", "name": "repo", "scmId": "git", "project": {"key": "TEST"},
+            "links": {"clone": [
+                {"href": "https://bitbucket.example.com/scm/test/repo.git", "name": "http"},
+                {"href": "ssh://git@bitbucket.example.com:7999/test/repo.git", "name": "ssh"}
+            ]}}"#)
+        .create_async()
+        .await;
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    assert!(handle(&clone_url_args(CloneProtocol::Ssh), &client).await.is_ok());
+    assert!(handle(&clone_url_args(CloneProtocol::Http), &client).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_clone_url_missing_protocol() {
+    let mut server = Server::new_async().await;
+    let _m = server
+        .mock("GET", "/rest/api/latest/projects/TEST/repos/repo")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"id": 1, "slug": "repo", "name": "repo", "scmId": "git", "project": {"key": "TEST"},
+            "links": {"clone": [{"href": "https://bitbucket.example.com/scm/test/repo.git", "name": "http"}]}}"#)
+        .create_async()
+        .await;
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&clone_url_args(CloneProtocol::Ssh), &client).await;
+    assert!(matches!(result, Err(CommandError::UnexpectedError(_))));
+}
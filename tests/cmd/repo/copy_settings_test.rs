@@ -0,0 +1,149 @@
+use bitbucket_server_cli::bitbucket::RepositoryArgs;
+use bitbucket_server_cli::cmd::repo::copy_settings::{handle, RepoCopySettingsArgs};
+use bitbucket_server_cli::cmd::repo::manifest::Section;
+use bitbucket_server_cli::cmd::{Command, CommandError};
+use bitbucket_server_rs::client;
+use clap::CommandFactory;
+use mockito::{Matcher, Server, ServerGuard};
+
+fn repository(project_key: &str, repository_slug: &str) -> RepositoryArgs {
+    RepositoryArgs {
+        project_key: project_key.to_string(),
+        repository_slug: repository_slug.to_string(),
+    }
+}
+
+async fn mock_webhooks(server: &mut ServerGuard, repository_slug: &str, status: usize, body: &str) {
+    server
+        .mock(
+            "GET",
+            format!("/rest/api/latest/projects/TEST/repos/{}/webhooks", repository_slug).as_str(),
+        )
+        .match_query(Matcher::Any)
+        .with_status(status)
+        .with_header("content-type", "application/json")
+        .with_body(body)
+        .create_async()
+        .await;
+}
+
+#[tokio::test]
+async fn test_copy_settings_reports_failing_target_and_continues() {
+    let mut server = Server::new_async().await;
+    mock_webhooks(
+        &mut server,
+        "template",
+        200,
+        r#"{"values": [{"id": 1, "name": "jenkins", "url": "https://ci.example.com", "events": ["repo:refs_changed"], "active": true}], "isLastPage": true}"#,
+    )
+    .await;
+    mock_webhooks(&mut server, "new-service", 200, r#"{"values": [], "isLastPage": true}"#).await;
+    mock_webhooks(&mut server, "missing", 404, r#"{"errors": []}"#).await;
+    mock_webhooks(&mut server, "locked", 403, r#"{"errors": []}"#).await;
+    let create = server
+        .mock("POST", "/rest/api/latest/projects/TEST/repos/new-service/webhooks")
+        .match_body(Matcher::PartialJson(serde_json::json!({
+            "name": "jenkins",
+            "url": "https://ci.example.com"
+        })))
+        .with_status(201)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"id": 5, "name": "jenkins", "url": "https://ci.example.com", "events": ["repo:refs_changed"], "active": true}"#)
+        .create_async()
+        .await;
+
+    let copy_args = RepoCopySettingsArgs {
+        from: repository("TEST", "template"),
+        to: vec![
+            repository("TEST", "missing"),
+            repository("TEST", "new-service"),
+            repository("TEST", "locked"),
+        ],
+        section: vec![Section::Webhooks],
+        dry_run: false,
+        parallelism: 2,
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&copy_args, &client).await;
+    let Err(CommandError::UnexpectedError(message)) = result else {
+        panic!("expected the failing targets, got {:?}", result);
+    };
+    let failed: Vec<_> = message.lines().map(|line| line.split(':').next()).collect();
+    assert_eq!(failed, vec![Some("TEST/missing"), Some("TEST/locked")]);
+    create.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_copy_settings_dry_run() {
+    let mut server = Server::new_async().await;
+    mock_webhooks(
+        &mut server,
+        "template",
+        200,
+        r#"{"values": [{"id": 1, "name": "jenkins", "url": "https://ci.example.com", "events": [], "active": true}], "isLastPage": true}"#,
+    )
+    .await;
+    mock_webhooks(&mut server, "new-service", 200, r#"{"values": [], "isLastPage": true}"#).await;
+    let create = server
+        .mock("POST", "/rest/api/latest/projects/TEST/repos/new-service/webhooks")
+        .expect(0)
+        .create_async()
+        .await;
+
+    let copy_args = RepoCopySettingsArgs {
+        from: repository("TEST", "template"),
+        to: vec![repository("TEST", "new-service")],
+        section: vec![Section::Webhooks],
+        dry_run: true,
+        parallelism: 4,
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&copy_args, &client).await;
+    assert!(result.is_ok());
+    create.assert_async().await;
+}
+
+#[test]
+fn test_copy_settings_command_line_args() {
+    let matches = Command::command()
+        .try_get_matches_from(vec![
+            "bitbucket-server-cli",
+            "repo",
+            "copy-settings",
+            "--from",
+            "PROJ/template",
+            "--to",
+            "PROJ/a",
+            "--to",
+            "OTHER/b",
+            "--section",
+            "branch-restrictions",
+            "--section",
+            "webhooks",
+            "--dry-run",
+        ])
+        .expect("Failed to parse arguments");
+    let copy_matches = matches
+        .subcommand_matches("repo")
+        .and_then(|m| m.subcommand_matches("copy-settings"))
+        .expect("No copy-settings subcommand");
+    let targets: Vec<String> = copy_matches
+        .get_many::<RepositoryArgs>("to")
+        .expect("No targets")
+        .map(|target| format!("{}/{}", target.project_key, target.repository_slug))
+        .collect();
+    assert_eq!(targets, vec!["PROJ/a", "OTHER/b"]);
+
+    let invalid = Command::command().try_get_matches_from(vec![
+        "bitbucket-server-cli",
+        "repo",
+        "copy-settings",
+        "--from",
+        "template",
+        "--to",
+        "PROJ/a",
+    ]);
+    assert!(invalid.is_err());
+}
@@ -0,0 +1,63 @@
+use bitbucket_server_cli::cmd::repo::create::{handle, RepoCreateArgs};
+use bitbucket_server_cli::cmd::Command;
+use bitbucket_server_rs::client;
+use clap::{CommandFactory, Parser};
+use mockito::{Matcher, Server};
+
+#[tokio::test]
+async fn test_create_repository() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/rest/api/latest/projects/TEST/repos")
+        .match_body(Matcher::Json(serde_json::json!({
+            "name": "New Service",
+            "scmId": "git",
+            "forkable": false,
+            "defaultBranch": "main",
+        })))
+        .with_status(201)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"id": 3, "slug": "new-service", "name": "New Service", "scmId": "git", "forkable": false, "project": {"key": "TEST"}}"#)
+        .create_async()
+        .await;
+
+    let create_args = RepoCreateArgs {
+        project_key: "TEST".to_string(),
+        name: "New Service".to_string(),
+        scm: "git".to_string(),
+        forkable: Some(false),
+        default_branch: Some("main".to_string()),
+        public: None,
+        description: None,
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&create_args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[test]
+fn test_repo_create_command_line_args() {
+    let args = vec![
+        "bitbucket-server-cli",
+        "repo",
+        "create",
+        "-k", "TEST",
+        "New Service",
+        "--forkable", "false",
+        "--public", "true",
+    ];
+
+    Command::parse_from(args.clone());
+
+    let matches = Command::command().try_get_matches_from(args).expect("Failed to parse arguments");
+    let create_matches = matches
+        .subcommand_matches("repo")
+        .and_then(|m| m.subcommand_matches("create"))
+        .expect("No repo create subcommand");
+    assert_eq!(create_matches.get_one::<String>("name").map(|s| s.as_str()), Some("New Service"));
+    assert_eq!(create_matches.get_one::<String>("scm").map(|s| s.as_str()), Some("git"));
+    assert_eq!(create_matches.get_one::<bool>("forkable").copied(), Some(false));
+    assert_eq!(create_matches.get_one::<bool>("public").copied(), Some(true));
+}
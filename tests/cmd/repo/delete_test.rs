@@ -0,0 +1,45 @@
+use bitbucket_server_cli::bitbucket::RepositoryArgs;
+use bitbucket_server_cli::cmd::repo::delete::{handle, RepoDeleteArgs};
+use bitbucket_server_cli::cmd::Command;
+use bitbucket_server_rs::client;
+use clap::{CommandFactory, Parser};
+use mockito::Server;
+
+#[tokio::test]
+async fn test_delete_repository_with_yes() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("DELETE", "/rest/api/latest/projects/TEST/repos/repo")
+        .with_status(202)
+        .create_async()
+        .await;
+
+    let delete_args = RepoDeleteArgs {
+        repository_args: RepositoryArgs {
+            project_key: "TEST".to_string(),
+            repository_slug: "repo".to_string(),
+        },
+        yes: true,
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&delete_args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[test]
+fn test_repo_delete_command_line_args() {
+    let args = vec!["bitbucket-server-cli", "repo", "delete", "-k", "TEST", "-r", "repo", "--yes"];
+
+    Command::parse_from(args.clone());
+
+    let matches = Command::command().try_get_matches_from(args).expect("Failed to parse arguments");
+    let delete_matches = matches
+        .subcommand_matches("repo")
+        .and_then(|m| m.subcommand_matches("delete"))
+        .expect("No repo delete subcommand");
+    assert_eq!(delete_matches.get_one::<String>("project_key").map(|s| s.as_str()), Some("TEST"));
+    assert_eq!(delete_matches.get_one::<String>("repository_slug").map(|s| s.as_str()), Some("repo"));
+    assert_eq!(delete_matches.get_one::<bool>("yes").copied(), Some(true));
+}
@@ -0,0 +1,109 @@
+use bitbucket_server_cli::cmd::repo::export::{handle, ExportFormat, RepoExportArgs};
+use bitbucket_server_cli::cmd::repo::manifest::Section;
+use bitbucket_server_cli::cmd::{Command, CommandError};
+use bitbucket_server_rs::client;
+use clap::CommandFactory;
+use mockito::{Matcher, Server};
+
+#[tokio::test]
+async fn test_export_project_writes_one_sorted_file_per_repository() {
+    let mut server = Server::new_async().await;
+    let _repos = server
+        .mock("GET", "/rest/api/latest/projects/TEST/repos")
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"values": [
+                {"id": 1, "slug": "api", "name": "api", "scmId": "git", "project": {"key": "TEST"}},
+                {"id": 2, "slug": "web", "name": "web", "scmId": "git", "project": {"key": "TEST"}}
+            ], "isLastPage": true}"#,
+        )
+        .create_async()
+        .await;
+    let _api_webhooks = server
+        .mock("GET", "/rest/api/latest/projects/TEST/repos/api/webhooks")
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"values": [
+                {"id": 2, "name": "slack", "url": "https://slack.example.com", "events": ["pr:opened"], "active": false},
+                {"id": 1, "name": "jenkins", "url": "https://ci.example.com", "events": ["repo:refs_changed", "pr:merged"], "active": true}
+            ], "isLastPage": true}"#,
+        )
+        .create_async()
+        .await;
+    let _web_webhooks = server
+        .mock("GET", "/rest/api/latest/projects/TEST/repos/web/webhooks")
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"values": [], "isLastPage": true}"#)
+        .create_async()
+        .await;
+
+    let output_dir = std::env::temp_dir().join("bitbucket-server-cli-export-test");
+    let export_args = RepoExportArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: None,
+        format: ExportFormat::Yaml,
+        output_dir: Some(output_dir.clone()),
+        section: vec![Section::Webhooks],
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&export_args, &client).await;
+    assert!(result.is_ok());
+
+    let api = std::fs::read_to_string(output_dir.join("api.yaml")).expect("api.yaml missing");
+    assert_eq!(
+        api,
+        "project: TEST
+repository: api
+webhooks:
+- name: jenkins
+  url: https://ci.example.com
+  events:
+  - pr:merged
+  - repo:refs_changed
+  active: true
+- name: slack
+  url: https://slack.example.com
+  events:
+  - pr:opened
+  active: false
+"
+    );
+    let web = std::fs::read_to_string(output_dir.join("web.yaml")).expect("web.yaml missing");
+    assert_eq!(web, "project: TEST\nrepository: web\nwebhooks: []\n");
+}
+
+#[tokio::test]
+async fn test_export_project_requires_output_dir() {
+    let export_args = RepoExportArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: None,
+        format: ExportFormat::Json,
+        output_dir: None,
+        section: vec![],
+    };
+
+    let client = client::new("http://localhost/rest", "token");
+    let result = handle(&export_args, &client).await;
+    assert!(matches!(result, Err(CommandError::ArgumentError(_))));
+}
+
+#[test]
+fn test_export_format_requires_output_dir() {
+    let stdout = Command::command().try_get_matches_from(vec![
+        "bitbucket-server-cli", "repo", "export", "-k", "TEST", "-r", "repo", "--format", "json",
+    ]);
+    assert!(stdout.is_err());
+
+    let files = Command::command().try_get_matches_from(vec![
+        "bitbucket-server-cli", "repo", "export", "-k", "TEST", "-r", "repo", "--format", "json",
+        "--output-dir", "exported",
+    ]);
+    assert!(files.is_ok());
+}
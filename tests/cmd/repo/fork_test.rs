@@ -0,0 +1,31 @@
+use bitbucket_server_cli::bitbucket::RepositoryArgs;
+use bitbucket_server_cli::cmd::repo::fork::{handle, RepoForkArgs};
+use bitbucket_server_rs::client;
+use mockito::{Matcher, Server};
+
+#[tokio::test]
+async fn test_fork_into_target_project() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/rest/api/latest/projects/TEST/repos/repo")
+        .match_body(Matcher::Json(serde_json::json!({"project": {"key": "TEAM"}})))
+        .with_status(201)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"id": 4, "slug": "repo", "name": "repo", "scmId": "git", "project": {"key": "TEAM"}, "origin": {"slug": "repo", "project": {"key": "TEST"}}}"#)
+        .create_async()
+        .await;
+
+    let fork_args = RepoForkArgs {
+        repository_args: RepositoryArgs {
+            project_key: "TEST".to_string(),
+            repository_slug: "repo".to_string(),
+        },
+        target_project: Some("TEAM".to_string()),
+        name: None,
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&fork_args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
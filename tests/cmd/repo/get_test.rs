@@ -0,0 +1,45 @@
+use bitbucket_server_cli::bitbucket::RepositoryArgs;
+use bitbucket_server_cli::cmd::repo::get::handle;
+use bitbucket_server_rs::client;
+use mockito::Server;
+
+#[tokio::test]
+async fn test_get_repository_success() {
+    let mut server = Server::new_async().await;
+    let _m = server
+        .mock("GET", "/rest/api/latest/projects/TEST/repos/repo")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"id": 1, "slug": "repo", "name": "Repo", "scmId": "git", "state": "AVAILABLE", "forkable": true, "public": false, "project": {"key": "TEST", "id": 1, "name": "Test", "type": "NORMAL"}}"#)
+        .create_async()
+        .await;
+
+    let repository_args = RepositoryArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: "repo".to_string(),
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&repository_args, &client).await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_get_repository_not_found() {
+    let mut server = Server::new_async().await;
+    let _m = server
+        .mock("GET", "/rest/api/latest/projects/TEST/repos/missing")
+        .with_status(404)
+        .with_body(r#"{"errors":[{"message":"Repository TEST/missing does not exist."}]}"#)
+        .create_async()
+        .await;
+
+    let repository_args = RepositoryArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: "missing".to_string(),
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&repository_args, &client).await;
+    assert!(result.is_err());
+}
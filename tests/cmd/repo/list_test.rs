@@ -0,0 +1,88 @@
+use bitbucket_server_cli::cmd::repo::list::{handle, RepoListArgs, RepositoryPermission};
+use bitbucket_server_cli::cmd::Command;
+use bitbucket_server_rs::client;
+use clap::{CommandFactory, Parser};
+use mockito::{Matcher, Server};
+
+const REPOSITORIES: &str = r#"{
+    "values": [
+        {"id": 1, "slug": "api", "name": "API", "scmId": "git", "forkable": true, "project": {"key": "TEST"}},
+        {"id": 2, "slug": "web", "name": "Web", "scmId": "git", "forkable": true, "project": {"key": "OTHER"}}
+    ],
+    "isLastPage": true
+}"#;
+
+#[tokio::test]
+async fn test_list_project_repositories() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("GET", "/rest/api/latest/projects/TEST/repos")
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(REPOSITORIES)
+        .create_async()
+        .await;
+
+    let list_args = RepoListArgs {
+        project_key: Some("TEST".to_string()),
+        name: Some("ap".to_string()),
+        permission: None,
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&list_args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_list_visible_repositories_with_permission() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("GET", "/rest/api/latest/repos")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("name".into(), "api".into()),
+            Matcher::UrlEncoded("permission".into(), "REPO_ADMIN".into()),
+        ]))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(REPOSITORIES)
+        .create_async()
+        .await;
+
+    let list_args = RepoListArgs {
+        project_key: None,
+        name: Some("api".to_string()),
+        permission: Some(RepositoryPermission::RepoAdmin),
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&list_args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[test]
+fn test_repo_list_command_line_args() {
+    let args = vec![
+        "bitbucket-server-cli",
+        "repo",
+        "list",
+        "--project-key", "TEST",
+        "--permission", "repo-write",
+    ];
+
+    Command::parse_from(args.clone());
+
+    let matches = Command::command().try_get_matches_from(args).expect("Failed to parse arguments");
+    let list_matches = matches
+        .subcommand_matches("repo")
+        .and_then(|m| m.subcommand_matches("list"))
+        .expect("No repo list subcommand");
+    assert_eq!(list_matches.get_one::<String>("project_key").map(|s| s.as_str()), Some("TEST"));
+    assert_eq!(
+        list_matches.get_one::<RepositoryPermission>("permission").copied(),
+        Some(RepositoryPermission::RepoWrite)
+    );
+}
@@ -0,0 +1,10 @@
+mod apply_test;
+mod clone_url_test;
+mod copy_settings_test;
+mod create_test;
+mod delete_test;
+mod export_test;
+mod fork_test;
+mod get_test;
+mod list_test;
+mod update_test;
@@ -0,0 +1,34 @@
+use bitbucket_server_cli::bitbucket::RepositoryArgs;
+use bitbucket_server_cli::cmd::repo::update::{handle, RepoUpdateArgs};
+use bitbucket_server_rs::client;
+use mockito::{Matcher, Server};
+
+#[tokio::test]
+async fn test_update_repository_only_sends_given_fields() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("PUT", "/rest/api/latest/projects/TEST/repos/repo")
+        .match_body(Matcher::Json(serde_json::json!({"description": "Payments API"})))
+        .with_status(201)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"id": 1, "slug": "repo", "name": "Repo", "scmId": "git", "description": "Payments API", "project": {"key": "TEST"}}"#)
+        .create_async()
+        .await;
+
+    let update_args = RepoUpdateArgs {
+        repository_args: RepositoryArgs {
+            project_key: "TEST".to_string(),
+            repository_slug: "repo".to_string(),
+        },
+        name: None,
+        forkable: None,
+        default_branch: None,
+        public: None,
+        description: Some("Payments API".to_string()),
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&update_args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
@@ -0,0 +1,63 @@
+use bitbucket_server_cli::bitbucket::RepositoryArgs;
+use bitbucket_server_cli::cmd::tag::create::{handle, TagCreateArgs};
+use bitbucket_server_cli::cmd::Command;
+use bitbucket_server_rs::client;
+use clap::{CommandFactory, Parser};
+use mockito::{Matcher, Server};
+
+#[tokio::test]
+async fn test_create_annotated_tag() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/rest/api/latest/projects/TEST/repos/repo/tags")
+        .match_body(Matcher::Json(serde_json::json!({
+            "name": "v1.2.0",
+            "startPoint": "abc123",
+            "message": "Release 1.2.0",
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"id": "refs/tags/v1.2.0", "displayId": "v1.2.0", "type": "TAG", "latestCommit": "abc123", "hash": "def456"}"#)
+        .create_async()
+        .await;
+
+    let repository_args = RepositoryArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: "repo".to_string(),
+    };
+    let create_args = TagCreateArgs {
+        name: "v1.2.0".to_string(),
+        commit_id: "abc123".to_string(),
+        message: Some("Release 1.2.0".to_string()),
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&repository_args, &create_args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[test]
+fn test_tag_create_command_line_args() {
+    let args = vec![
+        "bitbucket-server-cli",
+        "tag",
+        "-k", "TEST",
+        "-r", "repo",
+        "create",
+        "v1.2.0",
+        "--commit-id", "abc123",
+        "--message", "Release 1.2.0",
+    ];
+
+    Command::parse_from(args.clone());
+
+    let matches = Command::command().try_get_matches_from(args).expect("Failed to parse arguments");
+    let create_matches = matches
+        .subcommand_matches("tag")
+        .and_then(|m| m.subcommand_matches("create"))
+        .expect("No tag create subcommand");
+    assert_eq!(create_matches.get_one::<String>("name").map(|s| s.as_str()), Some("v1.2.0"));
+    assert_eq!(create_matches.get_one::<String>("commit_id").map(|s| s.as_str()), Some("abc123"));
+    assert_eq!(create_matches.get_one::<String>("message").map(|s| s.as_str()), Some("Release 1.2.0"));
+}
@@ -0,0 +1,50 @@
+use bitbucket_server_cli::bitbucket::RepositoryArgs;
+use bitbucket_server_cli::cmd::tag::delete::{handle, TagDeleteArgs};
+use bitbucket_server_rs::client;
+use mockito::Server;
+
+#[tokio::test]
+async fn test_delete_tag_success() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("DELETE", "/rest/git/latest/projects/TEST/repos/repo/tags/v1.0")
+        .with_status(204)
+        .create_async()
+        .await;
+
+    let repository_args = RepositoryArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: "repo".to_string(),
+    };
+    let delete_args = TagDeleteArgs {
+        name: "v1.0".to_string(),
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&repository_args, &delete_args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_delete_tag_encodes_name() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("DELETE", "/rest/git/latest/projects/TEST/repos/repo/tags/v1%232%3F%25")
+        .with_status(204)
+        .create_async()
+        .await;
+
+    let repository_args = RepositoryArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: "repo".to_string(),
+    };
+    let delete_args = TagDeleteArgs {
+        name: "v1#2?%".to_string(),
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&repository_args, &delete_args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
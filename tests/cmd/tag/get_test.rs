@@ -0,0 +1,77 @@
+use bitbucket_server_cli::bitbucket::RepositoryArgs;
+use bitbucket_server_cli::cmd::tag::get::{handle, TagGetArgs};
+use bitbucket_server_rs::client;
+use mockito::Server;
+
+#[tokio::test]
+async fn test_get_tag_success() {
+    let mut server = Server::new_async().await;
+    let _m = server
+        .mock("GET", "/rest/api/latest/projects/TEST/repos/repo/tags/v1.0")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"id": "refs/tags/v1.0", "displayId": "v1.0", "type": "TAG", "latestCommit": "abc123", "hash": "def456"}"#)
+        .create_async()
+        .await;
+
+    let repository_args = RepositoryArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: "repo".to_string(),
+    };
+    let get_args = TagGetArgs {
+        name: "v1.0".to_string(),
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&repository_args, &get_args, &client).await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn test_get_tag_not_found() {
+    let mut server = Server::new_async().await;
+    let _m = server
+        .mock("GET", "/rest/api/latest/projects/TEST/repos/repo/tags/v9.9")
+        .with_status(404)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"errors":[{"message":"Tag v9.9 does not exist"}]}"#)
+        .create_async()
+        .await;
+
+    let repository_args = RepositoryArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: "repo".to_string(),
+    };
+    let get_args = TagGetArgs {
+        name: "v9.9".to_string(),
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&repository_args, &get_args, &client).await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_get_tag_encodes_name() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("GET", "/rest/api/latest/projects/TEST/repos/repo/tags/release%2B1%20rc%231")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"id": "refs/tags/release+1 rc#1", "displayId": "release+1 rc#1", "type": "TAG", "latestCommit": "abc123"}"#)
+        .create_async()
+        .await;
+
+    let repository_args = RepositoryArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: "repo".to_string(),
+    };
+    let get_args = TagGetArgs {
+        name: "release+1 rc#1".to_string(),
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&repository_args, &get_args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
@@ -0,0 +1,66 @@
+use bitbucket_server_cli::bitbucket::{RefOrder, RepositoryArgs};
+use bitbucket_server_cli::cmd::tag::list::{handle, TagListArgs};
+use bitbucket_server_cli::cmd::Command;
+use bitbucket_server_rs::client;
+use clap::{CommandFactory, Parser};
+use mockito::{Matcher, Server};
+
+#[tokio::test]
+async fn test_list_tags_with_options() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("GET", "/rest/api/latest/projects/TEST/repos/repo/tags")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("filterText".into(), "v1.".into()),
+            Matcher::UrlEncoded("orderBy".into(), "ALPHABETICAL".into()),
+        ]))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{
+            "values": [
+                {"id": "refs/tags/v1.0", "displayId": "v1.0", "type": "TAG", "latestCommit": "abc123"},
+                {"id": "refs/tags/rc-v1.0", "displayId": "rc-v1.0", "type": "TAG", "latestCommit": "abc123"}
+            ],
+            "isLastPage": true
+        }"#)
+        .create_async()
+        .await;
+
+    let repository_args = RepositoryArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: "repo".to_string(),
+    };
+    let list_args = TagListArgs {
+        filter: Some("v1.".to_string()),
+        order_by: Some(RefOrder::Alphabetical),
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&repository_args, &list_args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[test]
+fn test_tag_list_command_line_args() {
+    let args = vec![
+        "bitbucket-server-cli",
+        "tag",
+        "--project-key", "TEST",
+        "--repository-slug", "repo",
+        "list",
+        "--filter", "v1.",
+        "--order-by", "alphabetical",
+    ];
+
+    Command::parse_from(args.clone());
+
+    let matches = Command::command().try_get_matches_from(args).expect("Failed to parse arguments");
+    let tag_matches = matches.subcommand_matches("tag").expect("No tag subcommand");
+    assert_eq!(tag_matches.get_one::<String>("project_key").map(|s| s.as_str()), Some("TEST"));
+    assert_eq!(tag_matches.get_one::<String>("repository_slug").map(|s| s.as_str()), Some("repo"));
+
+    let list_matches = tag_matches.subcommand_matches("list").expect("No list subcommand");
+    assert_eq!(list_matches.get_one::<String>("filter").map(|s| s.as_str()), Some("v1."));
+    assert_eq!(list_matches.get_one::<RefOrder>("order_by").copied(), Some(RefOrder::Alphabetical));
+}
@@ -0,0 +1,90 @@
+use bitbucket_server_cli::bitbucket::ScopeArgs;
+use bitbucket_server_cli::cmd::webhook::create::{handle, WebhookCreateArgs};
+use bitbucket_server_cli::cmd::Command;
+use bitbucket_server_rs::client;
+use clap::CommandFactory;
+use mockito::{Matcher, Server};
+
+const CREATED: &str = r#"{"id": 5, "name": "jenkins", "url": "https://ci.example.com/hook",
+    "events": ["pr:opened", "repo:refs_changed"], "active": true, "scopeType": "repository"}"#;
+
+#[tokio::test]
+async fn test_create_webhook_with_secret() {
+    std::env::set_var("BITBUCKET_SERVER_CLI_TEST_WEBHOOK_SECRET", "s3cret");
+
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", "/rest/api/latest/projects/TEST/repos/repo/webhooks")
+        .match_body(Matcher::Json(serde_json::json!({
+            "name": "jenkins",
+            "url": "https://ci.example.com/hook",
+            "events": ["pr:opened", "repo:refs_changed"],
+            "active": true,
+            "configuration": {"secret": "s3cret"}
+        })))
+        .with_status(201)
+        .with_header("content-type", "application/json")
+        .with_body(CREATED)
+        .create_async()
+        .await;
+
+    let scope = ScopeArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: Some("repo".to_string()),
+    };
+    let create_args = WebhookCreateArgs {
+        name: "jenkins".to_string(),
+        url: "https://ci.example.com/hook".to_string(),
+        event: vec!["repo:refs_changed".to_string(), "pr:opened".to_string()],
+        secret_env: Some("BITBUCKET_SERVER_CLI_TEST_WEBHOOK_SECRET".to_string()),
+        inactive: false,
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&scope, &create_args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_create_webhook_missing_secret_env() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("POST", Matcher::Any)
+        .expect(0)
+        .create_async()
+        .await;
+
+    let scope = ScopeArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: None,
+    };
+    let create_args = WebhookCreateArgs {
+        name: "jenkins".to_string(),
+        url: "https://ci.example.com/hook".to_string(),
+        event: vec!["repo:refs_changed".to_string()],
+        secret_env: Some("BITBUCKET_SERVER_CLI_TEST_UNSET_SECRET".to_string()),
+        inactive: true,
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&scope, &create_args, &client).await;
+    assert!(result.is_err());
+    mock.assert_async().await;
+}
+
+#[test]
+fn test_create_requires_an_event() {
+    let result = Command::command().try_get_matches_from(vec![
+        "bitbucket-server-cli",
+        "webhook",
+        "-k",
+        "TEST",
+        "create",
+        "--name",
+        "jenkins",
+        "--url",
+        "https://ci.example.com/hook",
+    ]);
+    assert!(result.is_err());
+}
@@ -0,0 +1,25 @@
+use bitbucket_server_cli::bitbucket::ScopeArgs;
+use bitbucket_server_cli::cmd::webhook::delete::{handle, WebhookDeleteArgs};
+use bitbucket_server_rs::client;
+use mockito::Server;
+
+#[tokio::test]
+async fn test_delete_webhook() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("DELETE", "/rest/api/latest/projects/TEST/webhooks/3")
+        .with_status(204)
+        .create_async()
+        .await;
+
+    let scope = ScopeArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: None,
+    };
+    let delete_args = WebhookDeleteArgs { id: 3 };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&scope, &delete_args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
@@ -0,0 +1,92 @@
+use bitbucket_server_cli::bitbucket::ScopeArgs;
+use bitbucket_server_cli::cmd::webhook::deliveries::{handle, Outcome, WebhookDeliveriesArgs};
+use bitbucket_server_rs::client;
+use mockito::{Matcher, Server};
+
+#[tokio::test]
+async fn test_deliveries_of_failing_webhook() {
+    let mut server = Server::new_async().await;
+    let statistics_mock = server
+        .mock(
+            "GET",
+            "/rest/api/latest/projects/TEST/repos/repo/webhooks/4/statistics/summary",
+        )
+        .match_query(Matcher::UrlEncoded(
+            "event".into(),
+            "repo:refs_changed".into(),
+        ))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"counts": {"successes": 3, "failures": 5, "errors": 1}}"#)
+        .create_async()
+        .await;
+    let latest_mock = server
+        .mock(
+            "GET",
+            "/rest/api/latest/projects/TEST/repos/repo/webhooks/4/latest",
+        )
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("event".into(), "repo:refs_changed".into()),
+            Matcher::UrlEncoded("outcome".into(), "FAILURE".into()),
+        ]))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"id": 77, "event": "repo:refs_changed",
+                "result": {"outcome": "FAILURE", "description": "403"}}"#,
+        )
+        .create_async()
+        .await;
+
+    let scope = ScopeArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: Some("repo".to_string()),
+    };
+    let deliveries_args = WebhookDeliveriesArgs {
+        id: 4,
+        event: Some("repo:refs_changed".to_string()),
+        outcome: Some(Outcome::Failure),
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&scope, &deliveries_args, &client).await;
+    assert!(result.is_ok());
+    statistics_mock.assert_async().await;
+    latest_mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_deliveries_of_new_webhook() {
+    let mut server = Server::new_async().await;
+    let statistics_mock = server
+        .mock(
+            "GET",
+            "/rest/api/latest/projects/TEST/webhooks/2/statistics/summary",
+        )
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"counts": {}}"#)
+        .create_async()
+        .await;
+    let latest_mock = server
+        .mock("GET", "/rest/api/latest/projects/TEST/webhooks/2/latest")
+        .with_status(204)
+        .create_async()
+        .await;
+
+    let scope = ScopeArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: None,
+    };
+    let deliveries_args = WebhookDeliveriesArgs {
+        id: 2,
+        event: None,
+        outcome: None,
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&scope, &deliveries_args, &client).await;
+    assert!(result.is_ok());
+    statistics_mock.assert_async().await;
+    latest_mock.assert_async().await;
+}
@@ -0,0 +1,59 @@
+use bitbucket_server_cli::bitbucket::ScopeArgs;
+use bitbucket_server_cli::cmd::webhook::get::{handle, WebhookGetArgs};
+use bitbucket_server_rs::client;
+use mockito::{Matcher, Server};
+
+#[tokio::test]
+async fn test_get_webhook_with_statistics() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock(
+            "GET",
+            "/rest/api/latest/projects/TEST/repos/repo/webhooks/4",
+        )
+        .match_query(Matcher::UrlEncoded("statistics".into(), "true".into()))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"id": 4, "name": "jenkins", "url": "https://ci.example.com/hook",
+                "events": ["pr:opened", "repo:refs_changed"], "active": false,
+                "scopeType": "repository",
+                "statistics": {"counts": {"successes": 10, "failures": 2, "errors": 0}}}"#,
+        )
+        .create_async()
+        .await;
+
+    let scope = ScopeArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: Some("repo".to_string()),
+    };
+    let get_args = WebhookGetArgs { id: 4 };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&scope, &get_args, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_get_missing_webhook() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("GET", "/rest/api/latest/projects/TEST/webhooks/9")
+        .match_query(Matcher::Any)
+        .with_status(404)
+        .with_body(r#"{"errors": [{"message": "Webhook 9 does not exist"}]}"#)
+        .create_async()
+        .await;
+
+    let scope = ScopeArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: None,
+    };
+    let get_args = WebhookGetArgs { id: 9 };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&scope, &get_args, &client).await;
+    assert!(result.is_err());
+    mock.assert_async().await;
+}
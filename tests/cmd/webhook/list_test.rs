@@ -0,0 +1,32 @@
+use bitbucket_server_cli::bitbucket::ScopeArgs;
+use bitbucket_server_cli::cmd::webhook::list::handle;
+use bitbucket_server_rs::client;
+use mockito::{Matcher, Server};
+
+#[tokio::test]
+async fn test_list_project_webhooks() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("GET", "/rest/api/latest/projects/TEST/webhooks")
+        .match_query(Matcher::UrlEncoded("start".into(), "0".into()))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"values": [{"id": 1, "name": "jenkins", "url": "https://ci.example.com/hook",
+                "events": ["repo:refs_changed"], "active": true, "scopeType": "project",
+                "configuration": {}}],
+                "isLastPage": true, "start": 0, "size": 1, "limit": 25}"#,
+        )
+        .create_async()
+        .await;
+
+    let scope = ScopeArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: None,
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&scope, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
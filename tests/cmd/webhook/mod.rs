@@ -0,0 +1,8 @@
+mod create_test;
+mod delete_test;
+mod deliveries_test;
+mod get_test;
+mod list_test;
+mod serve_test;
+mod test_test;
+mod update_test;
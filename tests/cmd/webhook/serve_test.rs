@@ -0,0 +1,254 @@
+use bitbucket_server_cli::cmd::webhook::serve::config::HandlersConfig;
+use bitbucket_server_cli::cmd::webhook::serve::{serve, Receiver, Rejection, MAX_BODY_SIZE};
+use bitbucket_server_cli::cmd::Command;
+use bitbucket_server_rs::client;
+use clap::CommandFactory;
+use mockito::{Matcher, Server};
+use ring::hmac;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+
+const REFS_CHANGED: &str = include_str!("payloads/refs_changed.json");
+const PR_OPENED: &str = include_str!("payloads/pr_opened.json");
+
+fn write_config(name: &str, content: &str) -> PathBuf {
+    let file = std::env::temp_dir().join(format!("bitbucket-server-cli-{}.yaml", name));
+    std::fs::write(&file, content).expect("Failed to write handlers");
+    file
+}
+
+fn sign(secret: &str, body: &str) -> String {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    let tag = hmac::sign(&key, body.as_bytes());
+    let hex: String = tag.as_ref().iter().map(|b| format!("{:02x}", b)).collect();
+    format!("sha256={}", hex)
+}
+
+fn receiver(name: &str, handlers: &str, secret: Option<&str>, server_url: &str) -> Receiver {
+    let config = HandlersConfig::load(&write_config(name, handlers)).unwrap();
+    let client = client::new(&format!("{}/rest", server_url), "token");
+    Receiver::new(config, secret, client)
+}
+
+#[test]
+fn test_receive_checks_signature() {
+    let receiver = receiver(
+        "serve-signature",
+        "handlers: []",
+        Some("s3cret"),
+        "http://localhost",
+    );
+
+    let event = receiver
+        .receive(Some(&sign("s3cret", REFS_CHANGED)), REFS_CHANGED.as_bytes())
+        .unwrap();
+    assert_eq!(event.event_key, "repo:refs_changed");
+
+    let wrong = receiver.receive(Some(&sign("other", REFS_CHANGED)), REFS_CHANGED.as_bytes());
+    assert_eq!(wrong.unwrap_err(), Rejection::Signature);
+
+    let missing = receiver.receive(None, REFS_CHANGED.as_bytes());
+    assert_eq!(missing.unwrap_err(), Rejection::Signature);
+}
+
+#[test]
+fn test_refs_changed_env_per_ref() {
+    let receiver = receiver("serve-env", "handlers: []", None, "http://localhost");
+    let event = receiver.receive(None, REFS_CHANGED.as_bytes()).unwrap();
+
+    let envs = event.envs();
+    assert_eq!(envs.len(), 2);
+    assert_eq!(envs[0]["BITBUCKET_PROJECT"], "TEST");
+    assert_eq!(envs[0]["BITBUCKET_REPOSITORY"], "repo");
+    assert_eq!(envs[0]["BITBUCKET_ACTOR"], "jdoe");
+    assert_eq!(envs[0]["BITBUCKET_BRANCH"], "main");
+    assert_eq!(
+        envs[0]["BITBUCKET_COMMIT"],
+        "178864a7d521b6f5e720b386b2c2b0ef8563e0dc"
+    );
+    assert_eq!(envs[1]["BITBUCKET_BRANCH"], "feature/old");
+    assert!(!envs[1].contains_key("BITBUCKET_COMMIT"));
+}
+
+#[tokio::test]
+async fn test_dispatch_runs_matching_commands() {
+    let output = std::env::temp_dir().join("bitbucket-server-cli-serve-run.txt");
+    let _ = std::fs::remove_file(&output);
+    let handlers = format!(
+        r#"
+handlers:
+  - name: deploy
+    events: [repo:refs_changed]
+    repository: TEST/repo
+    branch: main
+    run: echo "$BITBUCKET_BRANCH $BITBUCKET_COMMIT" >> {}
+  - name: other repository
+    events: [repo:refs_changed]
+    repository: TEST/other
+    run: "false"
+  - name: pull requests only
+    events: [pr:opened]
+    run: "false"
+"#,
+        output.display()
+    );
+    let receiver = receiver("serve-run", &handlers, None, "http://localhost");
+    let event = receiver.receive(None, REFS_CHANGED.as_bytes()).unwrap();
+
+    let results = receiver.dispatch(&event).await;
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].handler, "deploy");
+    assert!(results[0].success);
+    assert_eq!(
+        std::fs::read_to_string(&output).unwrap(),
+        "main 178864a7d521b6f5e720b386b2c2b0ef8563e0dc\n"
+    );
+}
+
+#[tokio::test]
+async fn test_dispatch_built_in_actions() {
+    let mut server = Server::new_async().await;
+    let comment_mock = server
+        .mock(
+            "POST",
+            "/rest/api/latest/projects/TEST/repos/repo/pull-requests/12/comments",
+        )
+        .match_body(Matcher::Json(serde_json::json!({
+            "text": "Thanks asmith! Checks will run against main."
+        })))
+        .with_status(201)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"id": 1, "text": "Thanks asmith! Checks will run against main."}"#)
+        .create_async()
+        .await;
+    let build_status_mock = server
+        .mock(
+            "POST",
+            "/rest/api/latest/projects/TEST/repos/repo/commits/9f3c2b1a0e8d7c6b5a4f3e2d1c0b9a8f7e6d5c4b/builds",
+        )
+        .match_body(Matcher::PartialJson(serde_json::json!({
+            "key": "review",
+            "state": "INPROGRESS",
+            "url": "https://ci.example.com/pr/12"
+        })))
+        .with_status(204)
+        .create_async()
+        .await;
+
+    let handlers = r#"
+handlers:
+  - events: [pr:opened]
+    comment: Thanks ${BITBUCKET_PR_AUTHOR}! Checks will run against ${BITBUCKET_BRANCH}.
+  - events: [pr:opened, pr:from_ref_updated]
+    buildStatus:
+      key: review
+      state: INPROGRESS
+      url: https://ci.example.com/pr/${BITBUCKET_PR_ID}
+"#;
+    let receiver = receiver("serve-actions", handlers, None, &server.url());
+    let event = receiver.receive(None, PR_OPENED.as_bytes()).unwrap();
+
+    let results = receiver.dispatch(&event).await;
+    assert_eq!(results.len(), 2);
+    assert!(results.iter().all(|result| result.success), "{:?}", results);
+    comment_mock.assert_async().await;
+    build_status_mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_serve_recorded_payloads() {
+    let receiver = receiver(
+        "serve-http",
+        "handlers: []",
+        Some("s3cret"),
+        "http://localhost",
+    );
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/", listener.local_addr().unwrap());
+    tokio::spawn(serve(listener, Arc::new(receiver)));
+
+    let http = reqwest::Client::new();
+    let signed = http
+        .post(&url)
+        .header("X-Event-Key", "pr:opened")
+        .header("X-Hub-Signature", sign("s3cret", PR_OPENED))
+        .body(PR_OPENED)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(signed.status(), 202);
+
+    let unsigned = http.post(&url).body(PR_OPENED).send().await.unwrap();
+    assert_eq!(unsigned.status(), 401);
+
+    let get = http.get(&url).send().await.unwrap();
+    assert_eq!(get.status(), 405);
+}
+
+#[tokio::test]
+async fn test_serve_rejects_large_bodies() {
+    let receiver = receiver("serve-large", "handlers: []", None, "http://localhost");
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/", listener.local_addr().unwrap());
+    tokio::spawn(serve(listener, Arc::new(receiver)));
+
+    let response = reqwest::Client::new()
+        .post(&url)
+        .body(vec![b' '; MAX_BODY_SIZE + 1])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 413);
+}
+
+#[test]
+fn test_handler_branch_patterns() {
+    let file = write_config(
+        "serve-branches",
+        "handlers:\n  - events: ['*']\n    branch: feature/o?d\n    run: echo old\n  - events: ['*']\n    branch: release/*\n    run: echo release\n",
+    );
+    let config = HandlersConfig::load(&file).unwrap();
+    let env = [
+        ("BITBUCKET_EVENT", "repo:refs_changed".to_string()),
+        ("BITBUCKET_BRANCH", "feature/old".to_string()),
+    ]
+    .into_iter()
+    .collect();
+
+    assert!(config.handlers[0].matches(&env));
+    assert!(!config.handlers[1].matches(&env));
+}
+
+#[test]
+fn test_load_rejects_handlers_without_events() {
+    let file = write_config(
+        "serve-invalid",
+        "handlers:\n  - events: []\n    run: echo hello\n",
+    );
+    assert!(HandlersConfig::load(&file).is_err());
+}
+
+#[test]
+fn test_serve_requires_secret_or_no_verify() {
+    let missing = Command::command().try_get_matches_from(vec![
+        "bitbucket-server-cli",
+        "webhook",
+        "serve",
+        "--config",
+        "handlers.yaml",
+    ]);
+    assert!(missing.is_err());
+
+    let no_verify = Command::command().try_get_matches_from(vec![
+        "bitbucket-server-cli",
+        "webhook",
+        "serve",
+        "--port",
+        "9000",
+        "--no-verify",
+        "--config",
+        "handlers.yaml",
+    ]);
+    assert!(no_verify.is_ok());
+}
@@ -0,0 +1,76 @@
+use bitbucket_server_cli::bitbucket::ScopeArgs;
+use bitbucket_server_cli::cmd::webhook::test::{handle, WebhookTestArgs};
+use bitbucket_server_cli::cmd::Command;
+use bitbucket_server_rs::client;
+use clap::CommandFactory;
+use mockito::{Matcher, Server};
+
+#[tokio::test]
+async fn test_test_existing_webhook() {
+    let mut server = Server::new_async().await;
+    let get_mock = server
+        .mock(
+            "GET",
+            "/rest/api/latest/projects/TEST/repos/repo/webhooks/4",
+        )
+        .match_query(Matcher::Any)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"id": 4, "name": "jenkins", "url": "https://ci.example.com/hook",
+                "events": ["repo:refs_changed"], "active": true, "scopeType": "repository"}"#,
+        )
+        .create_async()
+        .await;
+    let test_mock = server
+        .mock("POST", "/rest/api/latest/projects/TEST/repos/repo/webhooks/test")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("url".into(), "https://ci.example.com/hook".into()),
+            Matcher::UrlEncoded("webhookId".into(), "4".into()),
+            Matcher::UrlEncoded("sslVerificationRequired".into(), "true".into()),
+        ]))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"request": {"url": "https://ci.example.com/hook"}, "response": {"statusCode": 200}}"#)
+        .create_async()
+        .await;
+
+    let scope = ScopeArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: Some("repo".to_string()),
+    };
+    let test_args = WebhookTestArgs {
+        id: Some(4),
+        url: None,
+        insecure: false,
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&scope, &test_args, &client).await;
+    assert!(result.is_ok());
+    get_mock.assert_async().await;
+    test_mock.assert_async().await;
+}
+
+#[test]
+fn test_test_requires_an_id_or_url() {
+    let result = Command::command().try_get_matches_from(vec![
+        "bitbucket-server-cli",
+        "webhook",
+        "-k",
+        "TEST",
+        "test",
+    ]);
+    assert!(result.is_err());
+
+    let result = Command::command().try_get_matches_from(vec![
+        "bitbucket-server-cli",
+        "webhook",
+        "-k",
+        "TEST",
+        "test",
+        "--url",
+        "https://ci.example.com/hook",
+    ]);
+    assert!(result.is_ok());
+}
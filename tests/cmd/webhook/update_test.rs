@@ -0,0 +1,124 @@
+use bitbucket_server_cli::bitbucket::ScopeArgs;
+use bitbucket_server_cli::cmd::webhook::update::{handle, WebhookUpdateArgs};
+use bitbucket_server_rs::client;
+use mockito::{Matcher, Server};
+
+#[tokio::test]
+async fn test_update_events_and_deactivate() {
+    let mut server = Server::new_async().await;
+    let get_mock = server
+        .mock(
+            "GET",
+            "/rest/api/latest/projects/TEST/repos/repo/webhooks/4",
+        )
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"id": 4, "name": "jenkins", "url": "https://ci.example.com/hook",
+                "events": ["pr:opened", "repo:refs_changed"], "active": true,
+                "scopeType": "repository"}"#,
+        )
+        .create_async()
+        .await;
+    let put_mock = server
+        .mock(
+            "PUT",
+            "/rest/api/latest/projects/TEST/repos/repo/webhooks/4",
+        )
+        .match_body(Matcher::Json(serde_json::json!({
+            "id": 4,
+            "name": "jenkins",
+            "url": "https://ci.example.com/hook",
+            "events": ["pr:merged", "repo:refs_changed"],
+            "active": false,
+            "scopeType": "repository"
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"id": 4, "name": "jenkins", "url": "https://ci.example.com/hook",
+                "events": ["pr:merged", "repo:refs_changed"], "active": false,
+                "scopeType": "repository"}"#,
+        )
+        .create_async()
+        .await;
+
+    let scope = ScopeArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: Some("repo".to_string()),
+    };
+    let update_args = WebhookUpdateArgs {
+        id: 4,
+        name: None,
+        url: None,
+        event: vec![],
+        add_event: vec!["pr:merged".to_string()],
+        remove_event: vec!["pr:opened".to_string()],
+        secret_env: None,
+        active: Some(false),
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&scope, &update_args, &client).await;
+    assert!(result.is_ok());
+    get_mock.assert_async().await;
+    put_mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_update_keeps_unmodelled_fields() {
+    let mut server = Server::new_async().await;
+    let _get_mock = server
+        .mock("GET", "/rest/api/latest/projects/TEST/webhooks/9")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"id": 9, "name": "jenkins", "url": "https://ci.example.com/hook",
+                "events": ["repo:refs_changed"], "active": true, "scopeType": "project",
+                "configuration": {"createdBy": "bitbucket"},
+                "sslVerificationRequired": false}"#,
+        )
+        .create_async()
+        .await;
+    let put_mock = server
+        .mock("PUT", "/rest/api/latest/projects/TEST/webhooks/9")
+        .match_body(Matcher::Json(serde_json::json!({
+            "id": 9,
+            "name": "jenkins",
+            "url": "https://ci.example.com/new-hook",
+            "events": ["repo:refs_changed"],
+            "active": true,
+            "scopeType": "project",
+            "configuration": {"createdBy": "bitbucket", "secret": "s3cr3t"},
+            "sslVerificationRequired": false
+        })))
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"id": 9, "name": "jenkins", "url": "https://ci.example.com/new-hook",
+                "events": ["repo:refs_changed"], "active": true, "scopeType": "project"}"#,
+        )
+        .create_async()
+        .await;
+
+    std::env::set_var("BITBUCKET_SERVER_CLI_TEST_WEBHOOK_UPDATE_SECRET", "s3cr3t");
+    let scope = ScopeArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: None,
+    };
+    let update_args = WebhookUpdateArgs {
+        id: 9,
+        name: None,
+        url: Some("https://ci.example.com/new-hook".to_string()),
+        event: vec![],
+        add_event: vec![],
+        remove_event: vec![],
+        secret_env: Some("BITBUCKET_SERVER_CLI_TEST_WEBHOOK_UPDATE_SECRET".to_string()),
+        active: None,
+    };
+
+    let client = client::new(&format!("{}/rest", server.url()), "token");
+    let result = handle(&scope, &update_args, &client).await;
+    assert!(result.is_ok());
+    put_mock.assert_async().await;
+}
@@ -0,0 +1,187 @@
+use bitbucket_server_cli::bitbucket::CommitArgs;
+use bitbucket_server_cli::cmd::build_status::get::{handle, BuildStatusGetArgs};
+use bitbucket_server_cli::cmd::{Command, CommandError};
+use bitbucket_server_cli::config::{Auth, AuthScheme, Config, Settings};
+use bitbucket_server_cli::credentials::StoreKind;
+use clap::{CommandFactory, Parser};
+use mockito::Server;
+
+fn commit_args() -> CommitArgs {
+    CommitArgs {
+        project_key: "TEST".to_string(),
+        repository_slug: "repo".to_string(),
+        commit_id: "abc123".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_basic_auth_header() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("GET", "/rest/api/latest/projects/TEST/repos/repo/commits/abc123/builds")
+        // base64 of "jdoe:s3cr3t"
+        .match_header("authorization", "Basic amRvZTpzM2NyM3Q=")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"state": "SUCCESSFUL", "key": "build-1", "url": "http://example.com/builds/1", "createdDate": 1738198923, "updatedDate": 1738198924}"#)
+        .create_async()
+        .await;
+
+    let config = Config::new(
+        format!("{}/rest", server.url()),
+        Auth::Basic {
+            username: "jdoe".to_string(),
+            password: "s3cr3t".to_string(),
+        },
+    );
+    let client = config.client().unwrap();
+    assert_eq!(client.api_token, "");
+
+    let result = handle(&commit_args(), &BuildStatusGetArgs { key: None }, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_bearer_auth_header() {
+    let mut server = Server::new_async().await;
+    let mock = server
+        .mock("GET", "/rest/api/latest/projects/TEST/repos/repo/commits/abc123/builds")
+        .match_header("authorization", "Bearer my-token")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"state": "SUCCESSFUL", "key": "build-1", "url": "http://example.com/builds/1", "createdDate": 1738198923, "updatedDate": 1738198924}"#)
+        .create_async()
+        .await;
+
+    let config = Config::new(
+        format!("{}/rest", server.url()),
+        Auth::Bearer("my-token".to_string()),
+    );
+    let client = config.client().unwrap();
+
+    let result = handle(&commit_args(), &BuildStatusGetArgs { key: None }, &client).await;
+    assert!(result.is_ok());
+    mock.assert_async().await;
+}
+
+#[test]
+fn test_credentials_are_not_printed() {
+    let basic = Auth::Basic {
+        username: "jdoe".to_string(),
+        password: "s3cr3t".to_string(),
+    };
+    let bearer = Auth::Bearer("my-token".to_string());
+
+    assert_eq!(format!("{:?}", basic), "Basic(jdoe:***)");
+    assert_eq!(format!("{:?}", bearer), "Bearer(***)");
+    assert!(!format!("{:?}", basic.header().unwrap()).contains("amRvZTpzM2NyM3Q"));
+}
+
+#[test]
+fn test_password_from_file() {
+    let file = std::env::temp_dir().join("bitbucket-server-cli-password");
+    std::fs::write(&file, "s3cr3t\n").unwrap();
+
+    let args = Command::parse_from([
+        "bitbucket-server-cli",
+        "--auth",
+        "basic",
+        "--username",
+        "jdoe",
+        "--api-token-file",
+        file.to_str().unwrap(),
+        "repo",
+        "list",
+    ]);
+    assert_eq!(args.server_args.auth, AuthScheme::Basic);
+
+    let config = Config::from_args(&args.server_args).unwrap();
+    match config.auth {
+        Auth::Basic { password, .. } => assert_eq!(password, "s3cr3t"),
+        auth => panic!("Unexpected auth {:?}", auth),
+    }
+}
+
+#[test]
+fn test_invalid_auth_arguments() {
+    let missing = std::env::temp_dir().join("bitbucket-server-cli-missing-password");
+    let args = Command::parse_from([
+        "bitbucket-server-cli",
+        "--api-token-file",
+        missing.to_str().unwrap(),
+        "repo",
+        "list",
+    ]);
+    assert!(matches!(
+        Config::from_args(&args.server_args),
+        Err(CommandError::ArgumentError(_))
+    ));
+
+    for args in [
+        vec!["bitbucket-server-cli", "--auth", "digest", "repo", "list"],
+        vec![
+            "bitbucket-server-cli",
+            "--api-token-file",
+            "token",
+            "--api-token-stdin",
+            "repo",
+            "list",
+        ],
+    ] {
+        assert!(Command::command().try_get_matches_from(args).is_err());
+    }
+}
+
+#[test]
+fn test_token_command() {
+    let settings = Settings {
+        token_command: Some("echo \"token-for-$BITBUCKET_SERVER\"; echo ignored".to_string()),
+        secret_store: None,
+    };
+    let args = Command::parse_from([
+        "bitbucket-server-cli",
+        "--server",
+        "https://bitbucket.example.com/rest",
+        "repo",
+        "list",
+    ]);
+
+    let config = Config::resolve(&args.server_args, &settings).unwrap();
+    assert_eq!(
+        config.auth,
+        Auth::Bearer("token-for-https://bitbucket.example.com/rest".to_string())
+    );
+
+    let settings = Settings {
+        token_command: Some("echo oops >&2; exit 3".to_string()),
+        secret_store: None,
+    };
+    assert!(matches!(
+        Config::resolve(&args.server_args, &settings),
+        Err(CommandError::UnexpectedError(_))
+    ));
+}
+
+#[test]
+fn test_settings_file() {
+    let file = std::env::temp_dir().join("bitbucket-server-cli-config.yaml");
+    std::fs::write(
+        &file,
+        "token_command: pass show bitbucket\nsecret_store: file\n",
+    )
+    .unwrap();
+
+    let settings = Settings::load_from(&file).unwrap();
+    assert_eq!(
+        settings.token_command,
+        Some("pass show bitbucket".to_string())
+    );
+    assert_eq!(settings.secret_store, Some(StoreKind::File));
+
+    std::fs::write(&file, "tokenCommand: pass show bitbucket\n").unwrap();
+    assert!(matches!(
+        Settings::load_from(&file),
+        Err(CommandError::ArgumentError(_))
+    ));
+}
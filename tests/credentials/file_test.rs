@@ -0,0 +1,77 @@
+use bitbucket_server_cli::cmd::CommandError;
+use bitbucket_server_cli::credentials::{FileStore, SecretStore};
+use std::path::PathBuf;
+
+fn store_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir()
+        .join("bitbucket-server-cli-credentials")
+        .join(name);
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+#[test]
+fn test_file_store_round_trip() {
+    let path = store_path("round-trip");
+    let store = FileStore::new(path.clone(), "passphrase");
+
+    assert_eq!(
+        store.get("https://bitbucket.example.com/rest").unwrap(),
+        None
+    );
+    store
+        .set("https://bitbucket.example.com/rest", "token-1")
+        .unwrap();
+    store
+        .set("https://other.example.com/rest", "token-2")
+        .unwrap();
+
+    // a new instance reads what the other one wrote
+    let store = FileStore::new(path.clone(), "passphrase");
+    assert_eq!(
+        store.get("https://bitbucket.example.com/rest").unwrap(),
+        Some("token-1".to_string())
+    );
+    assert_eq!(
+        store.get("https://other.example.com/rest").unwrap(),
+        Some("token-2".to_string())
+    );
+
+    assert!(store.delete("https://bitbucket.example.com/rest").unwrap());
+    assert!(!store.delete("https://bitbucket.example.com/rest").unwrap());
+    assert_eq!(
+        store.get("https://bitbucket.example.com/rest").unwrap(),
+        None
+    );
+    assert_eq!(
+        store.get("https://other.example.com/rest").unwrap(),
+        Some("token-2".to_string())
+    );
+}
+
+#[test]
+fn test_file_store_is_encrypted() {
+    let path = store_path("encrypted");
+    let store = FileStore::new(path.clone(), "passphrase");
+    store
+        .set("https://bitbucket.example.com/rest", "BBDC-very-secret")
+        .unwrap();
+
+    let content = std::fs::read(&path).unwrap();
+    let text = String::from_utf8_lossy(&content);
+    assert!(!text.contains("BBDC-very-secret"));
+    assert!(!text.contains("bitbucket.example.com"));
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    let other = FileStore::new(path.clone(), "another passphrase");
+    assert!(matches!(
+        other.get("https://bitbucket.example.com/rest"),
+        Err(CommandError::UnexpectedError(_))
+    ));
+}
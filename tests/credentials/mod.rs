@@ -0,0 +1 @@
+mod file_test;
@@ -1 +1,4 @@
 mod cmd;
+mod config;
+mod credentials;
+mod output;
@@ -0,0 +1,135 @@
+use bitbucket_server_cli::output::query::Query;
+use serde_json::{json, Value};
+
+fn search(expression: &str, value: &Value) -> Value {
+    Query::parse(expression)
+        .unwrap_or_else(|e| panic!("{}: {}", expression, e))
+        .search(value)
+        .unwrap_or_else(|e| panic!("{}: {}", expression, e))
+}
+
+fn statuses() -> Value {
+    json!({
+        "size": 3,
+        "values": [
+            {"state": "SUCCESSFUL", "key": "build", "duration": 120, "tags": ["ci"]},
+            {"state": "FAILED", "key": "lint", "duration": 30, "tags": ["ci", "style"]},
+            {"state": "FAILED", "key": "e2e", "duration": 900, "tags": []}
+        ]
+    })
+}
+
+#[test]
+fn test_filter_projection() {
+    assert_eq!(
+        search("values[?state==`FAILED`].key", &statuses()),
+        json!(["lint", "e2e"])
+    );
+    assert_eq!(
+        search(
+            "values[?state=='FAILED' && duration > `100`].key",
+            &statuses()
+        ),
+        json!(["e2e"])
+    );
+    assert_eq!(
+        search("values[?!contains(tags, 'ci')].key", &statuses()),
+        json!(["e2e"])
+    );
+}
+
+#[test]
+fn test_fields_indexes_and_slices() {
+    let value = statuses();
+
+    assert_eq!(search("size", &value), json!(3));
+    assert_eq!(search("values[0].key", &value), json!("build"));
+    assert_eq!(search("values[-1].key", &value), json!("e2e"));
+    assert_eq!(search("values[1:].key", &value), json!(["lint", "e2e"]));
+    assert_eq!(
+        search("values[::-1].key", &value),
+        json!(["e2e", "lint", "build"])
+    );
+    assert_eq!(search("missing.field", &value), Value::Null);
+    assert_eq!(search("\"size\"", &value), json!(3));
+}
+
+#[test]
+fn test_projections_and_pipes() {
+    let value = statuses();
+
+    assert_eq!(
+        search("values[*].tags[]", &value),
+        json!(["ci", "ci", "style"])
+    );
+    assert_eq!(search("values[*].key | [0]", &value), json!("build"));
+    assert_eq!(
+        search("values[*].{name: key, ok: state == 'SUCCESSFUL'}", &value),
+        json!([
+            {"name": "build", "ok": true},
+            {"name": "lint", "ok": false},
+            {"name": "e2e", "ok": false}
+        ])
+    );
+    assert_eq!(
+        search("values[0].[key, state]", &value),
+        json!(["build", "SUCCESSFUL"])
+    );
+    assert_eq!(
+        search("*.key", &json!({"a": {"key": 1}, "b": {"key": 2}, "c": {}})),
+        json!([1, 2])
+    );
+}
+
+#[test]
+fn test_functions() {
+    let value = statuses();
+
+    assert_eq!(search("length(values)", &value), json!(3));
+    assert_eq!(
+        search("sort_by(values, &duration)[*].key", &value),
+        json!(["lint", "build", "e2e"])
+    );
+    assert_eq!(
+        search("max_by(values, &duration).key", &value),
+        json!("e2e")
+    );
+    assert_eq!(search("sum(values[*].duration)", &value), json!(1050));
+    assert_eq!(
+        search("join(', ', values[*].key)", &value),
+        json!("build, lint, e2e")
+    );
+    assert_eq!(
+        search("values[?starts_with(key, 'b')].key", &value),
+        json!(["build"])
+    );
+    assert_eq!(search("missing || `\"default\"`", &value), json!("default"));
+}
+
+#[test]
+fn test_legacy_string_literals() {
+    assert_eq!(
+        search("values[?state==`FAILED`].key", &statuses()),
+        json!(["lint", "e2e"])
+    );
+    assert_eq!(
+        search("values[?state==`\"FAILED\"`].key", &statuses()),
+        json!(["lint", "e2e"])
+    );
+    assert_eq!(search("`release/*`", &json!({})), json!("release/*"));
+    assert_eq!(search("` padded`", &json!({})), json!("padded"));
+    assert_eq!(search("`a\\`b`", &json!({})), json!("a`b"));
+    assert_eq!(search("'`raw`'", &json!({})), json!("`raw`"));
+}
+
+#[test]
+fn test_invalid_expressions() {
+    assert!(Query::parse("values[?state==").is_err());
+    assert!(Query::parse("values[").is_err());
+    assert!(Query::parse("a.b)").is_err());
+    assert!(Query::parse("`unterminated").is_err());
+    assert!(Query::parse("nope(values)")
+        .unwrap()
+        .search(&statuses())
+        .is_err());
+}
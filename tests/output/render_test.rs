@@ -0,0 +1,156 @@
+use bitbucket_server_cli::cmd::Command;
+use bitbucket_server_cli::output::{csv_line, render, render_template, OutputFormat};
+use clap::CommandFactory;
+use serde_json::json;
+
+fn statuses() -> serde_json::Value {
+    json!({
+        "size": 2,
+        "isLastPage": true,
+        "values": [
+            {"state": "SUCCESSFUL", "key": "build", "name": "Build", "url": "https://ci/1"},
+            {"state": "FAILED", "key": "lint", "name": "Lint, strict", "url": "https://ci/2"}
+        ]
+    })
+}
+
+#[test]
+fn test_render_table() {
+    let table = render(&statuses(), OutputFormat::Table, &["state", "key", "name"]).unwrap();
+
+    assert_eq!(
+        table,
+        "STATE       KEY    NAME\n\
+         SUCCESSFUL  build  Build\n\
+         FAILED      lint   Lint, strict"
+    );
+}
+
+#[test]
+fn test_render_table_of_nested_fields() {
+    let changes = json!([
+        {"type": "MODIFY", "path": {"toString": "src/main.rs"}},
+        {"type": "ADD", "path": {"toString": "src/lib.rs"}, "labels": ["a", "b"]}
+    ]);
+
+    let table = render(
+        &changes,
+        OutputFormat::Table,
+        &["type", "path.toString", "labels"],
+    )
+    .unwrap();
+
+    assert_eq!(
+        table,
+        "TYPE    PATH.TOSTRING  LABELS\n\
+         MODIFY  src/main.rs\n\
+         ADD     src/lib.rs     a,b"
+    );
+}
+
+#[test]
+fn test_render_csv() {
+    let csv = render(&statuses(), OutputFormat::Csv, &["key", "name"]).unwrap();
+
+    assert_eq!(csv, "key,name\nbuild,Build\nlint,\"Lint, strict\"");
+    assert_eq!(csv_line(&["a\"b", "c"]), "\"a\"\"b\",c");
+}
+
+#[test]
+fn test_render_csv_without_columns() {
+    let csv = render(&json!({"id": 1, "name": "x"}), OutputFormat::Csv, &[]).unwrap();
+
+    assert_eq!(csv, "id,name\n1,x");
+}
+
+#[test]
+fn test_render_ndjson() {
+    let ndjson = render(&statuses(), OutputFormat::Ndjson, &[]).unwrap();
+
+    let lines: Vec<&str> = ndjson.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(lines[1]).unwrap()["key"],
+        "lint"
+    );
+}
+
+#[test]
+fn test_render_json_and_yaml() {
+    let value = json!({"key": "build", "state": "SUCCESSFUL"});
+
+    assert_eq!(
+        render(&value, OutputFormat::Json, &[]).unwrap(),
+        r#"{"key":"build","state":"SUCCESSFUL"}"#
+    );
+    assert_eq!(
+        render(&value, OutputFormat::JsonPretty, &[]).unwrap(),
+        "{\n  \"key\": \"build\",\n  \"state\": \"SUCCESSFUL\"\n}"
+    );
+    assert_eq!(
+        render(&value, OutputFormat::Yaml, &[]).unwrap(),
+        "key: build\nstate: SUCCESSFUL"
+    );
+}
+
+#[test]
+fn test_output_is_global() {
+    let result = Command::command().try_get_matches_from(vec![
+        "bitbucket-server-cli",
+        "branch",
+        "-k",
+        "TEST",
+        "-r",
+        "repo",
+        "list",
+        "--output",
+        "table",
+    ]);
+    assert!(result.is_ok());
+
+    let result = Command::command().try_get_matches_from(vec![
+        "bitbucket-server-cli",
+        "--output",
+        "xml",
+        "branch",
+        "-k",
+        "TEST",
+        "-r",
+        "repo",
+        "list",
+    ]);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_render_template() {
+    let rendered = render_template(
+        &statuses(),
+        "{% for status in values %}{{ status.key }}: {{ status.state }}\n{% endfor %}",
+    )
+    .unwrap();
+
+    assert_eq!(rendered, "build: SUCCESSFUL\nlint: FAILED");
+    assert_eq!(
+        render_template(&json!(["a", "b"]), "{{ data | join(', ') }}").unwrap(),
+        "a, b"
+    );
+}
+
+#[test]
+fn test_template_conflicts_with_output() {
+    let result = Command::command().try_get_matches_from(vec![
+        "bitbucket-server-cli",
+        "--output",
+        "table",
+        "--template",
+        "{{ key }}",
+        "branch",
+        "-k",
+        "TEST",
+        "-r",
+        "repo",
+        "list",
+    ]);
+    assert!(result.is_err());
+}